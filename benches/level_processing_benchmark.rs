@@ -1,11 +1,57 @@
 use base64::{engine::general_purpose::URL_SAFE, Engine};
 use criterion::{criterion_group, criterion_main, Criterion};
 use dash_rs::{
-    model::level::{Level, LevelData},
+    model::level::{Level, LevelData, LevelObjectBuffer},
     GJFormat, Thunk,
 };
 use flate2::read::GzDecoder;
-use std::{fs::read_to_string, io::Read};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fs::read_to_string,
+    io::Read,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Allocator keeping track of the peak amount of memory allocated at once, used by
+/// [`peak_memory_benchmark`]
+struct PeakAllocator {
+    current: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+
+        if !ptr.is_null() {
+            let current = self.current.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            self.peak.fetch_max(current, Ordering::Relaxed);
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        self.current.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator {
+    current: AtomicUsize::new(0),
+    peak: AtomicUsize::new(0),
+};
+
+/// Returns how many bytes the given closure allocated at its peak, on top of what was already
+/// allocated when it was called
+fn peak_memory_of(f: impl FnOnce()) -> usize {
+    let baseline = ALLOCATOR.current.load(Ordering::Relaxed);
+
+    ALLOCATOR.peak.store(baseline, Ordering::Relaxed);
+    f();
+    ALLOCATOR.peak.load(Ordering::Relaxed) - baseline
+}
 
 pub fn ocular_miracle_benchmark(c: &mut Criterion) {
     let response = read_to_string("./benches/data/62152040_ocular_miracle_gjdownload_response").unwrap();
@@ -31,6 +77,48 @@ pub fn spacial_rend_benchmark(c: &mut Criterion) {
     });
 }
 
+pub fn buffered_spacial_rend_benchmark(c: &mut Criterion) {
+    let response = read_to_string("./benches/data/40292513_special_rend_gjdownload_response").unwrap();
+    let mut buffer = LevelObjectBuffer::new();
+
+    c.bench_function("parse spacial rend into reused buffer", |b| {
+        b.iter(|| {
+            let level: Level<LevelData> = Level::from_gj_str(&response).unwrap();
+
+            level.level_data.parse_objects_into(&mut buffer).unwrap();
+        })
+    });
+}
+
+/// Compares the peak memory usage of processing level data via `Thunk::process` and via a
+/// (warmed up) `LevelObjectBuffer`. Criterion does not measure memory, so the results are printed.
+pub fn peak_memory_benchmark(_: &mut Criterion) {
+    let response = read_to_string("./benches/data/62152040_ocular_miracle_gjdownload_response").unwrap();
+    let mut buffer = LevelObjectBuffer::new();
+
+    let processed = peak_memory_of(|| {
+        let mut level: Level<LevelData> = Level::from_gj_str(&response).unwrap();
+
+        level.level_data.level_data.process().unwrap();
+    });
+
+    let level: Level<LevelData> = Level::from_gj_str(&response).unwrap();
+
+    level.level_data.parse_objects_into(&mut buffer).unwrap();
+
+    let buffered = peak_memory_of(|| {
+        let level: Level<LevelData> = Level::from_gj_str(&response).unwrap();
+
+        level.level_data.parse_objects_into(&mut buffer).unwrap();
+    });
+
+    println!("peak memory processing ocular miracle: {} bytes via Thunk::process", processed);
+    println!(
+        "peak memory processing ocular miracle: {} bytes via a reused LevelObjectBuffer",
+        buffered
+    );
+}
+
 pub fn decoding_ocular_miracle_benchmark(c: &mut Criterion) {
     let response = read_to_string("./benches/data/62152040_ocular_miracle_gjdownload_response").unwrap();
 
@@ -75,6 +163,8 @@ criterion_group!(
     benches,
     ocular_miracle_benchmark,
     spacial_rend_benchmark,
+    buffered_spacial_rend_benchmark,
+    peak_memory_benchmark,
     decoding_spacial_rend_benchmark,
     decoding_ocular_miracle_benchmark
 );
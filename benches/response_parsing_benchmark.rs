@@ -0,0 +1,25 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use dash_rs::response::{parse_download_gj_level_response, parse_get_gj_levels_response};
+use std::fs::read_to_string;
+
+pub fn level_search_benchmark(c: &mut Criterion) {
+    // FIXME: This is not a real capture, but a response containing 10 levels repeated 5 times, whose
+    // creator and song sections only cover those 10 levels. It thus likely understates the cost of
+    // parsing a real 50 level response. Replace it with an actual getGJLevels21 capture.
+    let response = read_to_string("./benches/data/get_gj_levels_50_response").unwrap();
+
+    c.bench_function("parse 50 level search response", |b| {
+        b.iter(|| parse_get_gj_levels_response(&response).unwrap())
+    });
+}
+
+pub fn level_download_benchmark(c: &mut Criterion) {
+    let response = read_to_string("./benches/data/40292513_special_rend_gjdownload_response").unwrap();
+
+    c.bench_function("parse 2MB level download response", |b| {
+        b.iter(|| parse_download_gj_level_response(&response).unwrap())
+    });
+}
+
+criterion_group!(benches, level_search_benchmark, level_download_benchmark);
+criterion_main!(benches);
@@ -20,6 +20,10 @@ pub enum FieldMapping {
     ///
     /// These get initialized to [`Default::default`] during deserialization.
     NoIndex { field: Ident },
+
+    /// The API field into which indices not mapped to any other field are captured. Has no
+    /// corresponding internal field, but is exposed via `Dash::unknown_indices`.
+    UnknownIndices { field: Ident },
 }
 
 pub enum LitIndex {
@@ -125,28 +129,28 @@ impl FieldMapping {
     pub fn ser_field_tokens(&self, lifetime: &Lifetime) -> TokenStream {
         match self {
             FieldMapping::OneToOne(inner) => inner.ser_field_tokens(lifetime),
-            FieldMapping::NoIndex { .. } => quote!(),
+            FieldMapping::NoIndex { .. } | FieldMapping::UnknownIndices { .. } => quote!(),
         }
     }
 
     pub fn de_field_tokens(&self) -> TokenStream {
         match self {
             FieldMapping::OneToOne(inner) => inner.de_field_tokens(),
-            FieldMapping::NoIndex { .. } => quote!(),
+            FieldMapping::NoIndex { .. } | FieldMapping::UnknownIndices { .. } => quote!(),
         }
     }
 
     pub fn serialize(&self) -> TokenStream {
         match self {
             FieldMapping::OneToOne(inner) => inner.serialize(),
-            FieldMapping::NoIndex { .. } => quote!(),
+            FieldMapping::NoIndex { .. } | FieldMapping::UnknownIndices { .. } => quote!(),
         }
     }
 
     pub fn deserialize(&self) -> TokenStream {
         match self {
             FieldMapping::OneToOne(inner) => inner.deserialize(),
-            FieldMapping::NoIndex { field } => quote! {
+            FieldMapping::NoIndex { field } | FieldMapping::UnknownIndices { field } => quote! {
                 #field: Default::default(),
             },
         }
@@ -162,6 +166,7 @@ enum FieldMappingBuilder {
         passthrough: Vec<TokenStream>,
     },
     NoIndex,
+    UnknownIndices,
 }
 
 impl FieldMappingBuilder {
@@ -192,6 +197,14 @@ impl FieldMappingBuilder {
         true
     }
 
+    fn unknown_indices(&mut self) -> bool {
+        match std::mem::take(self) {
+            FieldMappingBuilder::Initial => *self = FieldMappingBuilder::UnknownIndices,
+            _ => return false,
+        }
+        true
+    }
+
     fn with_passthrough(&mut self, tokens: TokenStream) -> bool {
         match std::mem::take(self) {
             FieldMappingBuilder::Initial => {
@@ -204,7 +217,7 @@ impl FieldMappingBuilder {
                 passthrough.push(tokens);
                 *self = FieldMappingBuilder::OneToOne { index, passthrough }
             },
-            FieldMappingBuilder::NoIndex => return false,
+            FieldMappingBuilder::NoIndex | FieldMappingBuilder::UnknownIndices => return false,
         }
         true
     }
@@ -232,6 +245,7 @@ impl TryFrom<Field> for FieldMapping {
                 DashAttribute::Index(idx) => builder.with_index(idx),
                 DashAttribute::PassthroughToSerde(tokens) => builder.with_passthrough(tokens),
                 DashAttribute::NoIndex => builder.no_index(),
+                DashAttribute::UnknownIndices => builder.unknown_indices(),
             };
 
             if !build_success {
@@ -254,6 +268,7 @@ impl TryFrom<Field> for FieldMapping {
             })),
             FieldMappingBuilder::OneToOne { index: None, .. } => Err(Error::new_spanned(field, "missing #[dash(index = ...)] attribute")),
             FieldMappingBuilder::NoIndex => Ok(FieldMapping::NoIndex { field }),
+            FieldMappingBuilder::UnknownIndices => Ok(FieldMapping::UnknownIndices { field }),
         }
     }
 }
@@ -261,6 +276,7 @@ impl TryFrom<Field> for FieldMapping {
 enum DashAttribute {
     Index(LitIndex),
     NoIndex,
+    UnknownIndices,
     PassthroughToSerde(TokenStream),
 }
 
@@ -274,6 +290,11 @@ impl Parse for DashAttribute {
 
                 return Ok(DashAttribute::NoIndex);
             }
+            if key == "unknown_indices" {
+                input.advance_to(&fork);
+
+                return Ok(DashAttribute::UnknownIndices);
+            }
             if key == "index" {
                 let _ = fork.parse::<Token![=]>()?;
                 let lookahead = fork.lookahead1();
@@ -66,6 +66,35 @@ impl InternalStruct {
         }
     }
 
+    fn unknown_indices_implementation(&self) -> proc_macro2::TokenStream {
+        let unknown_indices_field = self.fields.iter().find_map(|ifield| match ifield {
+            FieldMapping::UnknownIndices { field } => Some(field),
+            _ => None,
+        });
+
+        let Some(field) = unknown_indices_field else {
+            return quote! {};
+        };
+
+        let lifetime = match self.lifetime {
+            Some(ref lifetime) => &lifetime.lifetime,
+            None => {
+                return syn::Error::new_spanned(field, "#[dash(unknown_indices)] requires the struct to have a lifetime")
+                    .into_compile_error()
+            },
+        };
+
+        quote! {
+            fn unknown_indices(&self) -> Option<&crate::serde::UnknownIndices<#lifetime>> {
+                Some(&self.#field)
+            }
+
+            fn unknown_indices_mut(&mut self) -> Option<&mut crate::serde::UnknownIndices<#lifetime>> {
+                Some(&mut self.#field)
+            }
+        }
+    }
+
     fn deserialize_implementation(&self) -> proc_macro2::TokenStream {
         // assume a `Deserializer` is in scope, named deserializer
         let deserialize_struct = self.deserialize_struct_name();
@@ -98,6 +127,7 @@ impl ToTokens for InternalStruct {
 
         let deserialize_impl = self.deserialize_implementation();
         let serialize_impl = self.serialize_implementation();
+        let unknown_indices_impl = self.unknown_indices_implementation();
 
         tokens.extend(quote! {
             const _: () = {
@@ -118,6 +148,8 @@ impl ToTokens for InternalStruct {
                     fn dash_serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
                         #serialize_impl
                     }
+
+                    #unknown_indices_impl
                 }
             };
         })
@@ -0,0 +1,111 @@
+//! Python bindings for the parsing layer of dash-rs
+//!
+//! Parsed objects are handed to Python as the same plain dictionaries/lists `dash-rs` produces when
+//! serializing its models to JSON. Building this crate with
+//! [maturin](https://github.com/PyO3/maturin) yields a Python module called `dash_rs`.
+//!
+//! These bindings live in their own crate, since pyo3's macros generate unsafe code (which dash-rs
+//! forbids) and an extension module has to be built as a `cdylib`.
+
+use dash_rs::response::{self, ResponseError};
+use pyo3::{exceptions::PyValueError, prelude::*};
+use serde::Serialize;
+
+fn to_python<T: Serialize>(py: Python<'_>, value: &T) -> PyResult<PyObject> {
+    let json = serde_json::to_string(value).map_err(|err| PyValueError::new_err(err.to_string()))?;
+
+    Ok(py.import_bound("json")?.call_method1("loads", (json,))?.unbind())
+}
+
+fn response_error(err: ResponseError<'_>) -> PyErr {
+    PyValueError::new_err(err.to_string())
+}
+
+/// Parses a `downloadGJLevel22.php` response into a dictionary
+#[pyfunction]
+fn parse_level(py: Python<'_>, response: &str) -> PyResult<PyObject> {
+    to_python(py, &response::parse_download_gj_level_response(response).map_err(response_error)?)
+}
+
+/// Parses a `getGJLevels21.php` response into a list of dictionaries
+#[pyfunction]
+fn parse_levels(py: Python<'_>, response: &str) -> PyResult<PyObject> {
+    to_python(py, &response::parse_get_gj_levels_response(response).map_err(response_error)?)
+}
+
+/// Parses a `getGJUserInfo20.php` response into a dictionary
+#[pyfunction]
+fn parse_profile(py: Python<'_>, response: &str) -> PyResult<PyObject> {
+    to_python(py, &response::parse_get_gj_user_info_response(response).map_err(response_error)?)
+}
+
+/// Parses a `downloadGJLevel22.php` response and analyzes the level's objects, see
+/// [`LevelMetadata::analysis`](dash_rs::model::level::metadata::LevelMetadata::analysis)
+#[pyfunction]
+fn analyze_level(py: Python<'_>, response: &str) -> PyResult<PyObject> {
+    let mut level = response::parse_download_gj_level_response(response).map_err(response_error)?;
+    let objects = level
+        .level_data
+        .level_data
+        .process()
+        .map_err(|err| PyValueError::new_err(err.to_string()))?;
+
+    to_python(py, &objects.analysis())
+}
+
+#[pymodule]
+#[pyo3(name = "dash_rs")]
+fn dash_rs_module(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(parse_level, module)?)?;
+    module.add_function(wrap_pyfunction!(parse_levels, module)?)?;
+    module.add_function(wrap_pyfunction!(parse_profile, module)?)?;
+    module.add_function(wrap_pyfunction!(analyze_level, module)?)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{analyze_level, parse_level, parse_profile};
+    use pyo3::{exceptions::PyValueError, prelude::*};
+
+    const LEVEL: &str = include_str!("../../tests/artifacts/level/897837/raw");
+    const PROFILE: &str = include_str!("../../tests/artifacts/profile/8451/raw");
+
+    #[test]
+    fn parses_into_dictionaries() {
+        Python::with_gil(|py| {
+            let level = parse_level(py, LEVEL.trim()).unwrap();
+            let profile = parse_profile(py, PROFILE.trim()).unwrap();
+
+            assert_eq!(
+                level.bind(py).get_item("name").unwrap().extract::<String>().unwrap(),
+                "time pressure"
+            );
+            assert_eq!(level.bind(py).get_item("level_id").unwrap().extract::<u64>().unwrap(), 897837);
+            assert_eq!(
+                profile.bind(py).get_item("name").unwrap().extract::<String>().unwrap(),
+                "stardust1971"
+            );
+        })
+    }
+
+    #[test]
+    fn analyzes_level() {
+        Python::with_gil(|py| {
+            let analysis = analyze_level(py, LEVEL.trim()).unwrap();
+
+            assert!(analysis.bind(py).get_item("length_in_seconds").unwrap().extract::<f64>().unwrap() > 0.0);
+        })
+    }
+
+    #[test]
+    fn malformed_responses_raise_value_error() {
+        Python::with_gil(|py| {
+            assert!(parse_level(py, "-1").unwrap_err().is_instance_of::<PyValueError>(py));
+            assert!(parse_profile(py, "1:stadust:2:not a number")
+                .unwrap_err()
+                .is_instance_of::<PyValueError>(py));
+        })
+    }
+}
@@ -0,0 +1,373 @@
+//! Module containing a blocking client for Geometry Dash servers, for use without an async runtime.
+//! Only available with the `blocking` feature.
+//!
+//! [`Client`] mirrors [`http::Client`](crate::http::Client) (which it is built upon), making
+//! requests via [`ureq`] on the current thread. Additionally, it provides methods that directly
+//! parse the responses of common endpoints into the usual models. As the response bodies do not
+//! outlive these methods, the returned models own their data.
+//!
+//! ```no_run
+//! use dash_rs::{blocking::Client, request::level::LevelRequest};
+//!
+//! let client = Client::default();
+//! let level = client.level(&LevelRequest::new(76298358))?;
+//!
+//! println!("{} has {} likes", level.name, level.likes);
+//! # Ok::<(), dash_rs::blocking::Error>(())
+//! ```
+
+use crate::{
+    cache::Cache,
+    http::{self, ClientError},
+    middleware::Middleware,
+    model::{
+        comment::{level::LevelComment, profile::ProfileComment},
+        level::{Level, ListedLevel},
+        user::{profile::Profile, searched::SearchedUser},
+    },
+    request::{
+        comment::{DeleteProfileCommentRequest, LevelCommentsRequest, ProfileCommentsRequest},
+        level::{LevelRequest, LevelsRequest},
+        user::{UserRequest, UserSearchRequest},
+        Request, ServerConfig,
+    },
+    response::{self, DeletionOutcome, ResponseError},
+};
+use std::{
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+use thiserror::Error;
+
+/// Error returned by the methods of [`Client`]
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The request could not be made, see [`ClientError`]
+    #[error("{0}")]
+    Client(#[from] ClientError<ureq::Error>),
+
+    /// The server responded with `"-1"`, see [`ResponseError::NotFound`]
+    #[error("not found")]
+    NotFound,
+
+    /// The response could not be parsed. Since the [`ResponseError`] borrows from the response,
+    /// only its message is retained.
+    #[error("{0}")]
+    Response(String),
+}
+
+impl From<ResponseError<'_>> for Error {
+    fn from(error: ResponseError<'_>) -> Self {
+        match error {
+            ResponseError::NotFound => Error::NotFound,
+            error => Error::Response(error.to_string()),
+        }
+    }
+}
+
+/// Makes blocking requests to the server described by a [`ServerConfig`]
+#[derive(Debug, Clone)]
+pub struct Client<'a> {
+    inner: http::Client<'a, ureq::Agent>,
+}
+
+impl Default for Client<'static> {
+    /// Constructs a client making requests to the official servers
+    fn default() -> Self {
+        Client::new(ServerConfig::default())
+    }
+}
+
+impl<'a> Client<'a> {
+    /// Constructs a new `Client` making requests to the given server, using a [`ureq::Agent`]
+    /// configured to not send a `User-Agent` header
+    pub fn new(server: ServerConfig<'a>) -> Self {
+        Client::with_agent(ureq::AgentBuilder::new().user_agent("").build(), server)
+    }
+
+    /// Constructs a new `Client` making requests to the given server using the given
+    /// [`ureq::Agent`]. See the notes on the [`HttpBackend`](crate::http::HttpBackend)
+    /// implementation of [`ureq::Agent`] regarding the `User-Agent` header.
+    pub fn with_agent(agent: ureq::Agent, server: ServerConfig<'a>) -> Self {
+        Client {
+            inner: http::Client::new(agent, server),
+        }
+    }
+
+    /// Caches the responses to requests made by this client in the given [`Cache`], see
+    /// [`http::Client::cache`](crate::http::Client::cache)
+    pub fn cache(self, cache: impl Cache + 'static) -> Self {
+        Client {
+            inner: self.inner.cache(cache),
+        }
+    }
+
+    /// Makes requests through the given [`Middleware`], blocking the current thread while waiting
+    /// between attempts. See [`http::Client::middleware`](crate::http::Client::middleware).
+    pub fn middleware(self, middleware: Middleware) -> Self {
+        Client {
+            inner: self.inner.middleware(middleware, |duration| {
+                thread::sleep(duration);
+
+                Box::pin(std::future::ready(()))
+            }),
+        }
+    }
+
+    /// The [`ureq::Agent`] used by this client
+    pub fn agent(&self) -> &ureq::Agent {
+        self.inner.backend()
+    }
+
+    /// The [`ServerConfig`] describing the server this client makes requests to
+    pub fn server(&self) -> &ServerConfig<'a> {
+        self.inner.server()
+    }
+
+    /// Sends the given request to the endpoint it is meant for, returning the body of the response.
+    /// See [`http::Client::send`](crate::http::Client::send).
+    pub fn send<'b, R: Request<'b>>(&self, request: &R) -> Result<String, ClientError<ureq::Error>> {
+        // ureq's HttpBackend implementation performs the request before returning an already
+        // completed future
+        block_on(self.inner.send(request))
+    }
+
+    /// Downloads a level
+    pub fn level(&self, request: &LevelRequest<'_>) -> Result<Level<'static>, Error> {
+        let body = self.send(request)?;
+
+        Ok(response::parse_download_gj_level_response_with_quirks(&body, self.server().quirks())?.into_owned())
+    }
+
+    /// Searches for levels
+    pub fn levels(&self, request: &LevelsRequest<'_>) -> Result<Vec<ListedLevel<'static>>, Error> {
+        let body = self.send(request)?;
+
+        // The server's quirks carry its compatibility profile. They do not enable inline level data,
+        // so dropping it loses nothing.
+        Ok(response::parse_get_gj_levels_response_with_quirks(&body, self.server().quirks())?
+            .into_iter()
+            .map(|level| level.map_data(|_| ()).into_owned())
+            .collect())
+    }
+
+    /// Retrieves a user's profile
+    pub fn profile(&self, request: &UserRequest<'_>) -> Result<Profile<'static>, Error> {
+        let body = self.send(request)?;
+
+        Ok(response::parse_get_gj_user_info_response(&body)?.into_owned())
+    }
+
+    /// Searches for a user
+    pub fn search_user(&self, request: &UserSearchRequest<'_>) -> Result<SearchedUser<'static>, Error> {
+        let body = self.send(request)?;
+
+        Ok(response::parse_get_gj_users_response(&body)?.into_owned())
+    }
+
+    /// Retrieves a page of a level's comments
+    pub fn level_comments(&self, request: &LevelCommentsRequest<'_>) -> Result<Vec<LevelComment<'static>>, Error> {
+        let body = self.send(request)?;
+
+        Ok(response::parse_get_gj_comments_response(&body)?
+            .into_iter()
+            .map(LevelComment::into_owned)
+            .collect())
+    }
+
+    /// Retrieves a page of a user's profile comments
+    pub fn profile_comments(&self, request: &ProfileCommentsRequest<'_>) -> Result<Vec<ProfileComment<'static>>, Error> {
+        let body = self.send(request)?;
+
+        Ok(response::parse_get_gj_acccount_comments_response(&body)?
+            .into_iter()
+            .map(ProfileComment::into_owned)
+            .collect())
+    }
+
+    /// Deletes the given profile comments of the account authenticated in `request`, one request at
+    /// a time (see [`DeleteProfileCommentRequest::batch`]). Returns the outcome of each deletion,
+    /// in the order the comment IDs were given in.
+    ///
+    /// Deletions rejected by the server do not stop the batch, but the first request that fails
+    /// outright (see [`Error`]) does.
+    pub fn delete_profile_comments<I>(
+        &self, request: DeleteProfileCommentRequest<'_>, comment_ids: I,
+    ) -> Result<Vec<DeletionOutcome>, Error>
+    where
+        I: IntoIterator<Item = u64>,
+    {
+        request
+            .batch(comment_ids)
+            .map(|request| {
+                let body = self.send(&request)?;
+
+                Ok(response::parse_delete_gj_acc_comment_response(&body)?)
+            })
+            .collect()
+    }
+}
+
+/// Waker unparking the thread that is blocked on the future
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark()
+    }
+}
+
+/// Drives the given future to completion on the current thread, parking the thread until the
+/// future signals that it can make progress
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context = Context::from_waker(&waker);
+    let mut future = pin!(future);
+
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            // Spurious wake-ups merely cause another poll
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::{Client, Error};
+    use crate::{
+        cache::InMemoryCache,
+        model::{level::Level, user::profile::Profile},
+        request::{
+            comment::{DeleteProfileCommentRequest, DELETE_PROFILE_COMMENT_ENDPOINT},
+            level::{LevelRequest, LevelsRequest},
+            user::{UserRequest, GET_USER_ENDPOINT},
+            AuthenticatedUser,
+        },
+        response::{ActionOutcome, Paged},
+        testing::MockServer,
+        GJFormat,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn profile() {
+        let server = MockServer::start().unwrap();
+        let client = Client::new(server.server_config());
+
+        server.respond(
+            GET_USER_ENDPOINT,
+            200,
+            include_str!("../tests/artifacts/profile/8451/raw").trim_end(),
+        );
+
+        let profile = client.profile(&UserRequest::new(8451)).unwrap();
+
+        assert_eq!(profile.user_id, 2073761);
+        assert_eq!(profile.account_id, 8451);
+
+        let requests = server.requests();
+
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].endpoint, GET_USER_ENDPOINT);
+        assert_eq!(
+            requests[0].body,
+            server.server_config().build_request(&UserRequest::new(8451)).unwrap()
+        );
+    }
+
+    #[test]
+    fn cached_profile() {
+        let server = MockServer::start().unwrap();
+        let client = Client::new(server.server_config()).cache(InMemoryCache::new(10, Duration::from_secs(60)));
+
+        server.respond(
+            GET_USER_ENDPOINT,
+            200,
+            include_str!("../tests/artifacts/profile/8451/raw").trim_end(),
+        );
+
+        let profile = client.profile(&UserRequest::new(8451)).unwrap();
+
+        assert_eq!(client.profile(&UserRequest::new(8451)).unwrap(), profile);
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn delete_profile_comments() {
+        let server = MockServer::start().unwrap();
+        let client = Client::new(server.server_config());
+        let request = DeleteProfileCommentRequest::new(AuthenticatedUser::new(1710032, "hash"), 0);
+
+        server.respond(DELETE_PROFILE_COMMENT_ENDPOINT, 200, "1");
+
+        assert_eq!(
+            client.delete_profile_comments(request, vec![1, 2]).unwrap(),
+            vec![ActionOutcome::Done; 2]
+        );
+
+        server.respond(DELETE_PROFILE_COMMENT_ENDPOINT, 200, "-1");
+
+        assert_eq!(
+            client.delete_profile_comments(request, vec![3]).unwrap(),
+            vec![ActionOutcome::Rejected(-1)]
+        );
+
+        let requests = server.requests();
+
+        assert_eq!(requests.len(), 3);
+        assert!(requests[1].body.contains("commentID=2"));
+    }
+
+    #[test]
+    fn not_found() {
+        let server = MockServer::start().unwrap();
+        let client = Client::new(server.server_config());
+
+        assert!(matches!(client.profile(&UserRequest::new(8451)), Err(Error::NotFound)));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn canned_responses_round_trip() {
+        let server = MockServer::start().unwrap();
+        let client = Client::new(server.server_config());
+
+        let profile = Profile::from_gj_str(include_str!("../tests/artifacts/profile/8451/raw").trim_end()).unwrap();
+
+        server.respond_with_profile(&profile).unwrap();
+
+        assert_eq!(client.profile(&UserRequest::new(profile.account_id)).unwrap(), profile);
+
+        let level: Level = Level::from_gj_str(include_str!("../tests/artifacts/level/897837/raw").trim_end()).unwrap();
+
+        server.respond_with_level(&level).unwrap();
+
+        assert_eq!(client.level(&LevelRequest::new(level.level_id)).unwrap().level_id, level.level_id);
+
+        let listed = Level::from_gj_str(include_str!("../tests/artifacts/listed_level/11774780/raw").trim_end()).unwrap();
+        let page = Paged {
+            items: vec![listed],
+            total: 1,
+            offset: 0,
+            page_size: 10,
+        };
+
+        server.respond_with_levels(&page, &[], &[]).unwrap();
+
+        let levels = client.levels(&LevelsRequest::new()).unwrap();
+
+        assert_eq!(levels.len(), 1);
+        assert_eq!(levels[0].level_id, page.items[0].level_id);
+        assert_eq!(levels[0].name, page.items[0].name);
+        assert_eq!(server.requests().len(), 3);
+    }
+}
@@ -0,0 +1,215 @@
+//! Module containing a caching layer for raw server responses. Only available with the `std`
+//! feature.
+//!
+//! Responses are cached by the [`Hash`] of the request that produced them (all request structs in
+//! [`request`](crate::request) derive it), so that repeated identical requests within some
+//! time-to-live do not hit the servers again. Caches are plugged into a
+//! [`Client`](crate::http::Client) via [`Client::cache`](crate::http::Client::cache), or into a
+//! [`Middleware`](crate::middleware::Middleware) via
+//! [`Middleware::cache`](crate::middleware::Middleware::cache).
+
+use std::{
+    any::type_name,
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// The key under which a response is cached
+pub type CacheKey = u64;
+
+/// Computes the [`CacheKey`] of the given request.
+///
+/// The type of the request is included in the hash, so that two different kinds of request with
+/// the same field values do not share a cache entry.
+pub fn cache_key<R: Hash + ?Sized>(request: &R) -> CacheKey {
+    let mut hasher = DefaultHasher::new();
+
+    type_name::<R>().hash(&mut hasher);
+    request.hash(&mut hasher);
+
+    hasher.finish()
+}
+
+/// Trait describing a storage backend for cached responses
+///
+/// Implementations are responsible for expiring entries themselves.
+pub trait Cache: Send + Sync {
+    /// Retrieves the response cached under the given key, if it exists and has not expired
+    fn get(&self, key: CacheKey) -> Option<String>;
+
+    /// Caches the given response under the given key, replacing any existing entry
+    fn insert(&self, key: CacheKey, response: String);
+
+    /// Removes the entry for the given key, if it exists
+    fn remove(&self, key: CacheKey);
+}
+
+#[derive(Debug)]
+struct Entry {
+    response: String,
+    inserted: Instant,
+    last_used: u64,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    entries: HashMap<CacheKey, Entry>,
+
+    /// Monotonically increasing counter used to determine the least recently used entry
+    clock: u64,
+}
+
+/// An in-memory [`Cache`] that evicts the least recently used entry once it is full, and treats
+/// entries older than its time-to-live as absent
+#[derive(Debug)]
+pub struct InMemoryCache {
+    capacity: usize,
+    ttl: Duration,
+    entries: Mutex<Entries>,
+}
+
+impl InMemoryCache {
+    /// Constructs a new, empty `InMemoryCache` holding at most `capacity` responses for at most
+    /// `ttl` each
+    pub fn new(capacity: usize, ttl: Duration) -> InMemoryCache {
+        InMemoryCache {
+            capacity,
+            ttl,
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    /// The amount of entries currently cached, including expired ones that have not been evicted
+    /// yet
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Whether this cache contains no entries
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Retrieves the response cached under the given key, treating `now` as the current point in
+    /// time
+    pub fn get_at(&self, key: CacheKey, now: Instant) -> Option<String> {
+        let mut entries = self.lock();
+
+        entries.clock += 1;
+
+        let clock = entries.clock;
+        let expired = match entries.entries.get_mut(&key) {
+            Some(entry) if now.saturating_duration_since(entry.inserted) < self.ttl => {
+                entry.last_used = clock;
+
+                return Some(entry.response.clone());
+            },
+            Some(_) => true,
+            None => false,
+        };
+
+        if expired {
+            entries.entries.remove(&key);
+        }
+
+        None
+    }
+
+    /// Caches the given response under the given key, treating `now` as the current point in time
+    pub fn insert_at(&self, key: CacheKey, response: String, now: Instant) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut entries = self.lock();
+
+        entries.clock += 1;
+
+        if !entries.entries.contains_key(&key) && entries.entries.len() >= self.capacity {
+            // Prefer evicting expired entries, and fall back to the least recently used one
+            let ttl = self.ttl;
+            let victim = entries
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| (now.saturating_duration_since(entry.inserted) < ttl, entry.last_used))
+                .map(|(key, _)| *key);
+
+            if let Some(victim) = victim {
+                entries.entries.remove(&victim);
+            }
+        }
+
+        let clock = entries.clock;
+
+        entries.entries.insert(
+            key,
+            Entry {
+                response,
+                inserted: now,
+                last_used: clock,
+            },
+        );
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Cache for InMemoryCache {
+    fn get(&self, key: CacheKey) -> Option<String> {
+        self.get_at(key, Instant::now())
+    }
+
+    fn insert(&self, key: CacheKey, response: String) {
+        self.insert_at(key, response, Instant::now())
+    }
+
+    fn remove(&self, key: CacheKey) {
+        self.lock().entries.remove(&key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cache_key, InMemoryCache};
+    use crate::request::{level::LevelRequest, user::UserRequest};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn keys_depend_on_request_type() {
+        assert_eq!(cache_key(&UserRequest::new(1)), cache_key(&UserRequest::new(1)));
+        assert_ne!(cache_key(&UserRequest::new(1)), cache_key(&UserRequest::new(2)));
+        assert_ne!(cache_key(&UserRequest::new(1)), cache_key(&LevelRequest::new(1)));
+    }
+
+    #[test]
+    fn entries_expire() {
+        let cache = InMemoryCache::new(10, Duration::from_secs(60));
+        let now = Instant::now();
+
+        cache.insert_at(1, "response".to_string(), now);
+
+        assert_eq!(cache.get_at(1, now + Duration::from_secs(59)).as_deref(), Some("response"));
+        assert_eq!(cache.get_at(1, now + Duration::from_secs(60)), None);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn least_recently_used_is_evicted() {
+        let cache = InMemoryCache::new(2, Duration::from_secs(60));
+        let now = Instant::now();
+
+        cache.insert_at(1, "one".to_string(), now);
+        cache.insert_at(2, "two".to_string(), now);
+        cache.get_at(1, now);
+        cache.insert_at(3, "three".to_string(), now);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get_at(1, now).as_deref(), Some("one"));
+        assert_eq!(cache.get_at(2, now), None);
+        assert_eq!(cache.get_at(3, now).as_deref(), Some("three"));
+    }
+}
@@ -0,0 +1,290 @@
+//! Module containing RobTop's "cryptographic" routines
+//!
+//! The Geometry Dash client obfuscates and integrity-checks various request parameters and response
+//! fields using cyclic XOR with hardcoded keys, urlsafe base64 and salted SHA-1 hashes. This module
+//! contains all known keys and salts, as well as the routines built on top of them.
+//!
+//! Note that none of this is actual cryptography - all keys are hardcoded into the game.
+
+use crate::{serde::ROBTOP_BASE64_CONFIG, util::cyclic_xor};
+use alloc::{
+    borrow::ToOwned,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use base64::{DecodeError, Engine};
+
+/// The XOR key the game uses to encode level passwords
+pub const LEVEL_PASSWORD_XOR_KEY: &str = "26364";
+
+/// The XOR key the game uses to encode private message bodies
+pub const MESSAGE_XOR_KEY: &str = "14251";
+
+/// The XOR key the game uses to encode account passwords (for the legacy `gjp` parameter)
+pub const ACCOUNT_PASSWORD_XOR_KEY: &str = "37526";
+
+/// The XOR key used for the `chk` of quest (challenge) responses
+pub const CHALLENGES_XOR_KEY: &str = "19847";
+
+/// The XOR key used for the `chk` of chest reward responses
+pub const REWARDS_XOR_KEY: &str = "59182";
+
+/// The XOR key used for the `chk` of comment uploads
+pub const COMMENT_XOR_KEY: &str = "29481";
+
+/// The XOR key used for the `chk` of like and rate requests
+pub const LIKE_XOR_KEY: &str = "58281";
+
+/// The XOR key used for `seed2` of level uploads, as well as the `chk` of level downloads
+pub const LEVEL_XOR_KEY: &str = "41274";
+
+/// The XOR key used for the `chk` of level leaderboard requests
+pub const LEVEL_LEADERBOARD_XOR_KEY: &str = "39673";
+
+/// The XOR key used for the `seed2` of user score updates
+pub const USER_SCORE_XOR_KEY: &str = "85271";
+
+/// The salt appended before hashing the `chk` of comment uploads
+pub const COMMENT_SALT: &str = "xPT6iUrtws0J";
+
+/// The salt appended before hashing the `chk` of like and rate requests
+pub const LIKE_SALT: &str = "ysg6pUrtjn0J";
+
+/// The salt appended before hashing the `seed2` of level uploads, as well as the `chk` of level
+/// downloads
+pub const LEVEL_SALT: &str = "xI25fpAapCQg";
+
+/// The salt appended before hashing the `chk` of level leaderboard requests
+pub const LEVEL_LEADERBOARD_SALT: &str = "yPg6pUrtWn0J";
+
+/// The salt appended before hashing the `seed2` of user score updates
+pub const USER_SCORE_SALT: &str = "xI35fsAapCRg";
+
+/// The salt appended to an account's password before hashing it into a `gjp2`
+pub const GJP2_SALT: &str = "mI29fmAnxgTs";
+
+/// XORs the given data with `key` and urlsafe base64 encodes the result
+pub fn xor_encode(data: &[u8], key: &str) -> String {
+    let mut data = data.to_vec();
+
+    cyclic_xor(&mut data, key);
+
+    base64_encode(&data)
+}
+
+/// Urlsafe base64 decodes the given string and XORs the result with `key`. Inverse of
+/// [`xor_encode`]
+pub fn xor_decode(encoded: &str, key: &str) -> Result<Vec<u8>, DecodeError> {
+    let mut data = base64_decode(encoded)?;
+
+    cyclic_xor(&mut data, key);
+
+    Ok(data)
+}
+
+/// Urlsafe base64 encodes the given data the way Geometry Dash does (with padding)
+pub fn base64_encode(data: &[u8]) -> String {
+    ROBTOP_BASE64_CONFIG.encode(data)
+}
+
+/// Urlsafe base64 decodes the given string as leniently as Geometry Dash does
+pub fn base64_decode(encoded: &str) -> Result<Vec<u8>, DecodeError> {
+    ROBTOP_BASE64_CONFIG.decode(encoded)
+}
+
+/// The hex-encoded SHA-1 hash of the given data
+pub fn sha1_hex(data: &[u8]) -> String {
+    sha1_smol::Sha1::from(data).digest().to_string()
+}
+
+/// Computes a `chk` value from the given request parameters.
+///
+/// ## GD Internals:
+/// The game concatenates the values of some of the request's parameters, appends `salt`, SHA-1
+/// hashes the result and then XOR encodes the hex digest with `key` (see [`xor_encode`]).
+pub fn chk(values: &[&str], salt: &str, key: &str) -> String {
+    let mut input = values.concat();
+    input.push_str(salt);
+
+    xor_encode(sha1_hex(input.as_bytes()).as_bytes(), key)
+}
+
+/// Computes the `gjp2` of the given account password, see
+/// [`AuthenticatedUser::password_hash`](crate::request::AuthenticatedUser::password_hash)
+pub fn gjp2(password: &str) -> String {
+    let mut input = password.to_owned();
+    input.push_str(GJP2_SALT);
+
+    sha1_hex(input.as_bytes())
+}
+
+/// Computes the legacy `gjp` of the given account password, which is simply the XOR encoded
+/// password
+pub fn gjp(password: &str) -> String {
+    xor_encode(password.as_bytes(), ACCOUNT_PASSWORD_XOR_KEY)
+}
+
+/// Selects the characters of the given (compressed and base64 encoded) level string that are used
+/// for computing the `seed2` of a level upload.
+///
+/// ## GD Internals:
+/// If the level string is shorter than 50 characters, it is used as is. Otherwise, 50 characters
+/// evenly spaced throughout the level string are used.
+pub fn level_upload_seed(level_string: &str) -> String {
+    const SEED_LENGTH: usize = 50;
+
+    if level_string.len() < SEED_LENGTH {
+        return level_string.to_owned();
+    }
+
+    let step = level_string.len() / SEED_LENGTH;
+
+    level_string.chars().step_by(step).take(SEED_LENGTH).collect()
+}
+
+/// Computes the `seed2` parameter of a level upload from the (compressed and base64 encoded) level
+/// string
+pub fn level_upload_seed2(level_string: &str) -> String {
+    chk(&[&level_upload_seed(level_string)], LEVEL_SALT, LEVEL_XOR_KEY)
+}
+
+/// Computes the first checksum the servers append to the response of a level download, from the
+/// (compressed and base64 encoded) level string.
+///
+/// ## GD Internals:
+/// 40 characters evenly spaced throughout the level string are concatenated with [`LEVEL_SALT`]
+/// and SHA-1 hashed. If the level string is shorter than 40 characters, its first character is
+/// repeated 40 times instead.
+///
+/// The sample is built by overwriting the placeholder `"aaaaa"` with the sampled characters (this is
+/// how the reference server implementation does it, where a step size of 0 for short level strings
+/// causes the first character to be written over and over again). An empty level string thus
+/// leaves the placeholder untouched, and `"aaaaa"` is what gets hashed.
+pub fn level_data_hash(level_string: &str) -> String {
+    const SAMPLE_LENGTH: usize = 40;
+
+    let bytes = level_string.as_bytes();
+    let step = bytes.len() / SAMPLE_LENGTH;
+
+    let mut sample: Vec<u8> = match bytes.first() {
+        // Nothing to sample, so the placeholder is hashed as is
+        None => b"aaaaa".to_vec(),
+        Some(&first) if step == 0 => vec![first; SAMPLE_LENGTH],
+        Some(_) => bytes.iter().step_by(step).take(SAMPLE_LENGTH).copied().collect(),
+    };
+
+    sample.extend_from_slice(LEVEL_SALT.as_bytes());
+
+    sha1_hex(&sample)
+}
+
+/// Computes the second checksum the servers append to the response of a level download.
+///
+/// ## GD Internals:
+/// The values of the creator's user ID, the star rating, whether the level is a demon, the level
+/// ID, whether the coins are verified, the feature score, the (decoded) level password and the
+/// daily/weekly ID (`0` for regular levels) are joined with commas, concatenated with
+/// [`LEVEL_SALT`] and SHA-1 hashed.
+pub fn level_info_hash(values: &[&str]) -> String {
+    let mut input = values.join(",");
+    input.push_str(LEVEL_SALT);
+
+    sha1_hex(input.as_bytes())
+}
+
+/// Computes the checksum the servers append to the response of a level search, from the level ID,
+/// star rating and coin verification status (`"1"` or `"0"`) of each level contained in the
+/// response.
+///
+/// ## GD Internals:
+/// For each level, the first and last character of its ID, its star rating and its coin
+/// verification status are concatenated. The results for all levels are concatenated with
+/// [`LEVEL_SALT`] and SHA-1 hashed.
+pub fn level_search_hash<'a>(levels: impl IntoIterator<Item = (&'a str, &'a str, &'a str)>) -> String {
+    let mut input = String::new();
+
+    for (level_id, stars, coins_verified) in levels {
+        input.extend(level_id.chars().next());
+        input.extend(level_id.chars().last());
+        input.push_str(stars);
+        input.push_str(coins_verified);
+    }
+
+    input.push_str(LEVEL_SALT);
+
+    sha1_hex(input.as_bytes())
+}
+
+/// Generates a random alphanumeric string of the given length, as used by the game for the `rs`,
+/// `uuid` and `udid` parameters of some requests. Only available with the `std` feature.
+#[cfg(feature = "std")]
+pub fn random_string(length: usize) -> String {
+    use rand::{distributions::Alphanumeric, Rng};
+
+    rand::thread_rng().sample_iter(&Alphanumeric).take(length).map(char::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        chk, gjp, gjp2, level_data_hash, level_info_hash, level_upload_seed, level_upload_seed2, random_string, xor_decode, xor_encode,
+        COMMENT_SALT, COMMENT_XOR_KEY,
+    };
+
+    const LEVEL_STRING: &str = "H4sIAAAAAAAAC6WQwQ3DIAxFF_KH8Be2QVS6eZQB8ZqDZA3uXPbRtp7rB2c3O4cYt0ssHO8WHmNoJjbnKUFYq9yRJBFvgH3AiHNsSDnb0mF4iLEaJnKeLbCx0M7b1Ko8wXJBa_5RQH-Kt4vH43qkA7G8CPdjK1R6gC2I3i2fp0cAmHDbIXkDAAA=";
+
+    #[test]
+    fn xor_roundtrip() {
+        let encoded = xor_encode(b"password", "37526");
+
+        assert_eq!(encoded, "Q1ZGQUFcRVE=");
+        assert_eq!(xor_decode(&encoded, "37526").unwrap(), b"password");
+    }
+
+    #[test]
+    fn password_hashes() {
+        assert_eq!(gjp2("password"), "b35ab22e8265f5904100b253e29511f129ca1ce8");
+        assert_eq!(gjp("password"), "Q1ZGQUFcRVE=");
+    }
+
+    #[test]
+    fn comment_chk() {
+        assert_eq!(
+            chk(&["1", "2"], COMMENT_SALT, COMMENT_XOR_KEY),
+            "UFtQWgdXCVFdBlYIUAkJBg1XDgYLXAcMAQQABVlXBl9VXgUBDwQNAg=="
+        );
+    }
+
+    #[test]
+    fn level_seed2() {
+        assert_eq!(level_upload_seed("short"), "short");
+        assert_eq!(
+            level_upload_seed(LEVEL_STRING),
+            "HIAACQ3AFHeVeBqAXR72OYsOHobUqRFHisnmiaKb0boXaR-44k"
+        );
+        assert_eq!(
+            level_upload_seed2(LEVEL_STRING),
+            "BQQGBVVRUFRWBQECAwUBVggGBwwMA1cDBVIAB1UAAglTVQMAAAMHUQ=="
+        );
+    }
+
+    #[test]
+    fn level_download_hashes() {
+        assert_eq!(level_data_hash("H4sIAAAAAAAAC"), level_data_hash("H"));
+        assert_eq!(level_data_hash("H"), "83acee05b74aa6f9813b46c92fd3d66da087c323");
+        assert_eq!(level_data_hash(""), "e43c9c7f873d54a3a0d46007be73edde02fe4039");
+        assert_eq!(
+            level_info_hash(&["842519", "10", "1", "897837", "0", "700", "1003101", "0"]),
+            "f153089c6726a8faef998600b2645dfbbb76eb74"
+        );
+    }
+
+    #[test]
+    fn random_strings() {
+        let rs = random_string(10);
+
+        assert_eq!(rs.len(), 10);
+        assert!(rs.chars().all(|c| c.is_ascii_alphanumeric()));
+    }
+}
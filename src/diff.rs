@@ -0,0 +1,193 @@
+//! Module containing utilities for comparing two observations of the same Geometry Dash entity
+//!
+//! Bots tracking statistics (such as the likes of a level or the stars of a player) periodically
+//! re-download the same entities and need to figure out what changed in between. The functions in
+//! this module compute typed change sets for [`Level`]s, [`Profile`]s and lists of comments.
+//!
+//! All change sets borrow from the compared models, and only contain the fields that actually
+//! changed.
+
+use crate::model::{
+    comment::{level::LevelComment, profile::ProfileComment},
+    level::{rating::Rating, Level, LevelLength},
+    user::{profile::Profile, ModLevel, Rank},
+};
+use alloc::vec::Vec;
+
+/// A value that changed between two observations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Change<T> {
+    /// The value in the older observation
+    pub old: T,
+
+    /// The value in the newer observation
+    pub new: T,
+}
+
+impl<T: PartialEq> Change<T> {
+    /// Returns a [`Change`] if `old` and `new` differ, and [`None`] otherwise
+    pub fn between(old: T, new: T) -> Option<Self> {
+        if old == new {
+            None
+        } else {
+            Some(Change { old, new })
+        }
+    }
+}
+
+impl<T: Copy + Into<i64>> Change<T> {
+    /// The amount by which the value changed, e.g. the number of likes a level gained (or lost, if
+    /// negative)
+    pub fn delta(&self) -> i64 {
+        self.new.into() - self.old.into()
+    }
+}
+
+/// The changes between two observations of the same [`Level`], see [`diff_levels`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LevelDiff<'a> {
+    pub name: Option<Change<&'a str>>,
+    pub version: Option<Change<u32>>,
+    pub rating: Option<Change<Rating>>,
+    pub downloads: Option<Change<u32>>,
+    pub likes: Option<Change<i32>>,
+    pub length: Option<Change<LevelLength>>,
+    pub object_amount: Option<Change<Option<u32>>>,
+}
+
+impl LevelDiff<'_> {
+    /// Whether no tracked field of the level changed
+    pub fn is_empty(&self) -> bool {
+        *self == LevelDiff::default()
+    }
+}
+
+/// Compares two observations of the same [`Level`]
+pub fn diff_levels<'a, Data, Song, User>(old: &'a Level<'_, Data, Song, User>, new: &'a Level<'_, Data, Song, User>) -> LevelDiff<'a> {
+    LevelDiff {
+        name: Change::between(old.name.as_ref(), new.name.as_ref()),
+        version: Change::between(old.version, new.version),
+        rating: Change::between(old.rating, new.rating),
+        downloads: Change::between(old.downloads, new.downloads),
+        likes: Change::between(old.likes, new.likes),
+        length: Change::between(old.length, new.length),
+        object_amount: Change::between(old.object_amount, new.object_amount),
+    }
+}
+
+/// The changes between two observations of the same [`Profile`], see [`diff_profiles`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProfileDiff<'a> {
+    pub name: Option<Change<&'a str>>,
+    pub stars: Option<Change<u32>>,
+    pub moons: Option<Change<u32>>,
+    pub demons: Option<Change<u16>>,
+    pub diamonds: Option<Change<u16>>,
+    pub secret_coins: Option<Change<u8>>,
+    pub user_coins: Option<Change<u16>>,
+    pub creator_points: Option<Change<u16>>,
+    pub global_rank: Option<Change<Option<Rank>>>,
+    pub mod_level: Option<Change<ModLevel>>,
+}
+
+impl ProfileDiff<'_> {
+    /// Whether no tracked field of the profile changed
+    pub fn is_empty(&self) -> bool {
+        *self == ProfileDiff::default()
+    }
+}
+
+/// Compares two observations of the same [`Profile`]
+pub fn diff_profiles<'a>(old: &'a Profile<'_>, new: &'a Profile<'_>) -> ProfileDiff<'a> {
+    ProfileDiff {
+        name: Change::between(old.name.as_ref(), new.name.as_ref()),
+        stars: Change::between(old.stars, new.stars),
+        moons: Change::between(old.moons, new.moons),
+        demons: Change::between(old.demons, new.demons),
+        diamonds: Change::between(old.diamonds, new.diamonds),
+        secret_coins: Change::between(old.secret_coins, new.secret_coins),
+        user_coins: Change::between(old.user_coins, new.user_coins),
+        creator_points: Change::between(old.creator_points, new.creator_points),
+        global_rank: Change::between(old.global_rank, new.global_rank),
+        mod_level: Change::between(old.mod_level, new.mod_level),
+    }
+}
+
+/// Trait for comments whose votes can be tracked via [`diff_comments`]
+pub trait Votable {
+    /// The ID uniquely identifying this comment
+    fn comment_id(&self) -> u64;
+
+    /// The number of likes this comment has (negative if it has more dislikes than likes)
+    fn likes(&self) -> i32;
+}
+
+impl Votable for LevelComment<'_> {
+    fn comment_id(&self) -> u64 {
+        self.comment_id
+    }
+
+    fn likes(&self) -> i32 {
+        self.likes
+    }
+}
+
+impl Votable for ProfileComment<'_> {
+    fn comment_id(&self) -> u64 {
+        self.comment_id
+    }
+
+    fn likes(&self) -> i32 {
+        self.likes
+    }
+}
+
+/// The changes between two observations of the same list of comments, see [`diff_comments`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommentsDiff<'a, C> {
+    /// Comments only contained in the newer observation
+    pub added: Vec<&'a C>,
+
+    /// Comments only contained in the older observation (because they were deleted, or moved to a
+    /// different page)
+    pub removed: Vec<&'a C>,
+
+    /// The comments contained in both observations whose likes changed, together with the change
+    pub likes: Vec<(&'a C, Change<i32>)>,
+}
+
+impl<C> CommentsDiff<'_, C> {
+    /// Whether the list of comments did not change
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.likes.is_empty()
+    }
+}
+
+/// Compares two observations of the same list of comments (for instance the first page of a
+/// level's comments), matching comments up by their ID.
+///
+/// The order of the returned comments follows their order in the respective observation.
+pub fn diff_comments<'a, C: Votable>(old: &'a [C], new: &'a [C]) -> CommentsDiff<'a, C> {
+    let find = |comments: &'a [C], comment_id: u64| comments.iter().find(|comment| comment.comment_id() == comment_id);
+
+    let mut diff = CommentsDiff {
+        added: Vec::new(),
+        removed: Vec::new(),
+        likes: Vec::new(),
+    };
+
+    for comment in new {
+        match find(old, comment.comment_id()) {
+            None => diff.added.push(comment),
+            Some(previous) => {
+                if let Some(change) = Change::between(previous.likes(), comment.likes()) {
+                    diff.likes.push((comment, change))
+                }
+            },
+        }
+    }
+
+    diff.removed = old.iter().filter(|comment| find(new, comment.comment_id()).is_none()).collect();
+
+    diff
+}
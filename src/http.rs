@@ -0,0 +1,453 @@
+//! Module containing an abstraction over the HTTP client used to talk to Geometry Dash servers.
+//! Only available with the `std` feature.
+//!
+//! dash-rs does not depend on any particular HTTP client. Instead, [`Client`] drives an arbitrary
+//! [`HttpBackend`], which only needs to be able to make `POST` requests with a form-encoded body.
+//! This allows using dash-rs in environments where the usual clients are not available (such as
+//! WASM), or routing requests through existing infrastructure (such as curl-based proxies).
+//!
+//! Implementations for [`reqwest::Client`] and [`ureq::Agent`] (which blocks the current thread
+//! and returns an already completed future) are provided behind the `reqwest` and `ureq` features
+//! respectively. For use in the browser, the `wasm` feature provides `FetchBackend` when compiling
+//! for `wasm32-unknown-unknown`.
+//!
+//! Responses can be cached by attaching a [`Cache`] via [`Client::cache`], and requests can be
+//! retried and ratelimited by attaching a [`Middleware`] via [`Client::middleware`].
+//!
+//! ## GD Internals:
+//! The Boomlings servers reject any request that has a `User-Agent` header set, so backends must
+//! make sure their HTTP client does not add one by default.
+
+use crate::{
+    cache::{cache_key, Cache},
+    middleware::{CloudflareError, Middleware, MiddlewareError},
+    request::{BuildRequestError, Request, ServerConfig},
+};
+use std::{
+    fmt::{Debug, Formatter},
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    time::Duration,
+};
+use thiserror::Error;
+
+/// The parts of an HTTP response dash-rs cares about
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HttpResponse {
+    /// The HTTP status code of the response
+    pub status: u16,
+
+    /// The body of the response
+    pub body: String,
+
+    /// The value of the response's `Retry-After` header, if present and in seconds, see
+    /// [`parse_retry_after`](crate::middleware::parse_retry_after)
+    pub retry_after: Option<Duration>,
+}
+
+/// Trait for HTTP clients capable of making requests to Geometry Dash servers
+pub trait HttpBackend {
+    /// The error returned if a request could not be made
+    type Error;
+
+    /// Makes a `POST` request to the given URL, with the given (already form-encoded) body and a
+    /// `Content-Type` of `application/x-www-form-urlencoded`.
+    ///
+    /// Responses with non-success status codes should not be turned into errors, as they are
+    /// inspected for Cloudflare errors by the caller.
+    ///
+    /// The returned future must be `Send`, so that requests can be made from within multi-threaded
+    /// runtimes (e.g. inside `tokio::spawn`). This requirement is lifted on `wasm32`, where the
+    /// browser's futures are not `Send`.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn post(&self, url: &str, form: &str) -> impl Future<Output = Result<HttpResponse, Self::Error>> + Send;
+
+    /// Makes a `POST` request to the given URL, with the given (already form-encoded) body and a
+    /// `Content-Type` of `application/x-www-form-urlencoded`.
+    ///
+    /// Responses with non-success status codes should not be turned into errors, as they are
+    /// inspected for Cloudflare errors by the caller.
+    #[cfg(target_arch = "wasm32")]
+    fn post(&self, url: &str, form: &str) -> impl Future<Output = Result<HttpResponse, Self::Error>>;
+}
+
+/// Error returned by [`Client::send`]
+#[derive(Debug, Error)]
+pub enum ClientError<E> {
+    /// The request could not be built, see [`BuildRequestError`]
+    #[error("{0}")]
+    Request(#[from] BuildRequestError),
+
+    /// The [`HttpBackend`] failed to make the request
+    #[error("{0}")]
+    Backend(E),
+
+    /// Cloudflare blocked the request
+    #[error("request blocked by cloudflare: {0:?}")]
+    Cloudflare(CloudflareError),
+}
+
+impl<E> From<MiddlewareError<E>> for ClientError<E> {
+    fn from(error: MiddlewareError<E>) -> Self {
+        match error {
+            MiddlewareError::Transport(error) => ClientError::Backend(error),
+            MiddlewareError::Cloudflare(error) => ClientError::Cloudflare(error),
+        }
+    }
+}
+
+/// The future returned by the sleep function passed to [`Client::middleware`]
+#[cfg(not(target_arch = "wasm32"))]
+pub type SleepFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// The future returned by the sleep function passed to [`Client::middleware`]
+#[cfg(target_arch = "wasm32")]
+pub type SleepFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+type Sleep = Arc<dyn Fn(Duration) -> SleepFuture + Send + Sync>;
+
+/// Makes requests to the server described by a [`ServerConfig`] using some [`HttpBackend`]
+#[derive(Clone)]
+pub struct Client<'a, B> {
+    backend: B,
+    server: ServerConfig<'a>,
+    cache: Option<Arc<dyn Cache>>,
+    middleware: Option<(Arc<Middleware>, Sleep)>,
+}
+
+impl<B: Debug> Debug for Client<'_, B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Client")
+            .field("backend", &self.backend)
+            .field("server", &self.server)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, B: HttpBackend> Client<'a, B> {
+    /// Constructs a new `Client` making requests to the given server, without caching, retrying or
+    /// ratelimiting
+    pub const fn new(backend: B, server: ServerConfig<'a>) -> Self {
+        Client {
+            backend,
+            server,
+            cache: None,
+            middleware: None,
+        }
+    }
+
+    /// Caches the responses to requests made by this client in the given [`Cache`]
+    ///
+    /// Responses are cached by the URL and serialized form of the request they were received for.
+    /// Responses blocked by Cloudflare and failed requests are never cached.
+    pub fn cache(mut self, cache: impl Cache + 'static) -> Self {
+        self.cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Makes requests through the given [`Middleware`], waiting between attempts (and for the
+    /// middleware's [`RateLimiter`](crate::middleware::RateLimiter)) on the futures returned by
+    /// `sleep`, for example `|duration| Box::pin(tokio::time::sleep(duration))`.
+    ///
+    /// The [`Cache`] of the middleware is not consulted, use [`Client::cache`] instead.
+    pub fn middleware(mut self, middleware: Middleware, sleep: impl Fn(Duration) -> SleepFuture + Send + Sync + 'static) -> Self {
+        self.middleware = Some((Arc::new(middleware), Arc::new(sleep)));
+        self
+    }
+
+    /// The [`HttpBackend`] used by this client
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// The [`ServerConfig`] describing the server this client makes requests to
+    pub fn server(&self) -> &ServerConfig<'a> {
+        &self.server
+    }
+
+    /// Sends the given request to the endpoint it is meant for, returning the body of the response.
+    ///
+    /// The request is validated and serialized via [`ServerConfig::build_request`]. The returned
+    /// body can then be passed to the appropriate parsing function in [`response`](crate::response).
+    /// If a [`Cache`] is attached, it is consulted before making the request. If a [`Middleware`]
+    /// is attached, the request is retried and ratelimited according to it, otherwise exactly one
+    /// attempt is made.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(endpoint = R::ENDPOINT)))]
+    pub async fn send<'b, R: Request<'b>>(&self, request: &R) -> Result<String, ClientError<B::Error>> {
+        let form = self.server.build_request(request)?;
+        let url = self.server.endpoint_url(R::ENDPOINT);
+        let key = cache_key(&(url.as_str(), form.as_str()));
+
+        if let Some(response) = self.cache.as_ref().and_then(|cache| cache.get(key)) {
+            return Ok(response);
+        }
+
+        let body = match &self.middleware {
+            Some((middleware, sleep)) => middleware.execute(|| self.post(&url, &form), sleep.as_ref()).await?,
+            None => {
+                let response = self.post(&url, &form).await.map_err(ClientError::Backend)?;
+
+                match CloudflareError::classify(response.status, &response.body) {
+                    Some(error) => return Err(ClientError::Cloudflare(error)),
+                    None => response.body,
+                }
+            },
+        };
+
+        if let Some(cache) = &self.cache {
+            cache.insert(key, body.clone());
+        }
+
+        Ok(body)
+    }
+
+    /// Makes a single attempt at `POST`ing the given form to the given URL
+    async fn post(&self, url: &str, form: &str) -> Result<HttpResponse, B::Error> {
+        let response = self.backend.post(url, form).await?;
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(%url, status = response.status, length = response.body.len(), "received response");
+
+        Ok(response)
+    }
+}
+
+#[cfg(all(feature = "reqwest", not(target_arch = "wasm32")))]
+impl HttpBackend for reqwest::Client {
+    type Error = reqwest::Error;
+
+    fn post(&self, url: &str, form: &str) -> impl Future<Output = Result<HttpResponse, Self::Error>> + Send {
+        let request = reqwest::Client::post(self, url)
+            .header(reqwest::header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(form.to_owned());
+
+        async move {
+            let response = request.send().await?;
+            let status = response.status().as_u16();
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(crate::middleware::parse_retry_after);
+
+            Ok(HttpResponse {
+                status,
+                body: response.text().await?,
+                retry_after,
+            })
+        }
+    }
+}
+
+/// Note that [`ureq::Agent`]s send a `User-Agent` header by default, which needs to be disabled via
+/// `ureq::AgentBuilder::user_agent("")` when talking to the Boomlings servers.
+#[cfg(feature = "ureq")]
+impl HttpBackend for ureq::Agent {
+    type Error = ureq::Error;
+
+    fn post(&self, url: &str, form: &str) -> impl Future<Output = Result<HttpResponse, Self::Error>> + Send {
+        let response = match ureq::Agent::post(self, url)
+            .set("Content-Type", "application/x-www-form-urlencoded")
+            .send_string(form)
+        {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => Ok(response),
+            Err(error) => Err(error),
+        };
+
+        std::future::ready(response.and_then(|response| {
+            Ok(HttpResponse {
+                status: response.status(),
+                retry_after: response.header("retry-after").and_then(crate::middleware::parse_retry_after),
+                body: response.into_string()?,
+            })
+        }))
+    }
+}
+
+/// [`HttpBackend`] making requests via the browser's Fetch API. Only available with the `wasm`
+/// feature when compiling for `wasm32`, and only functional when running on the main thread of a
+/// browser (since it requires a `Window`).
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FetchBackend;
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+impl HttpBackend for FetchBackend {
+    type Error = wasm_bindgen::JsValue;
+
+    fn post(&self, url: &str, form: &str) -> impl Future<Output = Result<HttpResponse, Self::Error>> {
+        use wasm_bindgen::{JsCast, JsValue};
+        use wasm_bindgen_futures::JsFuture;
+
+        let init = web_sys::RequestInit::new();
+        init.set_method("POST");
+        init.set_body(&JsValue::from_str(form));
+
+        let request = web_sys::Request::new_with_str_and_init(url, &init);
+
+        async move {
+            let request = request?;
+            request.headers().set("Content-Type", "application/x-www-form-urlencoded")?;
+
+            let window = web_sys::window().ok_or_else(|| JsValue::from_str("FetchBackend requires a Window"))?;
+            let response: web_sys::Response = JsFuture::from(window.fetch_with_request(&request)).await?.dyn_into()?;
+            let body = JsFuture::from(response.text()?).await?;
+
+            Ok(HttpResponse {
+                status: response.status(),
+                body: body.as_string().unwrap_or_default(),
+                retry_after: response
+                    .headers()
+                    .get("retry-after")?
+                    .as_deref()
+                    .and_then(crate::middleware::parse_retry_after),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Client, ClientError, HttpBackend, HttpResponse};
+    use crate::{
+        cache::InMemoryCache,
+        middleware::{CloudflareError, Middleware, RetryPolicy},
+        request::{user::UserRequest, ServerConfig},
+    };
+    use std::{
+        future::{ready, Future},
+        pin::pin,
+        sync::{Arc, Mutex},
+        task::{Context, Poll, Wake, Waker},
+        time::Duration,
+    };
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut future = pin!(future);
+
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut Context::from_waker(&waker)) {
+                return output;
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct MockBackend {
+        requests: Mutex<Vec<(String, String)>>,
+        status: u16,
+        body: &'static str,
+    }
+
+    impl HttpBackend for MockBackend {
+        type Error = ();
+
+        fn post(&self, url: &str, form: &str) -> impl Future<Output = Result<HttpResponse, Self::Error>> + Send {
+            self.requests.lock().unwrap().push((url.to_owned(), form.to_owned()));
+
+            ready(Ok(HttpResponse {
+                status: self.status,
+                body: self.body.to_owned(),
+                retry_after: None,
+            }))
+        }
+    }
+
+    #[test]
+    fn sends_requests_to_endpoint() {
+        let backend = MockBackend {
+            status: 200,
+            body: "1:stadust:2:1710032",
+            ..MockBackend::default()
+        };
+        let client = Client::new(backend, ServerConfig::new("https://example.com/"));
+
+        assert_eq!(block_on(client.send(&UserRequest::new(1710032))).unwrap(), "1:stadust:2:1710032");
+
+        let requests = client.backend().requests.lock().unwrap();
+
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].0, "https://example.com/getGJUserInfo20.php");
+        assert!(requests[0].1.contains("targetAccountID=1710032"));
+    }
+
+    #[test]
+    fn requests_are_send() {
+        fn assert_send<T: Send>(_: T) {}
+
+        let client = Client::new(MockBackend::default(), ServerConfig::default());
+
+        assert_send(client.send(&UserRequest::new(1710032)));
+    }
+
+    #[test]
+    fn reports_cloudflare_errors() {
+        let backend = MockBackend {
+            status: 429,
+            ..MockBackend::default()
+        };
+        let client = Client::new(backend, ServerConfig::new("https://example.com/"));
+
+        assert!(matches!(
+            block_on(client.send(&UserRequest::new(1710032))),
+            Err(ClientError::Cloudflare(CloudflareError::TooManyRequests))
+        ));
+    }
+
+    #[test]
+    fn caches_responses() {
+        let backend = MockBackend {
+            status: 200,
+            body: "1:stadust:2:1710032",
+            ..MockBackend::default()
+        };
+        let client = Client::new(backend, ServerConfig::new("https://example.com/")).cache(InMemoryCache::new(10, Duration::from_secs(60)));
+
+        assert_eq!(block_on(client.send(&UserRequest::new(1710032))).unwrap(), "1:stadust:2:1710032");
+        assert_eq!(block_on(client.send(&UserRequest::new(1710032))).unwrap(), "1:stadust:2:1710032");
+        assert_eq!(client.backend().requests.lock().unwrap().len(), 1);
+
+        block_on(client.send(&UserRequest::new(8451))).unwrap();
+        assert_eq!(client.backend().requests.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn retries_through_middleware() {
+        let backend = MockBackend {
+            status: 429,
+            ..MockBackend::default()
+        };
+        let middleware = Middleware::new(RetryPolicy::new(2).initial_backoff(Duration::ZERO));
+        let client = Client::new(backend, ServerConfig::new("https://example.com/"))
+            .cache(InMemoryCache::new(10, Duration::from_secs(60)))
+            .middleware(middleware, |_| Box::pin(ready(())));
+
+        assert!(matches!(
+            block_on(client.send(&UserRequest::new(1710032))),
+            Err(ClientError::Cloudflare(CloudflareError::TooManyRequests))
+        ));
+        assert_eq!(client.backend().requests.lock().unwrap().len(), 3);
+
+        // Blocked responses are not cached
+        assert!(block_on(client.send(&UserRequest::new(1710032))).is_err());
+        assert_eq!(client.backend().requests.lock().unwrap().len(), 6);
+    }
+
+    #[test]
+    fn requests_with_middleware_are_send() {
+        fn assert_send<T: Send>(_: T) {}
+
+        let client =
+            Client::new(MockBackend::default(), ServerConfig::default()).middleware(Middleware::default(), |_| Box::pin(ready(())));
+
+        assert_send(client.send(&UserRequest::new(1710032)));
+    }
+}
@@ -1,9 +1,29 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![forbid(unsafe_code)]
 
+extern crate alloc;
+
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "std")]
+pub mod cache;
+pub mod crypto;
+pub mod diff;
+#[cfg(feature = "std")]
+pub mod http;
+#[cfg(feature = "std")]
+pub mod middleware;
 pub mod model;
 pub mod request;
 pub mod response;
 pub(crate) mod serde;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod util;
 
-pub use crate::serde::{Dash, DeError, GJFormat, IndexedDeserializer, IndexedSerializer, ProcessError, SerError, Thunk, ThunkProcessor};
+pub use crate::serde::{
+    io, limits, Dash, DeError, GJFormat, IndexedDeserializer, IndexedSerializer, IntoOwnedThunkProcessor, ProcessError, RobtopDisplay,
+    SerError, Thunk, ThunkProcessor, UnknownIndices,
+};
@@ -0,0 +1,648 @@
+//! Module containing transport-agnostic retry and ratelimiting logic for talking to the Boomlings
+//! servers. Only available with the `std` feature.
+//!
+//! RobTop's servers (or rather, the Cloudflare instance in front of them) ban aggressively: Exceeding
+//! the ratelimit of an endpoint results in a `429 TOO MANY REQUESTS` response (often with
+//! `retry-after: 3600`), and repeatedly doing so gets your IP banned (Cloudflare error `1005`, see
+//! [`ResponseError::IpBanned`](crate::response::ResponseError::IpBanned)) or ratelimited on the
+//! Cloudflare level (error `1015`).
+//!
+//! [`Middleware`] wraps an arbitrary function performing the actual HTTP request, throttling
+//! requests through a token bucket [`RateLimiter`], retrying failed requests according to a
+//! [`RetryPolicy`] and allowing callers to observe Cloudflare errors before responses are handed to
+//! the parsing functions in [`response`](crate::response).
+//!
+//! The `*_blocking` methods wait by blocking the current thread, so they must not be used from
+//! within an async runtime. [`Middleware::execute`] and [`Middleware::execute_cached`] instead wait
+//! via a caller-provided sleep function (such as `tokio::time::sleep`), as dash-rs does not depend
+//! on any particular runtime.
+
+use crate::{
+    cache::{cache_key, Cache},
+    http::HttpResponse,
+};
+use std::{
+    future::Future,
+    hash::Hash,
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+
+/// Errors returned by Cloudflare (instead of by RobTop's servers) when it decides to block a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CloudflareError {
+    /// The server responded with `429 TOO MANY REQUESTS`, meaning the endpoint's ratelimit was
+    /// exceeded
+    TooManyRequests,
+
+    /// The response was `error code: 1005`, meaning the requesting IP has been banned
+    IpBanned,
+
+    /// The response was `error code: 1015`, meaning the requesting IP is being ratelimited by
+    /// Cloudflare
+    RateLimited,
+}
+
+impl CloudflareError {
+    /// Inspects the HTTP status code and body of a response and determines whether it was produced
+    /// by Cloudflare blocking the request
+    pub fn classify(status: u16, body: &str) -> Option<CloudflareError> {
+        match body.trim() {
+            "error code: 1005" => Some(CloudflareError::IpBanned),
+            "error code: 1015" => Some(CloudflareError::RateLimited),
+            _ if status == 429 => Some(CloudflareError::TooManyRequests),
+            _ => None,
+        }
+    }
+
+    /// Whether retrying a request that failed with this error can possibly succeed.
+    ///
+    /// IP bans are not temporary on the timescales relevant for retrying, and Cloudflare-level
+    /// ratelimits are only lifted once the client stops sending requests for a while, so retrying
+    /// either only risks prolonging the block. Only [`CloudflareError::TooManyRequests`] is
+    /// retryable.
+    pub const fn is_retryable(self) -> bool {
+        matches!(self, CloudflareError::TooManyRequests)
+    }
+}
+
+/// Parses the value of a `Retry-After` header.
+///
+/// Only the delay-seconds form is supported, as that is the only one Cloudflare sends. HTTP dates
+/// yield `None`.
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    value.trim().parse().ok().map(Duration::from_secs)
+}
+
+/// Configuration for retrying failed requests with exponential backoff
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RetryPolicy {
+    /// The maximum amount of times a request is retried. `0` disables retrying.
+    pub max_retries: u32,
+
+    /// The time to wait before the first retry
+    pub initial_backoff: Duration,
+
+    /// The upper bound for the time waited between two attempts
+    pub max_backoff: Duration,
+
+    /// The factor by which the backoff grows with each retry
+    pub multiplier: u32,
+}
+
+impl RetryPolicy {
+    /// A [`RetryPolicy`] that never retries
+    pub const NEVER: RetryPolicy = RetryPolicy::new(0);
+
+    /// Constructs a new `RetryPolicy` retrying up to `max_retries` times, starting with a backoff
+    /// of one second that doubles with every retry, up to a maximum of one minute
+    pub const fn new(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            multiplier: 2,
+        }
+    }
+
+    /// Sets the time to wait before the first retry
+    pub const fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Sets the upper bound for the time waited between two attempts
+    pub const fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets the factor by which the backoff grows with each retry
+    pub const fn multiplier(mut self, multiplier: u32) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// The time to wait before the given (1-based) retry
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.saturating_pow(retry.saturating_sub(1));
+
+        self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::new(3)
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: u32,
+    last_refill: Instant,
+}
+
+/// A token bucket ratelimiter.
+///
+/// The bucket holds up to `capacity` tokens, one of which is consumed per request. A new token is
+/// added every `refill_interval`. For example, the level download endpoint allows about 20 requests
+/// per minute, which corresponds to `RateLimiter::new(20, Duration::from_secs(3))`.
+#[derive(Debug)]
+pub struct RateLimiter {
+    capacity: u32,
+    refill_interval: Duration,
+    bucket: Mutex<Bucket>,
+}
+
+impl RateLimiter {
+    /// Constructs a new, full `RateLimiter`
+    pub fn new(capacity: u32, refill_interval: Duration) -> RateLimiter {
+        RateLimiter {
+            capacity,
+            refill_interval,
+            bucket: Mutex::new(Bucket {
+                tokens: capacity,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Tries to consume a token at the given point in time. If no token is available, returns the
+    /// time until the next one becomes available.
+    pub fn try_acquire_at(&self, now: Instant) -> Result<(), Duration> {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if !self.refill_interval.is_zero() {
+            let elapsed = now.saturating_duration_since(bucket.last_refill);
+            let refilled = (elapsed.as_nanos() / self.refill_interval.as_nanos()).min(self.capacity as u128) as u32;
+
+            if bucket.tokens + refilled >= self.capacity {
+                bucket.tokens = self.capacity;
+                bucket.last_refill = now;
+            } else if refilled > 0 {
+                bucket.tokens += refilled;
+                bucket.last_refill += self.refill_interval * refilled;
+            }
+        } else {
+            bucket.tokens = self.capacity;
+        }
+
+        if bucket.tokens == 0 {
+            return Err((bucket.last_refill + self.refill_interval).saturating_duration_since(now));
+        }
+
+        bucket.tokens -= 1;
+
+        Ok(())
+    }
+
+    /// Tries to consume a token right now, see [`RateLimiter::try_acquire_at`]
+    pub fn try_acquire(&self) -> Result<(), Duration> {
+        self.try_acquire_at(Instant::now())
+    }
+
+    /// Consumes a token, blocking the current thread until one becomes available
+    pub fn acquire_blocking(&self) {
+        while let Err(wait) = self.try_acquire() {
+            thread::sleep(wait);
+        }
+    }
+
+    /// Consumes a token, waiting via the given sleep function until one becomes available
+    pub async fn acquire<S, SF>(&self, mut sleep: S)
+    where
+        S: FnMut(Duration) -> SF,
+        SF: Future<Output = ()>,
+    {
+        while let Err(wait) = self.try_acquire() {
+            sleep(wait).await;
+        }
+    }
+}
+
+/// Error returned by [`Middleware::execute_blocking`] and [`Middleware::execute`]
+#[derive(Debug, Error)]
+pub enum MiddlewareError<E> {
+    /// The underlying transport failed on the last attempt
+    #[error("{0}")]
+    Transport(E),
+
+    /// Cloudflare blocked the last attempt
+    #[error("request blocked by cloudflare: {0:?}")]
+    Cloudflare(CloudflareError),
+}
+
+/// Information about a single attempt at making a request, passed to the observer registered via
+/// [`Middleware::observe`]
+#[derive(Debug, Clone, Copy)]
+pub struct Attempt<'a> {
+    /// The (0-based) index of this attempt
+    pub attempt: u32,
+
+    /// The HTTP status code of the response
+    pub status: u16,
+
+    /// The body of the response
+    pub body: &'a str,
+
+    /// The Cloudflare error this response represents, if any
+    pub cloudflare: Option<CloudflareError>,
+
+    /// The delay requested by the response's `Retry-After` header, if any
+    pub retry_after: Option<Duration>,
+}
+
+/// What to do after an attempt, see [`Middleware::evaluate`]
+enum Step<E> {
+    Done(Result<String, MiddlewareError<E>>),
+    Retry(Duration),
+}
+
+/// Combines a [`RetryPolicy`], an optional [`RateLimiter`], an optional [`Cache`] and an optional
+/// observer for responses
+#[derive(Default)]
+pub struct Middleware {
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    cache: Option<Box<dyn Cache>>,
+    observer: Option<Box<dyn Fn(&Attempt<'_>) + Send + Sync>>,
+}
+
+impl Middleware {
+    /// Constructs a new `Middleware` using the given [`RetryPolicy`], without ratelimiting
+    pub fn new(retry_policy: RetryPolicy) -> Middleware {
+        Middleware {
+            retry_policy,
+            rate_limiter: None,
+            cache: None,
+            observer: None,
+        }
+    }
+
+    /// Throttles requests made through this middleware using the given [`RateLimiter`]
+    pub fn rate_limit(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Caches responses to requests made via [`Middleware::execute_cached_blocking`] in the given
+    /// [`Cache`]
+    pub fn cache(mut self, cache: impl Cache + 'static) -> Self {
+        self.cache = Some(Box::new(cache));
+        self
+    }
+
+    /// Registers a function to be called with every response received, before it is checked for
+    /// Cloudflare errors or retried
+    pub fn observe(mut self, observer: impl Fn(&Attempt<'_>) + Send + Sync + 'static) -> Self {
+        self.observer = Some(Box::new(observer));
+        self
+    }
+
+    /// The [`RetryPolicy`] used by this middleware
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// The [`RateLimiter`] used by this middleware, if any
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+
+    /// Like [`Middleware::execute_blocking`], but first consults this middleware's [`Cache`] (if
+    /// any) for a response to the given request, and caches the response if there was none.
+    ///
+    /// Responses blocked by Cloudflare and failed requests are never cached.
+    pub fn execute_cached_blocking<R, F, E>(&self, request: &R, send: F) -> Result<String, MiddlewareError<E>>
+    where
+        R: Hash + ?Sized,
+        F: FnMut() -> Result<HttpResponse, E>,
+    {
+        let cache = match &self.cache {
+            Some(cache) => cache,
+            None => return self.execute_blocking(send),
+        };
+
+        let key = cache_key(request);
+
+        if let Some(response) = cache.get(key) {
+            return Ok(response);
+        }
+
+        let response = self.execute_blocking(send)?;
+
+        cache.insert(key, response.clone());
+
+        Ok(response)
+    }
+
+    /// Performs a request using the given `send` function.
+    ///
+    /// Requests blocked with `429 TOO MANY REQUESTS` and transport errors are retried according to
+    /// this middleware's [`RetryPolicy`], blocking the current thread while backing off. If a `429`
+    /// response carries a `Retry-After` header, it is waited for instead of the backoff, unless it
+    /// exceeds [`RetryPolicy::max_backoff`], in which case the request is not retried at all.
+    /// The body of the first response not blocked by Cloudflare is returned, and can then be passed
+    /// to the appropriate parsing function in [`response`](crate::response).
+    pub fn execute_blocking<F, E>(&self, mut send: F) -> Result<String, MiddlewareError<E>>
+    where
+        F: FnMut() -> Result<HttpResponse, E>,
+    {
+        let mut attempt = 0;
+
+        loop {
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire_blocking();
+            }
+
+            match self.evaluate(attempt, send()) {
+                Step::Done(result) => return result,
+                Step::Retry(wait) => thread::sleep(wait),
+            }
+
+            attempt += 1;
+        }
+    }
+
+    /// Like [`Middleware::execute_cached_blocking`], but asynchronous, see
+    /// [`Middleware::execute`]
+    pub async fn execute_cached<R, F, Fut, S, SF, E>(&self, request: &R, send: F, sleep: S) -> Result<String, MiddlewareError<E>>
+    where
+        R: Hash + ?Sized,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<HttpResponse, E>>,
+        S: FnMut(Duration) -> SF,
+        SF: Future<Output = ()>,
+    {
+        let cache = match &self.cache {
+            Some(cache) => cache,
+            None => return self.execute(send, sleep).await,
+        };
+
+        let key = cache_key(request);
+
+        if let Some(response) = cache.get(key) {
+            return Ok(response);
+        }
+
+        let response = self.execute(send, sleep).await?;
+
+        cache.insert(key, response.clone());
+
+        Ok(response)
+    }
+
+    /// Like [`Middleware::execute_blocking`], but asynchronous: Requests are made via the future
+    /// returned by `send`, and backoff and ratelimiting wait on the future returned by `sleep`
+    /// (for example `tokio::time::sleep`) instead of blocking the current thread.
+    pub async fn execute<F, Fut, S, SF, E>(&self, mut send: F, mut sleep: S) -> Result<String, MiddlewareError<E>>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<HttpResponse, E>>,
+        S: FnMut(Duration) -> SF,
+        SF: Future<Output = ()>,
+    {
+        let mut attempt = 0;
+
+        loop {
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire(&mut sleep).await;
+            }
+
+            match self.evaluate(attempt, send().await) {
+                Step::Done(result) => return result,
+                Step::Retry(wait) => sleep(wait).await,
+            }
+
+            attempt += 1;
+        }
+    }
+
+    /// Inspects the result of the given (0-based) attempt, and decides whether to return it or how
+    /// long to wait before retrying
+    fn evaluate<E>(&self, attempt: u32, result: Result<HttpResponse, E>) -> Step<E> {
+        let (error, retry_after) = match result {
+            Ok(response) => {
+                let cloudflare = CloudflareError::classify(response.status, &response.body);
+
+                if let Some(observer) = &self.observer {
+                    observer(&Attempt {
+                        attempt,
+                        status: response.status,
+                        body: &response.body,
+                        cloudflare,
+                        retry_after: response.retry_after,
+                    });
+                }
+
+                match cloudflare {
+                    None => return Step::Done(Ok(response.body)),
+                    Some(error) if !error.is_retryable() => return Step::Done(Err(MiddlewareError::Cloudflare(error))),
+                    Some(error) => (MiddlewareError::Cloudflare(error), response.retry_after),
+                }
+            },
+            Err(error) => (MiddlewareError::Transport(error), None),
+        };
+
+        if attempt >= self.retry_policy.max_retries {
+            return Step::Done(Err(error));
+        }
+
+        match retry_after {
+            Some(wait) if wait > self.retry_policy.max_backoff => Step::Done(Err(error)),
+            Some(wait) => Step::Retry(wait),
+            None => Step::Retry(self.retry_policy.backoff(attempt + 1)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_retry_after, CloudflareError, Middleware, MiddlewareError, RateLimiter, RetryPolicy};
+    use crate::{cache::InMemoryCache, http::HttpResponse, request::user::UserRequest};
+    use std::{
+        future::{ready, Future},
+        pin::pin,
+        sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc,
+        },
+        task::{Context, Poll, Wake, Waker},
+        time::{Duration, Instant},
+    };
+
+    fn response(status: u16, body: &str) -> HttpResponse {
+        HttpResponse {
+            status,
+            body: body.to_owned(),
+            retry_after: None,
+        }
+    }
+
+    #[test]
+    fn classify_cloudflare_errors() {
+        assert_eq!(CloudflareError::classify(200, "error code: 1005"), Some(CloudflareError::IpBanned));
+        assert_eq!(
+            CloudflareError::classify(403, "error code: 1015"),
+            Some(CloudflareError::RateLimited)
+        );
+        assert_eq!(CloudflareError::classify(429, ""), Some(CloudflareError::TooManyRequests));
+        assert_eq!(CloudflareError::classify(200, "-1"), None);
+    }
+
+    #[test]
+    fn exponential_backoff() {
+        let policy = RetryPolicy::new(5).max_backoff(Duration::from_secs(5));
+
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(3), Duration::from_secs(4));
+        assert_eq!(policy.backoff(4), Duration::from_secs(5));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn token_bucket() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(3));
+        let start = Instant::now();
+
+        assert!(limiter.try_acquire_at(start).is_ok());
+        assert!(limiter.try_acquire_at(start).is_ok());
+        assert_eq!(limiter.try_acquire_at(start + Duration::from_secs(1)), Err(Duration::from_secs(2)));
+        assert!(limiter.try_acquire_at(start + Duration::from_secs(3)).is_ok());
+        assert!(limiter.try_acquire_at(start + Duration::from_secs(3)).is_err());
+    }
+
+    #[test]
+    fn retries_until_success() {
+        let observed = Arc::new(AtomicU32::new(0));
+        let observed_clone = Arc::clone(&observed);
+
+        let middleware = Middleware::new(RetryPolicy::new(3).initial_backoff(Duration::ZERO)).observe(move |attempt| {
+            if attempt.cloudflare.is_some() {
+                observed_clone.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        let mut responses = vec![Ok(response(200, "1:2:3")), Ok(response(429, "")), Err(()), Ok(response(429, ""))];
+        let result = middleware.execute_blocking(|| responses.pop().unwrap());
+
+        assert_eq!(result.unwrap(), "1:2:3");
+        assert_eq!(observed.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn cached_requests_do_not_hit_the_server() {
+        let middleware = Middleware::new(RetryPolicy::NEVER).cache(InMemoryCache::new(16, Duration::from_secs(60)));
+        let mut attempts = 0;
+        let mut send = || {
+            attempts += 1;
+            Ok::<_, ()>(response(200, "1:stadust:2:1710032"))
+        };
+
+        for _ in 0..3 {
+            assert_eq!(
+                middleware.execute_cached_blocking(&UserRequest::new(1710032), &mut send).unwrap(),
+                "1:stadust:2:1710032"
+            );
+        }
+        middleware.execute_cached_blocking(&UserRequest::new(1), &mut send).unwrap();
+
+        assert_eq!(attempts, 2);
+    }
+
+    #[test]
+    fn does_not_retry_cloudflare_blocks() {
+        let middleware = Middleware::new(RetryPolicy::new(3).initial_backoff(Duration::ZERO));
+
+        for (body, expected) in [
+            ("error code: 1005", CloudflareError::IpBanned),
+            ("error code: 1015", CloudflareError::RateLimited),
+        ] {
+            let mut attempts = 0;
+
+            let result = middleware.execute_blocking(|| {
+                attempts += 1;
+                Ok::<_, ()>(response(200, body))
+            });
+
+            assert!(matches!(result, Err(MiddlewareError::Cloudflare(error)) if error == expected));
+            assert_eq!(attempts, 1);
+        }
+    }
+
+    #[test]
+    fn honours_retry_after() {
+        assert_eq!(parse_retry_after(" 3600 "), Some(Duration::from_secs(3600)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+
+        let middleware = Middleware::new(RetryPolicy::new(3).max_backoff(Duration::from_secs(60)));
+        let mut attempts = 0;
+
+        // Waiting an hour exceeds the policy's maximum backoff, so the request is not retried
+        let result = middleware.execute_blocking(|| {
+            attempts += 1;
+            Ok::<_, ()>(HttpResponse {
+                retry_after: Some(Duration::from_secs(3600)),
+                ..response(429, "")
+            })
+        });
+
+        assert!(matches!(result, Err(MiddlewareError::Cloudflare(CloudflareError::TooManyRequests))));
+        assert_eq!(attempts, 1);
+
+        let middleware = Middleware::new(RetryPolicy::new(3).initial_backoff(Duration::from_secs(60)));
+        let mut responses = vec![
+            response(200, "1:2:3"),
+            HttpResponse {
+                retry_after: Some(Duration::ZERO),
+                ..response(429, "")
+            },
+        ];
+        let start = Instant::now();
+
+        assert_eq!(
+            middleware.execute_blocking(|| Ok::<_, ()>(responses.pop().unwrap())).unwrap(),
+            "1:2:3"
+        );
+        assert!(start.elapsed() < Duration::from_secs(60));
+    }
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut future = pin!(future);
+
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut Context::from_waker(&waker)) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn async_retries_sleep_via_callback() {
+        let middleware = Middleware::new(RetryPolicy::new(3));
+        let mut responses = vec![Ok(response(200, "1:2:3")), Ok(response(429, "")), Err(())];
+        let mut sleeps = Vec::new();
+
+        let result = block_on(middleware.execute(
+            || ready(responses.pop().unwrap()),
+            |duration| {
+                sleeps.push(duration);
+                ready(())
+            },
+        ));
+
+        assert_eq!(result.unwrap(), "1:2:3");
+        assert_eq!(sleeps, [Duration::from_secs(1), Duration::from_secs(2)]);
+    }
+}
@@ -0,0 +1,199 @@
+//! Module containing the [`GdAge`] type used for RobTop's relative timestamps
+
+use alloc::{borrow::Cow, format};
+use core::{
+    fmt::{Display, Formatter},
+    time::Duration,
+};
+use serde::{Deserialize, Serialize};
+
+crate::dash_rs_newtype!(GdAge);
+
+/// The unit of time a [`GdAge`] is expressed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum AgeUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl AgeUnit {
+    /// The (approximate) number of seconds in one of this unit.
+    ///
+    /// Months are assumed to have 30 days, years to have 365 days.
+    pub const fn seconds(self) -> u64 {
+        match self {
+            AgeUnit::Second => 1,
+            AgeUnit::Minute => 60,
+            AgeUnit::Hour => 60 * 60,
+            AgeUnit::Day => 24 * 60 * 60,
+            AgeUnit::Week => 7 * 24 * 60 * 60,
+            AgeUnit::Month => 30 * 24 * 60 * 60,
+            AgeUnit::Year => 365 * 24 * 60 * 60,
+        }
+    }
+
+    /// All units, from largest to smallest
+    const DESCENDING: [AgeUnit; 7] = [
+        AgeUnit::Year,
+        AgeUnit::Month,
+        AgeUnit::Week,
+        AgeUnit::Day,
+        AgeUnit::Hour,
+        AgeUnit::Minute,
+        AgeUnit::Second,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            AgeUnit::Second => "second",
+            AgeUnit::Minute => "minute",
+            AgeUnit::Hour => "hour",
+            AgeUnit::Day => "day",
+            AgeUnit::Week => "week",
+            AgeUnit::Month => "month",
+            AgeUnit::Year => "year",
+        }
+    }
+
+    fn from_str(unit: &str) -> Option<AgeUnit> {
+        // RobTop uses the singular for amounts of 1 ("1 year") and the plural otherwise ("3 years")
+        let unit = unit.strip_suffix('s').unwrap_or(unit);
+
+        Some(match unit {
+            "second" => AgeUnit::Second,
+            "minute" => AgeUnit::Minute,
+            "hour" => AgeUnit::Hour,
+            "day" => AgeUnit::Day,
+            "week" => AgeUnit::Week,
+            "month" => AgeUnit::Month,
+            "year" => AgeUnit::Year,
+            _ => return None,
+        })
+    }
+}
+
+impl GdAge<'static> {
+    /// Constructs the age string for the given amount of the given unit, e.g. `"1 day"` or
+    /// `"3 days"`
+    pub fn new(amount: u64, unit: AgeUnit) -> Self {
+        match amount {
+            1 => GdAge(Cow::Owned(format!("1 {}", unit.as_str()))),
+            _ => GdAge(Cow::Owned(format!("{} {}s", amount, unit.as_str()))),
+        }
+    }
+
+    /// Constructs the age string the servers would send for something that happened the given
+    /// amount of seconds ago.
+    ///
+    /// Like the servers, this uses the largest unit of which at least one has passed, and rounds
+    /// down, e.g. 13 days become `"1 week"`. See [`AgeUnit::seconds`] for the lengths of months and
+    /// years.
+    pub fn from_seconds(seconds: u64) -> Self {
+        AgeUnit::DESCENDING
+            .iter()
+            .find(|unit| seconds >= unit.seconds())
+            .map(|&unit| GdAge::new(seconds / unit.seconds(), unit))
+            .unwrap_or_else(|| GdAge::new(0, AgeUnit::Second))
+    }
+
+    /// Constructs the age string the servers would send for something that happened the given
+    /// duration ago. See [`GdAge::from_seconds`]
+    pub fn from_duration(duration: Duration) -> Self {
+        GdAge::from_seconds(duration.as_secs())
+    }
+
+    /// Constructs the age string the servers would send at the unix timestamp `now` for something
+    /// that happened at the unix timestamp `then`. Timestamps in the future are treated as having
+    /// happened just now.
+    pub fn between(then: u64, now: u64) -> Self {
+        GdAge::from_seconds(now.saturating_sub(then))
+    }
+}
+
+impl<'a> GdAge<'a> {
+    /// Parses this age into its amount and unit, e.g. `"3 months"` into `(3, AgeUnit::Month)`.
+    ///
+    /// Returns [`None`] if the string is not of the expected `"<amount> <unit>"` form. A trailing
+    /// `"ago"`, as displayed in-game, is accepted.
+    pub fn parse(&self) -> Option<(u64, AgeUnit)> {
+        let mut parts = self.0.split_whitespace();
+
+        let amount = parts.next()?.parse().ok()?;
+        let unit = AgeUnit::from_str(parts.next()?)?;
+
+        match parts.next() {
+            None | Some("ago") if parts.next().is_none() => Some((amount, unit)),
+            _ => None,
+        }
+    }
+
+    /// The (approximate) number of seconds this age represents. See [`AgeUnit::seconds`]
+    ///
+    /// Returns [`None`] if the age cannot be parsed, or if the number of seconds does not fit into a
+    /// `u64`.
+    pub fn as_seconds(&self) -> Option<u64> {
+        self.parse().and_then(|(amount, unit)| amount.checked_mul(unit.seconds()))
+    }
+
+    /// The (approximate) duration this age represents. See [`AgeUnit::seconds`]
+    #[cfg(feature = "chrono")]
+    pub fn to_duration(&self) -> Option<chrono::Duration> {
+        use core::convert::TryFrom;
+
+        self.as_seconds()
+            .and_then(|seconds| i64::try_from(seconds).ok())
+            .and_then(chrono::Duration::try_seconds)
+    }
+
+    /// The exact string this age was constructed from
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for GdAge<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AgeUnit, GdAge};
+    use std::{borrow::Cow, time::Duration};
+
+    fn age(s: &str) -> GdAge<'_> {
+        GdAge(Cow::Borrowed(s))
+    }
+
+    #[test]
+    fn parse_ages() {
+        assert_eq!(age("1 year").parse(), Some((1, AgeUnit::Year)));
+        assert_eq!(age("3 months").parse(), Some((3, AgeUnit::Month)));
+        assert_eq!(age("5 days ago").parse(), Some((5, AgeUnit::Day)));
+        assert_eq!(age("6 days").as_seconds(), Some(6 * 24 * 60 * 60));
+        assert_eq!(age("soon").parse(), None);
+        assert_eq!(age("3 fortnights").parse(), None);
+        assert_eq!(age("18446744073709551615 years").parse(), Some((u64::MAX, AgeUnit::Year)));
+        assert_eq!(age("18446744073709551615 years").as_seconds(), None);
+        assert_eq!(age("18446744073709551615 seconds").as_seconds(), Some(u64::MAX));
+    }
+
+    #[test]
+    fn format_ages() {
+        assert_eq!(GdAge::from_seconds(0).as_str(), "0 seconds");
+        assert_eq!(GdAge::from_seconds(1).as_str(), "1 second");
+        assert_eq!(GdAge::from_seconds(59).as_str(), "59 seconds");
+        assert_eq!(GdAge::from_seconds(60 * 60).as_str(), "1 hour");
+        assert_eq!(GdAge::from_duration(Duration::from_secs(13 * 24 * 60 * 60)).as_str(), "1 week");
+        assert_eq!(GdAge::from_seconds(29 * 24 * 60 * 60).as_str(), "4 weeks");
+        assert_eq!(GdAge::from_seconds(3 * 365 * 24 * 60 * 60).as_str(), "3 years");
+        assert_eq!(GdAge::between(100, 50).as_str(), "0 seconds");
+        assert_eq!(GdAge::new(6, AgeUnit::Month).parse(), Some((6, AgeUnit::Month)));
+    }
+}
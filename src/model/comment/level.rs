@@ -1,14 +1,21 @@
-use std::borrow::Cow;
-// use std::borrow::Cow;
+use alloc::{
+    borrow::Cow,
+    format,
+    string::{String, ToString},
+};
 
 use dash_rs_derive::Dash;
 use serde::{Deserialize, Serialize};
 use variant_partial_eq::VariantPartialEq;
 
 use crate::{
-    model::user::{Color, IconType, ModLevel},
+    model::{
+        age::GdAge,
+        comment::markup::Markup,
+        user::{Color, IconType, ModLevel},
+    },
     serde::{Base64Decoder, Thunk},
-    GJFormat, ProcessError, ThunkProcessor,
+    GJFormat, IntoOwnedThunkProcessor, ProcessError, ThunkProcessor,
 };
 
 #[derive(Debug, Eq, VariantPartialEq, Clone, Deserialize, Serialize, Dash)]
@@ -18,6 +25,13 @@ pub struct LevelComment<'a> {
     #[dash(no_index)]
     pub user: Option<CommentUser<'a>>,
 
+    /// The id of the level this [`LevelComment`] was posted on. Only provided when retrieving a
+    /// user's comment history, as otherwise the level is known from the request
+    #[dash(index = 1)]
+    #[dash(default)]
+    #[dash(skip_serializing_if = "Option::is_none")]
+    pub level_id: Option<u64>,
+
     /// The actual content of the [`LevelComment`] made.
     #[dash(index = 2)]
     #[serde(borrow)]
@@ -44,7 +58,7 @@ pub struct LevelComment<'a> {
 
     /// Robtop's completely braindead way of keeping track of when this [`LevelComment`] was posted
     #[dash(index = 9)]
-    pub time_since_post: Cow<'a, str>,
+    pub time_since_post: GdAge<'a>,
 
     /// If enabled by the user making this [`LevelComment`], the progress they have done on the
     /// level this comment is on.
@@ -69,6 +83,46 @@ impl<'de> GJFormat<'de> for LevelComment<'de> {
     const MAP_LIKE: bool = true;
 }
 
+impl<'a> LevelComment<'a> {
+    pub fn into_owned(self) -> LevelComment<'static> {
+        LevelComment {
+            user: self.user.map(CommentUser::into_owned),
+            level_id: self.level_id,
+            content: self.content.map(Thunk::into_owned),
+            user_id: self.user_id,
+            likes: self.likes,
+            comment_id: self.comment_id,
+            is_flagged_spam: self.is_flagged_spam,
+            time_since_post: self.time_since_post.into_owned(),
+            progress: self.progress,
+            mod_level: self.mod_level,
+            special_color: self.special_color.map(Thunk::into_owned),
+        }
+    }
+
+    /// Decodes the content of this [`LevelComment`] (if that has not happened yet) and splits it at
+    /// the color tags it contains. Returns [`None`] if the comment has no content.
+    ///
+    /// Since [`Markup`] is lossless, the content can be restored exactly via
+    /// [`LevelComment::set_markup`].
+    pub fn markup(&mut self) -> Option<Result<Markup<'_>, ProcessError>> {
+        let content = self.content.as_mut()?;
+
+        Some(content.process().map(|content| Markup::parse(content)))
+    }
+
+    /// Decodes the content of this [`LevelComment`] (if that has not happened yet) and strips all
+    /// color tags from it. Returns [`None`] if the comment has no content.
+    pub fn plain_content(&mut self) -> Option<Result<String, ProcessError>> {
+        self.markup().map(|markup| markup.map(|markup| markup.plain_text()))
+    }
+
+    /// Replaces the content of this [`LevelComment`] with the given [`Markup`]
+    pub fn set_markup(&mut self, markup: &Markup<'_>) {
+        self.content = Some(Thunk::Processed(Cow::Owned(markup.to_string())));
+    }
+}
+
 impl ThunkProcessor for Color {
     type Error = ProcessError;
     type Output<'a> = Color;
@@ -102,6 +156,12 @@ impl ThunkProcessor for Color {
     }
 }
 
+impl IntoOwnedThunkProcessor for Color {
+    fn output_into_owned(output: Self::Output<'_>) -> Self::Output<'static> {
+        output
+    }
+}
+
 #[derive(Debug, Eq, VariantPartialEq, Clone, Deserialize, Serialize, Dash)]
 pub struct CommentUser<'a> {
     /// This [`CommentUser`]'s name
@@ -144,3 +204,17 @@ impl<'de> GJFormat<'de> for CommentUser<'de> {
     const DELIMITER: &'static str = "~";
     const MAP_LIKE: bool = true;
 }
+
+impl<'a> CommentUser<'a> {
+    pub fn into_owned(self) -> CommentUser<'static> {
+        CommentUser {
+            name: Cow::Owned(self.name.into_owned()),
+            icon_index: self.icon_index,
+            primary_color: self.primary_color,
+            secondary_color: self.secondary_color,
+            icon_type: self.icon_type,
+            has_glow: self.has_glow,
+            account_id: self.account_id,
+        }
+    }
+}
@@ -0,0 +1,238 @@
+//! Module containing types for processing the color tags that can be contained in the text of
+//! comments
+//!
+//! ## GD Internals:
+//! Text displayed in Geometry Dash can contain markup of the form `<cX>text</c>`, where `X` is a
+//! single letter selecting the color `text` is displayed in. Tags that are never closed color the
+//! remainder of the text. All other tags are displayed as-is.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::{Display, Formatter};
+
+/// The color selected by a `<cX>` tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TagColor {
+    /// ## GD Internals:
+    /// This variant is represented by the tag `<cb>`
+    Blue,
+
+    /// ## GD Internals:
+    /// This variant is represented by the tag `<cg>`
+    Green,
+
+    /// ## GD Internals:
+    /// This variant is represented by the tag `<cl>`
+    LightBlue,
+
+    /// ## GD Internals:
+    /// This variant is represented by the tag `<co>`
+    Orange,
+
+    /// ## GD Internals:
+    /// This variant is represented by the tag `<cp>`
+    Pink,
+
+    /// ## GD Internals:
+    /// This variant is represented by the tag `<cr>`
+    Red,
+
+    /// ## GD Internals:
+    /// This variant is represented by the tag `<cy>`
+    Yellow,
+
+    /// Unknown color tag. This variant will be constructed if robtop ever adds more colors and will
+    /// hold the raw letter
+    Unknown(char),
+}
+
+impl From<char> for TagColor {
+    fn from(letter: char) -> Self {
+        match letter {
+            'b' => TagColor::Blue,
+            'g' => TagColor::Green,
+            'l' => TagColor::LightBlue,
+            'o' => TagColor::Orange,
+            'p' => TagColor::Pink,
+            'r' => TagColor::Red,
+            'y' => TagColor::Yellow,
+            letter => TagColor::Unknown(letter),
+        }
+    }
+}
+
+impl From<TagColor> for char {
+    fn from(color: TagColor) -> Self {
+        match color {
+            TagColor::Blue => 'b',
+            TagColor::Green => 'g',
+            TagColor::LightBlue => 'l',
+            TagColor::Orange => 'o',
+            TagColor::Pink => 'p',
+            TagColor::Red => 'r',
+            TagColor::Yellow => 'y',
+            TagColor::Unknown(letter) => letter,
+        }
+    }
+}
+
+/// A part of some [`Markup`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Segment<'a> {
+    /// Text displayed in the default color
+    Text(&'a str),
+
+    /// Text enclosed in a color tag
+    Colored {
+        color: TagColor,
+        text: &'a str,
+
+        /// Whether the tag was closed by `</c>`, or extended until the end of the text
+        closed: bool,
+    },
+}
+
+/// Text split at its color tags
+///
+/// Parsing is lossless: The [`Display`] implementation reproduces the exact text this was parsed
+/// from, including malformed and unknown tags.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Markup<'a> {
+    pub segments: Vec<Segment<'a>>,
+}
+
+impl<'a> Markup<'a> {
+    /// Splits the given text at its color tags
+    pub fn parse(text: &'a str) -> Markup<'a> {
+        let mut segments = Vec::new();
+        let mut rest = text;
+
+        while !rest.is_empty() {
+            let (color, start) = match find_opening_tag(rest) {
+                Some(tag) => tag,
+                None => {
+                    segments.push(Segment::Text(rest));
+                    break;
+                },
+            };
+
+            if start > 0 {
+                segments.push(Segment::Text(&rest[..start]));
+            }
+
+            // Opening tags are always 4 bytes long, since the color is an ASCII letter
+            rest = &rest[start + 4..];
+
+            match rest.find("</c>") {
+                Some(end) => {
+                    segments.push(Segment::Colored {
+                        color,
+                        text: &rest[..end],
+                        closed: true,
+                    });
+                    rest = &rest[end + 4..];
+                },
+                None => {
+                    segments.push(Segment::Colored {
+                        color,
+                        text: rest,
+                        closed: false,
+                    });
+                    break;
+                },
+            }
+        }
+
+        Markup { segments }
+    }
+
+    /// The text without any color tags, as it would be displayed in-game
+    pub fn plain_text(&self) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) | Segment::Colored { text, .. } => *text,
+            })
+            .collect()
+    }
+}
+
+impl Display for Markup<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(text) => f.write_str(text)?,
+                Segment::Colored { color, text, closed } => {
+                    write!(f, "<c{}>{}", char::from(*color), text)?;
+
+                    if *closed {
+                        f.write_str("</c>")?;
+                    }
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Finds the first opening color tag in the given text, returning its color and byte offset
+fn find_opening_tag(text: &str) -> Option<(TagColor, usize)> {
+    text.match_indices("<c").find_map(|(start, _)| {
+        let tag = text.as_bytes().get(start + 2..start + 4)?;
+
+        match tag {
+            [letter, b'>'] if letter.is_ascii_alphabetic() => Some((TagColor::from(*letter as char), start)),
+            _ => None,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Markup, Segment, TagColor};
+    use alloc::string::ToString;
+
+    #[test]
+    fn parse_color_tags() {
+        let text = "hello <cg>green</c> and <cr>red";
+        let markup = Markup::parse(text);
+
+        assert_eq!(
+            markup.segments,
+            vec![
+                Segment::Text("hello "),
+                Segment::Colored {
+                    color: TagColor::Green,
+                    text: "green",
+                    closed: true
+                },
+                Segment::Text(" and "),
+                Segment::Colored {
+                    color: TagColor::Red,
+                    text: "red",
+                    closed: false
+                },
+            ]
+        );
+        assert_eq!(markup.plain_text(), "hello green and red");
+        assert_eq!(markup.to_string(), text);
+    }
+
+    #[test]
+    fn malformed_tags_round_trip() {
+        for text in [
+            "",
+            "<c",
+            "<cg",
+            "<c1>x</c>",
+            "a</c>b",
+            "<cg><cr>x</c></c>",
+            "<cx>?</c>",
+            "<cg>ü</c>😀",
+        ]
+        .iter()
+        {
+            assert_eq!(Markup::parse(text).to_string(), *text);
+        }
+    }
+}
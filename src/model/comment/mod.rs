@@ -1,2 +1,4 @@
 pub mod level;
+pub mod markup;
 pub mod profile;
+pub mod thread;
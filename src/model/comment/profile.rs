@@ -1,10 +1,10 @@
 use crate::{
+    model::age::GdAge,
     serde::{Base64Decoder, Thunk},
     GJFormat,
 };
 use dash_rs_derive::Dash;
 use serde::{Deserialize, Serialize};
-use std::borrow::Cow;
 use variant_partial_eq::VariantPartialEq;
 
 #[derive(Debug, Serialize, Deserialize, Eq, VariantPartialEq, Clone, Dash)]
@@ -27,10 +27,21 @@ pub struct ProfileComment<'a> {
     /// Robtop's completely braindead way of keeping track of when this [`ProfileComment`] was
     /// posted
     #[dash(index = 9)]
-    pub time_since_post: Cow<'a, str>,
+    pub time_since_post: GdAge<'a>,
 }
 
 impl<'de> GJFormat<'de> for ProfileComment<'de> {
     const DELIMITER: &'static str = "~";
     const MAP_LIKE: bool = true;
 }
+
+impl<'a> ProfileComment<'a> {
+    pub fn into_owned(self) -> ProfileComment<'static> {
+        ProfileComment {
+            content: self.content.map(Thunk::into_owned),
+            likes: self.likes,
+            comment_id: self.comment_id,
+            time_since_post: self.time_since_post.into_owned(),
+        }
+    }
+}
@@ -0,0 +1,306 @@
+//! Module containing the [`CommentThread`] type, which flattens multiple pages of
+//! [`LevelComment`]s into a single list
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+use core::cmp::Reverse;
+
+use crate::{model::comment::level::LevelComment, request::comment::SortMode, response::Paged};
+
+/// All comments retrieved for some level (or from some user's comment history), merged from
+/// possibly multiple pages
+///
+/// Comments are deduplicated by their [`comment_id`](LevelComment::comment_id), as comments can
+/// shift between pages if new comments are posted while browsing. If a comment is contained in
+/// multiple pages, the version from the most recently added page is kept.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommentThread<'a> {
+    comments: Vec<LevelComment<'a>>,
+
+    /// Maps comment IDs to the position of the respective comment in `comments`
+    positions: BTreeMap<u64, usize>,
+    fetched_pages: BTreeSet<u32>,
+    total: Option<u32>,
+    page_size: Option<u32>,
+}
+
+impl<'a> CommentThread<'a> {
+    /// Constructs a new, empty `CommentThread`
+    pub fn new() -> Self {
+        CommentThread::default()
+    }
+
+    /// Merges the comments from the given page into this thread, and records the page as fetched
+    pub fn add_page(&mut self, page: Paged<LevelComment<'a>>) {
+        self.fetched_pages.insert(page.page());
+        self.total = Some(page.total);
+        self.page_size = Some(page.page_size);
+
+        for comment in page.items {
+            self.insert(comment);
+        }
+    }
+
+    /// Merges a single comment into this thread, replacing any existing comment with the same ID
+    pub fn insert(&mut self, comment: LevelComment<'a>) {
+        match self.positions.get(&comment.comment_id) {
+            Some(&position) => self.comments[position] = comment,
+            None => {
+                self.positions.insert(comment.comment_id, self.comments.len());
+                self.comments.push(comment);
+            },
+        }
+    }
+
+    /// The comments in this thread, in the order they were added (or last sorted in)
+    pub fn comments(&self) -> &[LevelComment<'a>] {
+        &self.comments
+    }
+
+    /// The (0-based) indices of all pages that were added to this thread
+    pub fn fetched_pages(&self) -> impl Iterator<Item = u32> + '_ {
+        self.fetched_pages.iter().copied()
+    }
+
+    /// Whether the page with the given (0-based) index was added to this thread
+    pub fn has_page(&self, page: u32) -> bool {
+        self.fetched_pages.contains(&page)
+    }
+
+    /// The (0-based) indices of all pages that have not been added to this thread yet, according
+    /// to the pagination information of the most recently added page.
+    ///
+    /// Empty if no page was added yet. Since the page count is reported by the server, the pages
+    /// are computed lazily instead of being collected up front.
+    pub fn missing_pages(&self) -> impl Iterator<Item = u32> + '_ {
+        (0..self.page_count()).filter(move |page| !self.has_page(*page))
+    }
+
+    /// Whether all available pages have been added to this thread
+    pub fn is_complete(&self) -> bool {
+        let page_count = self.page_count();
+
+        // Only iterates over the pages actually fetched, regardless of the reported page count
+        self.total.is_some() && self.fetched_pages.range(..page_count).count() == page_count as usize
+    }
+
+    /// The total amount of comments available, as reported by the most recently added page
+    pub fn total(&self) -> Option<u32> {
+        self.total
+    }
+
+    /// The total amount of pages available, as reported by the most recently added page
+    pub fn page_count(&self) -> u32 {
+        match (self.total, self.page_size) {
+            (Some(total), Some(page_size)) if page_size != 0 => total.div_ceil(page_size),
+            _ => 0,
+        }
+    }
+
+    /// The amount of (distinct) comments in this thread
+    pub fn len(&self) -> usize {
+        self.comments.len()
+    }
+
+    /// Whether this thread contains no comments
+    pub fn is_empty(&self) -> bool {
+        self.comments.is_empty()
+    }
+
+    /// Sorts the comments in this thread the same way the server would for the given
+    /// [`SortMode`].
+    ///
+    /// Ties (and, when sorting by age, comments whose age cannot be parsed) are ordered by
+    /// descending comment ID, as comment IDs are assigned in increasing order.
+    pub fn sort(&mut self, mode: SortMode) {
+        match mode {
+            SortMode::Liked => self
+                .comments
+                .sort_by_key(|comment| (Reverse(comment.likes), Reverse(comment.comment_id))),
+            SortMode::Recent => self.comments.sort_by_key(|comment| {
+                (
+                    comment.time_since_post.as_seconds().unwrap_or(u64::MAX),
+                    Reverse(comment.comment_id),
+                )
+            }),
+        }
+
+        self.positions = self
+            .comments
+            .iter()
+            .enumerate()
+            .map(|(position, comment)| (comment.comment_id, position))
+            .collect();
+    }
+
+    /// Splits this thread back into pages of at most `page_size` comments each, in the current
+    /// order
+    pub fn into_pages(self, page_size: u32) -> Vec<Paged<LevelComment<'a>>> {
+        let total = self.comments.len() as u32;
+        let mut pages = Vec::new();
+        let mut comments = self.comments.into_iter().peekable();
+        let mut offset = 0;
+
+        while comments.peek().is_some() && page_size != 0 {
+            let items: Vec<_> = comments.by_ref().take(page_size as usize).collect();
+            let len = items.len() as u32;
+
+            pages.push(Paged {
+                items,
+                total,
+                offset,
+                page_size,
+            });
+
+            offset += len;
+        }
+
+        pages
+    }
+
+    /// Consumes this thread, returning its comments
+    pub fn into_comments(self) -> Vec<LevelComment<'a>> {
+        self.comments
+    }
+
+    pub fn into_owned(self) -> CommentThread<'static> {
+        CommentThread {
+            comments: self.comments.into_iter().map(LevelComment::into_owned).collect(),
+            positions: self.positions,
+            fetched_pages: self.fetched_pages,
+            total: self.total,
+            page_size: self.page_size,
+        }
+    }
+}
+
+impl<'a> From<Paged<LevelComment<'a>>> for CommentThread<'a> {
+    fn from(page: Paged<LevelComment<'a>>) -> Self {
+        let mut thread = CommentThread::new();
+        thread.add_page(page);
+        thread
+    }
+}
+
+impl<'a> Extend<Paged<LevelComment<'a>>> for CommentThread<'a> {
+    fn extend<I: IntoIterator<Item = Paged<LevelComment<'a>>>>(&mut self, pages: I) {
+        for page in pages {
+            self.add_page(page)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommentThread;
+    use crate::{
+        model::{age::GdAge, comment::level::LevelComment, user::ModLevel},
+        request::comment::SortMode,
+        response::Paged,
+    };
+    use alloc::{borrow::Cow, vec, vec::Vec};
+
+    fn comment(comment_id: u64, likes: i32, age: &'static str) -> LevelComment<'static> {
+        LevelComment {
+            user: None,
+            level_id: None,
+            content: None,
+            user_id: 0,
+            likes,
+            comment_id,
+            is_flagged_spam: false,
+            time_since_post: GdAge(Cow::Borrowed(age)),
+            progress: None,
+            mod_level: ModLevel::None,
+            special_color: None,
+        }
+    }
+
+    fn page(items: Vec<LevelComment<'static>>, offset: u32) -> Paged<LevelComment<'static>> {
+        Paged {
+            items,
+            total: 5,
+            offset,
+            page_size: 2,
+        }
+    }
+
+    fn ids(thread: &CommentThread) -> Vec<u64> {
+        thread.comments().iter().map(|comment| comment.comment_id).collect()
+    }
+
+    #[test]
+    fn pages_are_merged_and_deduplicated() {
+        let mut thread = CommentThread::from(page(vec![comment(5, 0, "1 hour"), comment(4, 3, "2 hours")], 0));
+
+        assert_eq!(thread.missing_pages().collect::<Vec<_>>(), vec![1, 2]);
+        assert!(!thread.is_complete());
+
+        // a new comment was posted in between, shifting comment 4 onto the second page
+        thread.extend(vec![
+            page(vec![comment(4, 7, "2 hours"), comment(3, 1, "1 day")], 2),
+            page(vec![comment(2, 7, "3 days")], 4),
+        ]);
+
+        assert_eq!(ids(&thread), vec![5, 4, 3, 2]);
+        assert_eq!(thread.comments()[1].likes, 7);
+        assert_eq!(thread.fetched_pages().collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert!(thread.is_complete());
+
+        // comments inserted after sorting still replace the existing ones
+        thread.sort(SortMode::Liked);
+        thread.insert(comment(5, 9, "1 hour"));
+
+        assert_eq!(ids(&thread), vec![4, 2, 3, 5]);
+        assert_eq!(thread.comments()[3].likes, 9);
+    }
+
+    #[test]
+    fn huge_page_counts() {
+        let thread = CommentThread::from(Paged {
+            items: vec![comment(1, 0, "1 hour")],
+            total: u32::MAX,
+            offset: 0,
+            page_size: 1,
+        });
+
+        assert_eq!(thread.page_count(), u32::MAX);
+        assert_eq!(thread.missing_pages().next(), Some(1));
+        assert!(!thread.is_complete());
+    }
+
+    #[test]
+    fn sorting() {
+        let mut thread = CommentThread::new();
+
+        thread.insert(comment(1, 10, "1 year"));
+        thread.insert(comment(2, 10, "3 months"));
+        thread.insert(comment(3, 20, "2 weeks"));
+        thread.insert(comment(4, 0, "2 weeks"));
+
+        thread.sort(SortMode::Liked);
+        assert_eq!(ids(&thread), vec![3, 2, 1, 4]);
+
+        thread.sort(SortMode::Recent);
+        assert_eq!(ids(&thread), vec![4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn into_pages() {
+        let mut thread = CommentThread::new();
+
+        for id in 0..5 {
+            thread.insert(comment(id, 0, "1 second"));
+        }
+
+        let pages = thread.into_pages(2);
+
+        assert_eq!(pages.len(), 3);
+        assert_eq!(pages[2].page(), 2);
+        assert_eq!(pages[2].items.len(), 1);
+        assert!(pages[1].has_next_page());
+        assert!(!pages[2].has_next_page());
+    }
+}
@@ -1,6 +1,6 @@
+use alloc::borrow::Cow;
 use dash_rs_derive::Dash;
 use serde::{Deserialize, Serialize};
-use std::borrow::Cow;
 use variant_partial_eq::VariantPartialEq;
 
 use crate::GJFormat;
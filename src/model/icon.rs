@@ -0,0 +1,166 @@
+//! Module containing types describing the icons and colors a player uses
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::{
+    comment::level::CommentUser,
+    user::{profile::Profile, searched::SearchedUser, Color, IconType},
+};
+
+/// The icons a player uses, together with their colors
+///
+/// Which icons are known depends on where this set was obtained from: A [`Profile`] contains all
+/// icons, while [`SearchedUser`]s and [`CommentUser`]s only contain the icon displayed next to
+/// them (see [`IconSet::displayed`]).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct IconSet {
+    pub cube: Option<u16>,
+    pub ship: Option<u16>,
+    pub ball: Option<u16>,
+    pub ufo: Option<u16>,
+    pub wave: Option<u16>,
+    pub robot: Option<u16>,
+    pub spider: Option<u16>,
+    pub swing: Option<u16>,
+    pub jetpack: Option<u16>,
+
+    /// The type of the icon displayed next to the player's name, e.g. in comments and search
+    /// results. Always [`IconType::Cube`] for icon sets obtained from a [`Profile`]
+    pub displayed: IconType,
+
+    pub primary_color: Color,
+    pub secondary_color: Color,
+    pub glow: bool,
+}
+
+impl IconSet {
+    /// Constructs an icon set only containing the given icon
+    fn single(icon_type: IconType, index: u16, primary_color: Color, secondary_color: Color, glow: bool) -> Self {
+        let mut icon_set = IconSet {
+            cube: None,
+            ship: None,
+            ball: None,
+            ufo: None,
+            wave: None,
+            robot: None,
+            spider: None,
+            swing: None,
+            jetpack: None,
+            displayed: icon_type,
+            primary_color,
+            secondary_color,
+            glow,
+        };
+
+        if let Some(slot) = icon_set.slot_mut(icon_type) {
+            *slot = Some(index);
+        }
+
+        icon_set
+    }
+
+    fn slot_mut(&mut self, icon_type: IconType) -> Option<&mut Option<u16>> {
+        match icon_type {
+            IconType::Cube => Some(&mut self.cube),
+            IconType::Ship => Some(&mut self.ship),
+            IconType::Ball => Some(&mut self.ball),
+            IconType::Ufo => Some(&mut self.ufo),
+            IconType::Wave => Some(&mut self.wave),
+            IconType::Robot => Some(&mut self.robot),
+            IconType::Spider => Some(&mut self.spider),
+            IconType::Swing => Some(&mut self.swing),
+            IconType::Jetpack => Some(&mut self.jetpack),
+            IconType::Unknown(_) => None,
+        }
+    }
+
+    /// The (1-based) index of the icon of the given type, if known
+    pub fn icon(&self, icon_type: IconType) -> Option<u16> {
+        match icon_type {
+            IconType::Cube => self.cube,
+            IconType::Ship => self.ship,
+            IconType::Ball => self.ball,
+            IconType::Ufo => self.ufo,
+            IconType::Wave => self.wave,
+            IconType::Robot => self.robot,
+            IconType::Spider => self.spider,
+            IconType::Swing => self.swing,
+            IconType::Jetpack => self.jetpack,
+            IconType::Unknown(_) => None,
+        }
+    }
+
+    /// The (1-based) index of the icon displayed next to the player's name, if known
+    pub fn displayed_icon(&self) -> Option<u16> {
+        self.icon(self.displayed)
+    }
+}
+
+impl Profile<'_> {
+    /// The icons this [`Profile`] uses
+    pub fn icon_set(&self) -> IconSet {
+        IconSet {
+            cube: Some(self.cube_index),
+            ship: Some(self.ship_index as u16),
+            ball: Some(self.ball_index as u16),
+            ufo: Some(self.ufo_index as u16),
+            wave: Some(self.wave_index as u16),
+            robot: Some(self.robot_index as u16),
+            spider: Some(self.spider_index as u16),
+            swing: Some(self.swing_index as u16),
+            jetpack: Some(self.jetpack_index as u16),
+            displayed: IconType::Cube,
+            primary_color: self.primary_color,
+            secondary_color: self.secondary_color,
+            glow: self.has_glow,
+        }
+    }
+}
+
+impl SearchedUser<'_> {
+    /// The icon displayed next to this [`SearchedUser`]
+    pub fn icon_set(&self) -> IconSet {
+        IconSet::single(
+            self.icon_type,
+            self.icon_index,
+            self.primary_color,
+            self.secondary_color,
+            self.has_glow,
+        )
+    }
+}
+
+impl CommentUser<'_> {
+    /// The icon displayed next to this [`CommentUser`]'s comments
+    pub fn icon_set(&self) -> IconSet {
+        IconSet::single(
+            self.icon_type,
+            self.icon_index,
+            self.primary_color,
+            self.secondary_color,
+            self.has_glow,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IconSet;
+    use crate::model::user::{Color, IconType};
+
+    #[test]
+    fn resolve_colors() {
+        assert_eq!(Color::from(9).rgb(), Some((255, 0, 0)));
+        assert_eq!(Color::from(16).rgb_u32(), Some(0x00C8FF));
+        assert_eq!(Color::from(200).rgb(), None);
+    }
+
+    #[test]
+    fn single_icon() {
+        let icons = IconSet::single(IconType::Ufo, 12, Color::from(0), Color::from(3), true);
+
+        assert_eq!(icons.displayed_icon(), Some(12));
+        assert_eq!(icons.icon(IconType::Cube), None);
+        assert_eq!(icons.primary_color, Color::Known(125, 255, 0));
+    }
+}
@@ -0,0 +1,351 @@
+//! Module containing analysis routines operating on parsed level data
+
+use crate::model::level::{
+    metadata::LevelMetadata,
+    object::{ids, layer::ZLayer, speed::Speed, LevelObject, ObjectData},
+    Objects,
+};
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+use serde::{Deserialize, Serialize};
+
+/// A point in a level at which the player's speed changes
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpeedChange {
+    /// The x position at which the speed change happens
+    pub x: f32,
+
+    /// The time (in seconds) after the level start at which the player reaches this speed change
+    pub time: f32,
+
+    /// The speed the player has after this speed change
+    pub speed: Speed,
+}
+
+/// The result of analyzing some level's objects, see [`LevelMetadata::analysis`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelAnalysis {
+    /// The length of the level, in seconds
+    ///
+    /// This is the time it takes the player to reach the furthest object in the level.
+    pub length_in_seconds: f32,
+
+    /// All speed changes in the level, in the order the player encounters them.
+    ///
+    /// The first entry always describes the level's starting speed at position `0`. Only speed
+    /// portals that are "checked" (meaning the player actually passes through them) are included.
+    /// Since the effect of speeds unknown to dash-rs cannot be determined, portals of unknown
+    /// speed are left out, and an unknown starting speed is assumed to be [`Speed::Normal`].
+    pub speed_timeline: Vec<SpeedChange>,
+
+    /// The amount of objects for each object id present in the level
+    pub object_counts: BTreeMap<u16, usize>,
+
+    /// The amount of jump orbs (of any color) in the level
+    pub orb_count: usize,
+
+    /// The amount of jump pads (of any color) in the level
+    pub pad_count: usize,
+}
+
+impl LevelAnalysis {
+    /// The amount of objects with the given id in the level
+    pub fn object_count(&self, id: u16) -> usize {
+        self.object_counts.get(&id).copied().unwrap_or(0)
+    }
+
+    /// The total amount of objects in the level
+    pub fn total_objects(&self) -> usize {
+        self.object_counts.values().sum()
+    }
+
+    /// The speed the player has at the given x position
+    pub fn speed_at(&self, x: f32) -> Speed {
+        self.speed_timeline
+            .iter()
+            .take_while(|change| change.x <= x)
+            .last()
+            .map(|change| change.speed)
+            .unwrap_or_default()
+    }
+}
+
+impl LevelMetadata {
+    /// Analyzes the given objects, which are assumed to belong to the level this metadata section
+    /// was taken from.
+    pub fn analysis(&self, objects: &[LevelObject]) -> LevelAnalysis {
+        let mut object_counts = BTreeMap::new();
+        let mut orb_count = 0;
+        let mut pad_count = 0;
+
+        for object in objects {
+            if ids::ORBS.contains(&object.id) {
+                orb_count += 1;
+            } else if ids::PADS.contains(&object.id) {
+                pad_count += 1;
+            }
+
+            *object_counts.entry(object.id).or_insert(0) += 1;
+        }
+
+        let (speed_timeline, furthest_x) = self.speed_timeline(objects);
+
+        LevelAnalysis {
+            length_in_seconds: time_at(&speed_timeline, furthest_x),
+            speed_timeline,
+            object_counts,
+            orb_count,
+            pad_count,
+        }
+    }
+
+    /// Computes the [`LevelAnalysis::speed_timeline`] of the given objects, together with the x
+    /// position of the furthest object
+    ///
+    /// Speeds unknown to dash-rs are skipped: Portals of unknown speed are ignored, and an unknown
+    /// starting speed is replaced with the default speed.
+    fn speed_timeline(&self, objects: &[LevelObject]) -> (Vec<SpeedChange>, f32) {
+        let mut portals = Vec::new();
+        let mut furthest_x = 0.0;
+
+        for object in objects {
+            match object.metadata {
+                ObjectData::SpeedPortal {
+                    checked: true,
+                    speed: Speed::Unknown(_),
+                } => {},
+                ObjectData::SpeedPortal { checked: true, speed } => portals.push((object.x, speed)),
+                _ => {},
+            }
+
+            furthest_x = f32::max(furthest_x, object.x);
+        }
+
+        portals.sort_by(|(x1, _), (x2, _)| x1.total_cmp(x2));
+
+        let mut speed_timeline = vec![SpeedChange {
+            x: 0.0,
+            time: 0.0,
+            speed: match self.starting_speed {
+                Speed::Unknown(_) => Speed::default(),
+                speed => speed,
+            },
+        }];
+
+        for (x, speed) in portals {
+            speed_timeline.push(SpeedChange {
+                x,
+                time: time_at(&speed_timeline, x),
+                speed,
+            })
+        }
+
+        (speed_timeline, furthest_x)
+    }
+}
+
+/// The time it takes the player to reach the given x position, which must not lie before the last
+/// entry of the given (non-empty) speed timeline
+fn time_at(speed_timeline: &[SpeedChange], x: f32) -> f32 {
+    let last = speed_timeline[speed_timeline.len() - 1];
+
+    last.time + (x - last.x) / f32::from(last.speed)
+}
+
+impl Objects {
+    /// Analyzes the objects in this level, see [`LevelMetadata::analysis`]
+    pub fn analysis(&self) -> LevelAnalysis {
+        self.meta.analysis(&self.objects)
+    }
+
+    /// The length of this level in seconds, see [`LevelAnalysis::length_in_seconds`]
+    pub fn length_in_seconds(&self) -> f32 {
+        let (speed_timeline, furthest_x) = self.meta.speed_timeline(&self.objects);
+
+        time_at(&speed_timeline, furthest_x)
+    }
+
+    /// Groups the objects in this level by their editor layer.
+    ///
+    /// Objects on two editor layers are contained in the groups of both layers.
+    pub fn by_editor_layer(&self) -> BTreeMap<u16, Vec<&LevelObject>> {
+        let mut layers: BTreeMap<u16, Vec<&LevelObject>> = BTreeMap::new();
+
+        for object in &self.objects {
+            layers.entry(object.editor_layer).or_default().push(object);
+
+            if object.editor_layer_2 != 0 && object.editor_layer_2 != object.editor_layer {
+                layers.entry(object.editor_layer_2).or_default().push(object);
+            }
+        }
+
+        layers
+    }
+
+    /// Groups the objects in this level by their [`ZLayer`], ordering the objects in each group by
+    /// their z order (in the order they are rendered).
+    pub fn by_z_layer(&self) -> BTreeMap<ZLayer, Vec<&LevelObject>> {
+        let mut layers: BTreeMap<ZLayer, Vec<&LevelObject>> = BTreeMap::new();
+
+        for object in &self.objects {
+            layers.entry(object.z_layer).or_default().push(object);
+        }
+
+        for objects in layers.values_mut() {
+            // stable sort, so objects with the same z order remain in level string order
+            objects.sort_by_key(|object| object.z_order);
+        }
+
+        layers
+    }
+
+    /// Removes all objects that are not on any of the given editor layers from this level
+    pub fn retain_editor_layers(&mut self, layers: &[u16]) {
+        self.objects
+            .retain(|object| layers.iter().any(|&layer| object.is_on_editor_layer(layer)))
+    }
+
+    /// Removes all objects that are not on any of the given z layers from this level
+    pub fn retain_z_layers(&mut self, layers: &[ZLayer]) {
+        self.objects.retain(|object| layers.contains(&object.z_layer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        model::level::{
+            metadata::LevelMetadata,
+            object::{ids, layer::ZLayer, speed::Speed, LevelObject, ObjectData},
+            Objects,
+        },
+        GJFormat,
+    };
+    use alloc::{string::String, vec::Vec};
+
+    fn object(id: u16, x: f32, metadata: ObjectData) -> LevelObject {
+        LevelObject {
+            metadata,
+            ..LevelObject::new(id, x, 15.0)
+        }
+    }
+
+    #[test]
+    fn analyze_speed_changes() {
+        let metadata = LevelMetadata {
+            starting_speed: Speed::Slow,
+            ..Default::default()
+        };
+        let objects = [
+            object(1, 1000.0, ObjectData::Unknown),
+            object(
+                ids::FAST_PORTAL,
+                251.16,
+                ObjectData::SpeedPortal {
+                    checked: true,
+                    speed: Speed::Fast,
+                },
+            ),
+            object(
+                ids::SLOW_PORTAL,
+                500.0,
+                ObjectData::SpeedPortal {
+                    checked: false,
+                    speed: Speed::Slow,
+                },
+            ),
+            object(ids::YELLOW_ORB, 300.0, ObjectData::Unknown),
+            object(ids::YELLOW_PAD, 400.0, ObjectData::Unknown),
+            object(ids::PINK_PAD, 450.0, ObjectData::Unknown),
+        ];
+
+        let analysis = metadata.analysis(&objects);
+
+        assert_eq!(analysis.speed_timeline.len(), 2);
+        assert_eq!(analysis.speed_timeline[1].time, 1.0);
+        assert_eq!(analysis.speed_at(100.0), Speed::Slow);
+        assert_eq!(analysis.speed_at(600.0), Speed::Fast);
+        assert_eq!(analysis.length_in_seconds, 1.0 + (1000.0 - 251.16) / 468.0);
+        assert_eq!(analysis.orb_count, 1);
+        assert_eq!(analysis.pad_count, 2);
+        assert_eq!(analysis.object_count(ids::YELLOW_PAD), 1);
+        assert_eq!(analysis.total_objects(), 6);
+        assert_eq!(
+            Objects {
+                meta: metadata,
+                objects: objects.to_vec(),
+                ..Default::default()
+            }
+            .length_in_seconds(),
+            analysis.length_in_seconds
+        );
+    }
+
+    #[test]
+    fn unknown_speeds_are_skipped() {
+        let metadata = LevelMetadata {
+            starting_speed: Speed::Unknown(9),
+            ..Default::default()
+        };
+        let objects = [
+            object(1, 1000.0, ObjectData::Unknown),
+            object(
+                ids::FAST_PORTAL,
+                500.0,
+                ObjectData::SpeedPortal {
+                    checked: true,
+                    speed: Speed::Unknown(7),
+                },
+            ),
+        ];
+
+        let analysis = metadata.analysis(&objects);
+
+        assert_eq!(analysis.speed_timeline.len(), 1);
+        assert_eq!(analysis.speed_at(600.0), Speed::Normal);
+        assert_eq!(analysis.length_in_seconds, 1000.0 / 311.58);
+        assert!(analysis.length_in_seconds.is_finite());
+    }
+
+    #[test]
+    fn layers() {
+        let objects = "1,1,2,15,3,15,20,2;1,1,2,45,3,15,20,1,61,2,24,-3,25,4;1,1,2,75,3,15,24,-3,25,-1;1,1,2,105,3,15,24,7"
+            .split(';')
+            .map(LevelObject::from_gj_str)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let mut level = Objects {
+            objects,
+            ..Default::default()
+        };
+
+        let editor_layers = level.by_editor_layer();
+
+        assert_eq!(editor_layers.keys().copied().collect::<Vec<_>>(), [0, 1, 2]);
+        assert_eq!(editor_layers[&2].iter().map(|object| object.x).collect::<Vec<_>>(), [15.0, 45.0]);
+
+        let z_layers = level.by_z_layer();
+
+        assert_eq!(
+            z_layers.keys().copied().collect::<Vec<_>>(),
+            [ZLayer::B4, ZLayer::Default, ZLayer::T2]
+        );
+        assert_eq!(
+            z_layers[&ZLayer::B4].iter().map(|object| object.x).collect::<Vec<_>>(),
+            [75.0, 45.0]
+        );
+
+        level.retain_editor_layers(&[2]);
+
+        assert_eq!(level.objects.len(), 2);
+
+        level.retain_z_layers(&[ZLayer::B4]);
+
+        assert_eq!(level.objects.len(), 1);
+
+        let mut serialized = Vec::new();
+        level.objects[0].write_gj(&mut serialized).unwrap();
+        let serialized = String::from_utf8(serialized).unwrap();
+
+        assert_eq!(LevelObject::from_gj_str(&serialized).unwrap(), level.objects[0]);
+    }
+}
@@ -0,0 +1,271 @@
+//! Module containing the [`LevelBuilder`] struct, which allows constructing [`Level`]s from plain
+//! Rust values, e.g. for GDPS implementations
+
+use alloc::borrow::Cow;
+
+use crate::{
+    model::{
+        age::GdAge,
+        level::{
+            rating::Rating, Featured, Level, LevelData, LevelLength, LevelProcessError, LevelRating, LevelString, Objects, Password,
+            TwoPlayer,
+        },
+        song::MainSong,
+        GameVersion,
+    },
+    serde::{Thunk, UnknownIndices},
+};
+
+/// Builder for [`Level`]s
+///
+/// All values are provided in their decoded form (e.g. the description as plain text, and the
+/// level data as either [`Objects`] or a decompressed level string). Serializing the built
+/// [`Level`] into RobTop's format performs all required encodings. Constructed via
+/// [`Level::builder`].
+///
+/// Values not explicitly set default to those of a freshly uploaded, unrated level.
+#[derive(Debug)]
+pub struct LevelBuilder<'a> {
+    level: Level<'a, (), Option<u64>, u64>,
+    objects: Option<Objects>,
+    level_string: Option<Cow<'a, str>>,
+    password: Password,
+    time_since_upload: GdAge<'a>,
+    time_since_update: GdAge<'a>,
+}
+
+impl<'a> Level<'a> {
+    /// Constructs a [`LevelBuilder`] for the level with the given ID and name
+    pub fn builder(level_id: u64, name: impl Into<Cow<'a, str>>) -> LevelBuilder<'a> {
+        LevelBuilder::new(level_id, name)
+    }
+}
+
+impl<'a> LevelBuilder<'a> {
+    /// Constructs a [`LevelBuilder`] for the level with the given ID and name
+    pub fn new(level_id: u64, name: impl Into<Cow<'a, str>>) -> Self {
+        LevelBuilder {
+            level: Level {
+                level_id,
+                name: name.into(),
+                description: None,
+                version: 1,
+                creator: 0,
+                rating: Rating {
+                    level_rating: LevelRating::NotAvailable,
+                    stars: 0,
+                    featured: Featured::NotFeatured,
+                    epic: 0,
+                    coin_amount: 0,
+                    coins_verified: false,
+                },
+                rating_sum: None,
+                downloads: 0,
+                main_song: Some(MainSong::from(0)),
+                gd_version: GameVersion::from(22),
+                likes: 0,
+                length: LevelLength::Tiny,
+                copy_of: None,
+                two_player: TwoPlayer::from(false),
+                custom_song: None,
+                stars_requested: None,
+                object_amount: None,
+                index_46: None,
+                index_47: None,
+                unknown_indices: UnknownIndices::new(),
+                level_data: (),
+            },
+            objects: None,
+            level_string: None,
+            password: Password::NoCopy,
+            time_since_upload: GdAge(Cow::Borrowed("0 seconds")),
+            time_since_update: GdAge(Cow::Borrowed("0 seconds")),
+        }
+    }
+
+    /// Sets the level's description, as plain (not base64 encoded) text
+    pub fn description(mut self, description: impl Into<Cow<'a, str>>) -> Self {
+        self.level.description = Some(Thunk::Processed(description.into()));
+        self
+    }
+
+    pub fn version(mut self, version: u32) -> Self {
+        self.level.version = version;
+        self
+    }
+
+    /// Sets the user ID of the level's creator
+    pub fn creator(mut self, user_id: u64) -> Self {
+        self.level.creator = user_id;
+        self
+    }
+
+    pub fn rating(mut self, rating: Rating) -> Self {
+        self.level.rating = rating;
+        self
+    }
+
+    pub fn downloads(mut self, downloads: u32) -> Self {
+        self.level.downloads = downloads;
+        self
+    }
+
+    pub fn likes(mut self, likes: i32) -> Self {
+        self.level.likes = likes;
+        self
+    }
+
+    pub fn length(mut self, length: LevelLength) -> Self {
+        self.level.length = length;
+        self
+    }
+
+    pub fn gd_version(mut self, gd_version: GameVersion) -> Self {
+        self.level.gd_version = gd_version;
+        self
+    }
+
+    /// Sets the main song used by the level, replacing any previously set custom song
+    pub fn main_song(mut self, main_song: MainSong) -> Self {
+        self.level.main_song = Some(main_song);
+        self.level.custom_song = None;
+        self
+    }
+
+    /// Sets the ID of the newgrounds song used by the level, replacing any previously set main song
+    pub fn custom_song(mut self, song_id: u64) -> Self {
+        self.level.main_song = None;
+        self.level.custom_song = Some(song_id);
+        self
+    }
+
+    pub fn copy_of(mut self, level_id: u64) -> Self {
+        self.level.copy_of = Some(level_id);
+        self
+    }
+
+    pub fn two_player(mut self, two_player: bool) -> Self {
+        self.level.two_player = TwoPlayer::from(two_player);
+        self
+    }
+
+    pub fn stars_requested(mut self, stars: u8) -> Self {
+        self.level.stars_requested = Some(stars);
+        self
+    }
+
+    /// Sets the amount of objects in the level. If not set, this is derived from the level's
+    /// [`Objects`] if those are provided via [`LevelBuilder::objects`]
+    pub fn object_amount(mut self, object_amount: u32) -> Self {
+        self.level.object_amount = Some(object_amount);
+        self
+    }
+
+    /// Sets the level's data, replacing any previously set level string
+    pub fn objects(mut self, objects: Objects) -> Self {
+        self.objects = Some(objects);
+        self.level_string = None;
+        self
+    }
+
+    /// Sets the level's data as a decompressed level string, replacing any previously set
+    /// [`Objects`]
+    pub fn level_string(mut self, level_string: impl Into<Cow<'a, str>>) -> Self {
+        self.level_string = Some(level_string.into());
+        self.objects = None;
+        self
+    }
+
+    pub fn password(mut self, password: Password) -> Self {
+        self.password = password;
+        self
+    }
+
+    pub fn time_since_upload(mut self, age: impl Into<Cow<'a, str>>) -> Self {
+        self.time_since_upload = GdAge(age.into());
+        self
+    }
+
+    pub fn time_since_update(mut self, age: impl Into<Cow<'a, str>>) -> Self {
+        self.time_since_update = GdAge(age.into());
+        self
+    }
+
+    /// Builds the level as it would appear in a `getGJLevels` response, without any level data
+    pub fn build_listed(mut self) -> Level<'a, (), Option<u64>, u64> {
+        if self.level.object_amount.is_none() {
+            self.level.object_amount = self.objects.as_ref().map(|objects| objects.objects.len() as u32);
+        }
+
+        self.level
+    }
+
+    /// Builds the level as it would appear in a `downloadGJLevel` response.
+    ///
+    /// If no level data was provided, the level will contain no objects. A level string set via
+    /// [`LevelBuilder::level_string`] is compressed here, which fails without the `std` feature.
+    pub fn build(self) -> Result<Level<'a>, LevelProcessError> {
+        let level_data = match (self.level_string, self.objects) {
+            (Some(level_string), _) => Thunk::Unprocessed(Cow::Owned(LevelString::new(level_string.into_owned()).encode()?.into_owned())),
+            (None, Some(objects)) => Thunk::Processed(objects),
+            (None, None) => Thunk::Processed(Objects::default()),
+        };
+        let object_amount = match level_data {
+            Thunk::Processed(ref objects) => self.level.object_amount.or(Some(objects.objects.len() as u32)),
+            Thunk::Unprocessed(_) => self.level.object_amount,
+        };
+
+        Ok(Level {
+            object_amount,
+            ..self.level
+        }
+        .with_data(LevelData {
+            level_data,
+            password: Thunk::Processed(self.password),
+            time_since_upload: self.time_since_upload,
+            time_since_update: self.time_since_update,
+            index_36: Cow::Borrowed(""),
+            index_40: Cow::Borrowed("0"),
+            index_52: Cow::Borrowed(""),
+            index_53: Cow::Borrowed(""),
+            index_57: Cow::Borrowed("0"),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        model::level::{Level, Password},
+        GJFormat,
+    };
+    use alloc::vec::Vec;
+
+    #[test]
+    fn built_level_is_encoded() {
+        let level = Level::builder(1, "Test")
+            .description("Hello World")
+            .creator(71)
+            .level_string("kA4,0;1,1,2,15,3,15;")
+            .password(Password::PasswordCopy(1234))
+            .build()
+            .unwrap();
+
+        let mut bytes = Vec::new();
+        level.write_gj(&mut bytes).unwrap();
+
+        let serialized = core::str::from_utf8(&bytes).unwrap();
+
+        assert!(!serialized.contains("Hello World"));
+
+        let parsed = Level::from_gj_str(serialized).unwrap();
+
+        assert_eq!(parsed.creator, 71);
+        assert_eq!(parsed.description.unwrap().into_processed().unwrap(), "Hello World");
+        assert_eq!(
+            parsed.level_data.level_string().unwrap().into_processed().unwrap().as_str(),
+            "kA4,0;1,1,2,15,3,15;"
+        );
+        assert_eq!(parsed.level_data.password.into_processed().unwrap(), Password::PasswordCopy(1234));
+    }
+}
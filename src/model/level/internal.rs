@@ -1,13 +1,17 @@
 use crate::{
     model::{
-        level::{DemonRating, Level, LevelData, LevelLength, LevelRating},
+        level::{rating::Rating, DemonRating, Level, LevelData, LevelLength, LevelRating, RatingSum},
         song::MainSong,
     },
-    serde::InternalProxy,
-    Dash,
+    request::{compat_profile, CompatProfile},
+    serde::{
+        limits::{Limit, Limits},
+        GJFormat, IndexedDeserializer, InternalProxy, UnknownIndices,
+    },
+    Dash, DeError,
 };
+use core::borrow::Borrow;
 use serde::{de::Error, Deserialize, Serialize};
-use std::borrow::Borrow;
 
 #[derive(Serialize, Deserialize, Debug)]
 struct InternalLevel<'src> {
@@ -24,9 +28,9 @@ struct InternalLevel<'src> {
     #[serde(serialize_with = "crate::util::false_to_empty_string")]
     #[serde(rename = "25")]
     index_25: bool,
-    #[serde(serialize_with = "crate::util::true_to_ten")]
+    #[serde(with = "crate::util::default_to_none")]
     #[serde(rename = "8")]
-    index_8: bool,
+    index_8: Option<u32>,
     #[serde(rename = "9")]
     index_9: i32,
     #[serde(serialize_with = "crate::util::false_to_empty_string")]
@@ -54,18 +58,25 @@ struct InternalLevel<'src> {
     #[serde(with = "crate::util::default_to_none")]
     #[serde(rename = "35")]
     index_35: Option<u64>,
+    // Indices 37 and upwards were introduced in 2.x and are missing from the responses of servers
+    // running older versions. Whether that is acceptable depends on the compatibility profile, see
+    // `introduced_in`
+    #[serde(default, deserialize_with = "present")]
     #[serde(rename = "37")]
-    index_37: u8,
+    index_37: Option<u8>,
+    #[serde(default, deserialize_with = "present")]
     #[serde(rename = "38")]
-    index_38: bool,
-    #[serde(with = "crate::util::default_to_none")]
+    index_38: Option<bool>,
+    #[serde(default, deserialize_with = "present")]
     #[serde(rename = "39")]
     index_39: Option<u8>,
+    #[serde(default, deserialize_with = "present")]
     #[serde(rename = "42")]
-    index_42: bool,
+    index_42: Option<u8>,
+    #[serde(default, deserialize_with = "present")]
     #[serde(rename = "43")]
-    index_43: u8,
-    #[serde(with = "crate::util::default_to_none")]
+    index_43: Option<u8>,
+    #[serde(default, deserialize_with = "present")]
     #[serde(rename = "45")]
     index_45: Option<u32>,
     #[serde(rename = "46")]
@@ -103,50 +114,75 @@ struct InternalLevel<'src> {
     index_57: Option<&'src str>,
 }
 
-impl<'de> Dash<'de> for Level<'de, (), Option<u64>, u64> {
-    fn dash_deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        let internal = InternalLevel::deserialize(deserializer)?;
+/// The values of the indices introduced in 2.x, see [`InternalLevel::introduced_indices`]
+struct IntroducedIndices {
+    coin_amount: u8,
+    coins_verified: bool,
+    stars_requested: Option<u8>,
+    epic: u8,
+    object_amount: Option<u32>,
+}
 
-        Ok(Self {
-            level_id: InternalProxy::from_deserialize_proxy(internal.index_1),
-            name: InternalProxy::from_deserialize_proxy(internal.index_2),
-            description: InternalProxy::from_deserialize_proxy(internal.index_3),
-            version: InternalProxy::from_deserialize_proxy(internal.index_5),
-            creator: InternalProxy::from_deserialize_proxy(internal.index_6),
-            downloads: InternalProxy::from_deserialize_proxy(internal.index_10),
-            gd_version: InternalProxy::from_deserialize_proxy(internal.index_13),
-            likes: InternalProxy::from_deserialize_proxy(internal.index_14),
-            length: InternalProxy::from_deserialize_proxy(internal.index_15),
-            stars: InternalProxy::from_deserialize_proxy(internal.index_18),
-            featured: InternalProxy::from_deserialize_proxy(internal.index_19),
-            copy_of: InternalProxy::from_deserialize_proxy(internal.index_30),
-            two_player: InternalProxy::from_deserialize_proxy(internal.index_31),
-            custom_song: InternalProxy::from_deserialize_proxy(internal.index_35),
-            coin_amount: InternalProxy::from_deserialize_proxy(internal.index_37),
-            coins_verified: InternalProxy::from_deserialize_proxy(internal.index_38),
-            stars_requested: InternalProxy::from_deserialize_proxy(internal.index_39),
-            is_epic: InternalProxy::from_deserialize_proxy(internal.index_42),
-            object_amount: InternalProxy::from_deserialize_proxy(internal.index_45),
-            index_46: InternalProxy::from_deserialize_proxy(internal.index_46),
-            index_47: InternalProxy::from_deserialize_proxy(internal.index_47),
+impl InternalLevel<'_> {
+    /// Resolves the indices introduced in 2.x, which are allowed to be missing only if the given
+    /// [`CompatProfile`] predates the version they were introduced in. In that case, they take on
+    /// their default values.
+    fn introduced_indices<E: Error>(&self, compat: CompatProfile) -> Result<IntroducedIndices, E> {
+        // Not needed for constructing a `Level`, but still required unless the profile predates 2.1
+        introduced_in::<_, E>(self.index_43, compat, CompatProfile::Gd21, "43")?;
 
-            main_song: if internal.index_35.is_some() {
-                None
-            } else {
-                Some(MainSong::from(internal.index_12))
-            },
-            difficulty: if !internal.index_8 {
-                LevelRating::NotAvailable
-            } else if internal.index_25 {
-                LevelRating::Auto
-            } else if internal.index_17 {
-                LevelRating::Demon(DemonRating::from_response_value(internal.index_9))
-            } else {
-                LevelRating::from_response_value(internal.index_9)
-            },
-            level_data: (),
+        Ok(IntroducedIndices {
+            coin_amount: introduced_in::<_, E>(self.index_37, compat, CompatProfile::Gd20, "37")?,
+            coins_verified: introduced_in::<_, E>(self.index_38, compat, CompatProfile::Gd20, "38")?,
+            stars_requested: Some(introduced_in::<_, E>(self.index_39, compat, CompatProfile::Gd20, "39")?).filter(|&stars| stars != 0),
+            epic: introduced_in::<_, E>(self.index_42, compat, CompatProfile::Gd21, "42")?,
+            object_amount: Some(introduced_in::<_, E>(self.index_45, compat, CompatProfile::Gd21, "45")?).filter(|&amount| amount != 0),
         })
     }
+}
+
+/// Deserializes an index that may be missing, without treating empty values as missing (as
+/// `Option`'s [`Deserialize`] implementation would)
+fn present<'de, D: serde::Deserializer<'de>, T: Deserialize<'de>>(deserializer: D) -> Result<Option<T>, D::Error> {
+    T::deserialize(deserializer).map(Some)
+}
+
+/// Resolves the value of an index introduced in the given version of the game, for a server
+/// implementing the version described by `compat`
+fn introduced_in<T: Default, E: Error>(
+    value: Option<T>, compat: CompatProfile, version: CompatProfile, index: &'static str,
+) -> Result<T, E> {
+    match value {
+        Some(value) => Ok(value),
+        None if compat < version => Ok(T::default()),
+        None => Err(E::missing_field(index)),
+    }
+}
+
+/// Deserialization of levels for servers implementing a specific version of the game
+///
+/// The [`Dash`] implementations of levels defer to this, using the global [`compat_profile`].
+pub(crate) trait CompatDash<'de>: Sized {
+    fn compat_deserialize<D: serde::Deserializer<'de>>(deserializer: D, compat: CompatProfile) -> Result<Self, D::Error>;
+}
+
+/// Like [`GJFormat::from_gj_str_with_limits`], but treats the indices introduced after the version
+/// described by `compat` as optional instead of consulting the global [`compat_profile`]
+pub(crate) fn from_gj_str_with_compat<'de, L>(input: &'de str, limits: &Limits, compat: CompatProfile) -> Result<L, DeError<'de>>
+where
+    L: GJFormat<'de> + CompatDash<'de>,
+{
+    limits.check(Limit::InputLength, input.len())?;
+
+    let mut indexed_deserializer = IndexedDeserializer::new(input, L::DELIMITER, L::MAP_LIKE).with_sub_delimiter(L::SUB_DELIMITER);
+
+    L::compat_deserialize(&mut indexed_deserializer, compat)
+}
+
+impl<'de> Dash<'de> for Level<'de, (), Option<u64>, u64> {
+    fn dash_deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::compat_deserialize(deserializer, compat_profile())
+    }
 
     fn dash_serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         // We are in a manual impl, so we can do the .as_deref() trick to avoid needing two separate structs
@@ -162,32 +198,39 @@ impl<'de> Dash<'de> for Level<'de, (), Option<u64>, u64> {
             index_13: self.gd_version.to_serialize_proxy(),
             index_14: self.likes.to_serialize_proxy(),
             index_15: self.length.to_serialize_proxy(),
-            index_18: self.stars.to_serialize_proxy(),
-            index_19: self.featured.to_serialize_proxy(),
+            index_18: self.rating.stars.to_serialize_proxy(),
+            index_19: self.rating.featured.to_serialize_proxy(),
             index_30: self.copy_of.to_serialize_proxy(),
             index_31: self.two_player.to_serialize_proxy(),
             index_35: self.custom_song.to_serialize_proxy(),
-            index_37: self.coin_amount.to_serialize_proxy(),
-            index_38: self.coins_verified.to_serialize_proxy(),
-            index_39: self.stars_requested.to_serialize_proxy(),
-            index_42: self.is_epic.to_serialize_proxy(),
-            index_45: self.object_amount.to_serialize_proxy(),
+            index_37: Some(self.rating.coin_amount.to_serialize_proxy()),
+            index_38: Some(self.rating.coins_verified.to_serialize_proxy()),
+            index_39: Some(self.stars_requested.unwrap_or_default()),
+            index_42: Some(self.rating.epic.to_serialize_proxy()),
+            index_45: Some(self.object_amount.unwrap_or_default()),
             index_46: self.index_46.to_serialize_proxy(),
             index_47: self.index_47.to_serialize_proxy(),
 
             index_12: self.main_song.map(|song| song.main_song_id).unwrap_or(0),
-            index_25: self.difficulty == LevelRating::Auto,
-            index_8: self.difficulty != LevelRating::NotAvailable,
-            index_9: self.difficulty.into_response_value(),
-            index_17: self.difficulty.is_demon(),
-            index_43: match self.difficulty {
+            index_25: self.rating.level_rating == LevelRating::Auto,
+            index_8: match self.rating_sum {
+                Some(rating_sum) => Some(rating_sum.count),
+                None if self.rating.level_rating == LevelRating::NotAvailable => None,
+                None => Some(10),
+            },
+            index_9: match self.rating_sum {
+                Some(rating_sum) => rating_sum.sum,
+                None => self.rating.level_rating.into_response_value(),
+            },
+            index_17: self.rating.level_rating.is_demon(),
+            index_43: Some(match self.rating.level_rating {
                 LevelRating::Demon(DemonRating::Easy) => 3,
                 LevelRating::Demon(DemonRating::Medium) => 4,
                 LevelRating::Demon(DemonRating::Hard) => 0,
                 LevelRating::Demon(DemonRating::Insane) => 5,
                 LevelRating::Demon(DemonRating::Extreme) => 6,
                 _ => 5,
-            },
+            }),
             index_4: None,
             index_27: None,
             index_28: None,
@@ -200,26 +243,20 @@ impl<'de> Dash<'de> for Level<'de, (), Option<u64>, u64> {
         };
         internal.serialize(serializer)
     }
+
+    fn unknown_indices(&self) -> Option<&UnknownIndices<'de>> {
+        Some(&self.unknown_indices)
+    }
+
+    fn unknown_indices_mut(&mut self) -> Option<&mut UnknownIndices<'de>> {
+        Some(&mut self.unknown_indices)
+    }
 }
 
-impl<'de> Dash<'de> for Level<'de, LevelData<'de>, Option<u64>, u64> {
-    fn dash_deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+impl<'de> CompatDash<'de> for Level<'de, (), Option<u64>, u64> {
+    fn compat_deserialize<D: serde::Deserializer<'de>>(deserializer: D, compat: CompatProfile) -> Result<Self, D::Error> {
         let internal = InternalLevel::deserialize(deserializer)?;
-
-        let level_data = match (internal.index_4, internal.index_27, internal.index_28, internal.index_29) {
-            (Some(idx4), Some(idx27), Some(idx28), Some(idx29)) => LevelData {
-                level_data: InternalProxy::from_deserialize_proxy(idx4),
-                password: InternalProxy::from_deserialize_proxy(idx27),
-                time_since_upload: InternalProxy::from_deserialize_proxy(idx28),
-                time_since_update: InternalProxy::from_deserialize_proxy(idx29),
-                index_36: InternalProxy::from_deserialize_proxy(internal.index_36.unwrap_or_default()),
-                index_40: InternalProxy::from_deserialize_proxy(internal.index_40.unwrap_or_default()),
-                index_52: InternalProxy::from_deserialize_proxy(internal.index_52.unwrap_or_default()),
-                index_53: InternalProxy::from_deserialize_proxy(internal.index_53.unwrap_or_default()),
-                index_57: InternalProxy::from_deserialize_proxy(internal.index_57.unwrap_or_default()),
-            },
-            _ => return Err(D::Error::custom("Missing indices for level data!")),
-        };
+        let introduced = internal.introduced_indices::<D::Error>(compat)?;
 
         Ok(Self {
             level_id: InternalProxy::from_deserialize_proxy(internal.index_1),
@@ -231,37 +268,38 @@ impl<'de> Dash<'de> for Level<'de, LevelData<'de>, Option<u64>, u64> {
             gd_version: InternalProxy::from_deserialize_proxy(internal.index_13),
             likes: InternalProxy::from_deserialize_proxy(internal.index_14),
             length: InternalProxy::from_deserialize_proxy(internal.index_15),
-            stars: InternalProxy::from_deserialize_proxy(internal.index_18),
-            featured: InternalProxy::from_deserialize_proxy(internal.index_19),
             copy_of: InternalProxy::from_deserialize_proxy(internal.index_30),
             two_player: InternalProxy::from_deserialize_proxy(internal.index_31),
             custom_song: InternalProxy::from_deserialize_proxy(internal.index_35),
-            coin_amount: InternalProxy::from_deserialize_proxy(internal.index_37),
-            coins_verified: InternalProxy::from_deserialize_proxy(internal.index_38),
-            stars_requested: InternalProxy::from_deserialize_proxy(internal.index_39),
-            is_epic: InternalProxy::from_deserialize_proxy(internal.index_42),
-            object_amount: InternalProxy::from_deserialize_proxy(internal.index_45),
+            stars_requested: introduced.stars_requested,
+            object_amount: introduced.object_amount,
             index_46: InternalProxy::from_deserialize_proxy(internal.index_46),
             index_47: InternalProxy::from_deserialize_proxy(internal.index_47),
+            unknown_indices: UnknownIndices::new(),
 
             main_song: if internal.index_35.is_some() {
                 None
             } else {
                 Some(MainSong::from(internal.index_12))
             },
-            difficulty: if !internal.index_8 {
-                LevelRating::NotAvailable
-            } else if internal.index_25 {
-                LevelRating::Auto
-            } else if internal.index_17 {
-                LevelRating::Demon(DemonRating::from_response_value(internal.index_9))
-            } else {
-                LevelRating::from_response_value(internal.index_9)
+            rating: Rating {
+                level_rating: LevelRating::from_internal(internal.index_8, internal.index_9, internal.index_17, internal.index_25),
+                stars: InternalProxy::from_deserialize_proxy(internal.index_18),
+                featured: InternalProxy::from_deserialize_proxy(internal.index_19),
+                epic: introduced.epic,
+                coin_amount: introduced.coin_amount,
+                coins_verified: introduced.coins_verified,
             },
-
-            level_data,
+            rating_sum: RatingSum::from_internal(internal.index_8, internal.index_9),
+            level_data: (),
         })
     }
+}
+
+impl<'de> Dash<'de> for Level<'de, LevelData<'de>, Option<u64>, u64> {
+    fn dash_deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::compat_deserialize(deserializer, compat_profile())
+    }
 
     fn dash_serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         // We are in a manual impl, so we can do the .as_deref() trick to avoid needing two separate structs
@@ -279,32 +317,39 @@ impl<'de> Dash<'de> for Level<'de, LevelData<'de>, Option<u64>, u64> {
             index_13: self.gd_version.to_serialize_proxy(),
             index_14: self.likes.to_serialize_proxy(),
             index_15: self.length.to_serialize_proxy(),
-            index_18: self.stars.to_serialize_proxy(),
-            index_19: self.featured.to_serialize_proxy(),
+            index_18: self.rating.stars.to_serialize_proxy(),
+            index_19: self.rating.featured.to_serialize_proxy(),
             index_30: self.copy_of.to_serialize_proxy(),
             index_31: self.two_player.to_serialize_proxy(),
             index_35: self.custom_song.to_serialize_proxy(),
-            index_37: self.coin_amount.to_serialize_proxy(),
-            index_38: self.coins_verified.to_serialize_proxy(),
-            index_39: self.stars_requested.to_serialize_proxy(),
-            index_42: self.is_epic.to_serialize_proxy(),
-            index_45: self.object_amount.to_serialize_proxy(),
+            index_37: Some(self.rating.coin_amount.to_serialize_proxy()),
+            index_38: Some(self.rating.coins_verified.to_serialize_proxy()),
+            index_39: Some(self.stars_requested.unwrap_or_default()),
+            index_42: Some(self.rating.epic.to_serialize_proxy()),
+            index_45: Some(self.object_amount.unwrap_or_default()),
             index_46: self.index_46.to_serialize_proxy(),
             index_47: self.index_47.to_serialize_proxy(),
 
             index_12: self.main_song.map(|song| song.main_song_id).unwrap_or(0),
-            index_25: self.difficulty == LevelRating::Auto,
-            index_8: self.difficulty != LevelRating::NotAvailable,
-            index_9: self.difficulty.into_response_value(),
-            index_17: self.difficulty.is_demon(),
-            index_43: match self.difficulty {
+            index_25: self.rating.level_rating == LevelRating::Auto,
+            index_8: match self.rating_sum {
+                Some(rating_sum) => Some(rating_sum.count),
+                None if self.rating.level_rating == LevelRating::NotAvailable => None,
+                None => Some(10),
+            },
+            index_9: match self.rating_sum {
+                Some(rating_sum) => rating_sum.sum,
+                None => self.rating.level_rating.into_response_value(),
+            },
+            index_17: self.rating.level_rating.is_demon(),
+            index_43: Some(match self.rating.level_rating {
                 LevelRating::Demon(DemonRating::Easy) => 3,
                 LevelRating::Demon(DemonRating::Medium) => 4,
                 LevelRating::Demon(DemonRating::Hard) => 0,
                 LevelRating::Demon(DemonRating::Insane) => 5,
                 LevelRating::Demon(DemonRating::Extreme) => 6,
                 _ => 5,
-            },
+            }),
 
             index_4: Some(index_4.borrow()),
             index_27: Some(index_27.borrow()),
@@ -318,6 +363,73 @@ impl<'de> Dash<'de> for Level<'de, LevelData<'de>, Option<u64>, u64> {
         };
         internal.serialize(serializer)
     }
+
+    fn unknown_indices(&self) -> Option<&UnknownIndices<'de>> {
+        Some(&self.unknown_indices)
+    }
+
+    fn unknown_indices_mut(&mut self) -> Option<&mut UnknownIndices<'de>> {
+        Some(&mut self.unknown_indices)
+    }
+}
+
+impl<'de> CompatDash<'de> for Level<'de, LevelData<'de>, Option<u64>, u64> {
+    fn compat_deserialize<D: serde::Deserializer<'de>>(deserializer: D, compat: CompatProfile) -> Result<Self, D::Error> {
+        let internal = InternalLevel::deserialize(deserializer)?;
+        let introduced = internal.introduced_indices::<D::Error>(compat)?;
+
+        let level_data = match (internal.index_4, internal.index_27, internal.index_28, internal.index_29) {
+            (Some(idx4), Some(idx27), Some(idx28), Some(idx29)) => LevelData {
+                level_data: InternalProxy::from_deserialize_proxy(idx4),
+                password: InternalProxy::from_deserialize_proxy(idx27),
+                time_since_upload: InternalProxy::from_deserialize_proxy(idx28),
+                time_since_update: InternalProxy::from_deserialize_proxy(idx29),
+                index_36: InternalProxy::from_deserialize_proxy(internal.index_36.unwrap_or_default()),
+                index_40: InternalProxy::from_deserialize_proxy(internal.index_40.unwrap_or_default()),
+                index_52: InternalProxy::from_deserialize_proxy(internal.index_52.unwrap_or_default()),
+                index_53: InternalProxy::from_deserialize_proxy(internal.index_53.unwrap_or_default()),
+                index_57: InternalProxy::from_deserialize_proxy(internal.index_57.unwrap_or_default()),
+            },
+            _ => return Err(D::Error::custom("Missing indices for level data!")),
+        };
+
+        Ok(Self {
+            level_id: InternalProxy::from_deserialize_proxy(internal.index_1),
+            name: InternalProxy::from_deserialize_proxy(internal.index_2),
+            description: InternalProxy::from_deserialize_proxy(internal.index_3),
+            version: InternalProxy::from_deserialize_proxy(internal.index_5),
+            creator: InternalProxy::from_deserialize_proxy(internal.index_6),
+            downloads: InternalProxy::from_deserialize_proxy(internal.index_10),
+            gd_version: InternalProxy::from_deserialize_proxy(internal.index_13),
+            likes: InternalProxy::from_deserialize_proxy(internal.index_14),
+            length: InternalProxy::from_deserialize_proxy(internal.index_15),
+            copy_of: InternalProxy::from_deserialize_proxy(internal.index_30),
+            two_player: InternalProxy::from_deserialize_proxy(internal.index_31),
+            custom_song: InternalProxy::from_deserialize_proxy(internal.index_35),
+            stars_requested: introduced.stars_requested,
+            object_amount: introduced.object_amount,
+            index_46: InternalProxy::from_deserialize_proxy(internal.index_46),
+            index_47: InternalProxy::from_deserialize_proxy(internal.index_47),
+            unknown_indices: UnknownIndices::new(),
+
+            main_song: if internal.index_35.is_some() {
+                None
+            } else {
+                Some(MainSong::from(internal.index_12))
+            },
+            rating: Rating {
+                level_rating: LevelRating::from_internal(internal.index_8, internal.index_9, internal.index_17, internal.index_25),
+                stars: InternalProxy::from_deserialize_proxy(internal.index_18),
+                featured: InternalProxy::from_deserialize_proxy(internal.index_19),
+                epic: introduced.epic,
+                coin_amount: introduced.coin_amount,
+                coins_verified: introduced.coins_verified,
+            },
+            rating_sum: RatingSum::from_internal(internal.index_8, internal.index_9),
+
+            level_data,
+        })
+    }
 }
 
 impl InternalProxy for LevelLength {
@@ -352,7 +464,40 @@ impl InternalProxy for LevelLength {
     }
 }
 
+impl RatingSum {
+    fn from_internal(count: Option<u32>, sum: i32) -> Option<RatingSum> {
+        match count {
+            None | Some(0) | Some(10) => None,
+            Some(count) => Some(RatingSum { sum, count }),
+        }
+    }
+}
+
 impl LevelRating {
+    fn from_internal(count: Option<u32>, sum: i32, is_demon: bool, is_auto: bool) -> LevelRating {
+        let value = match count {
+            None | Some(0) => return LevelRating::NotAvailable,
+            Some(10) => sum,
+            // Raw vote sums are mapped to the face closest to the average vote. Integer version of
+            // `(sum / count).round()`, as float rounding is unavailable without std
+            Some(count) => {
+                let (sum, count) = (i64::from(sum), i64::from(count));
+                let average = (2 * sum + sum.signum() * count) / (2 * count);
+
+                // Demons have no "not available" face
+                average.clamp(i64::from(is_demon), 5) as i32 * 10
+            },
+        };
+
+        if is_auto {
+            LevelRating::Auto
+        } else if is_demon {
+            LevelRating::Demon(DemonRating::from_response_value(value))
+        } else {
+            LevelRating::from_response_value(value)
+        }
+    }
+
     fn from_response_value(value: i32) -> LevelRating {
         match value {
             0 => LevelRating::NotAvailable,
@@ -403,3 +548,67 @@ impl DemonRating {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::model::level::{DemonRating, LevelRating, RatingSum};
+
+    #[test]
+    fn rating_sum_from_internal() {
+        let cases = [
+            (None, 0, None),
+            (Some(10), 30, None),
+            (Some(1), 5, Some(RatingSum { sum: 5, count: 1 })),
+            (Some(9), 27, Some(RatingSum { sum: 27, count: 9 })),
+            (Some(11), 33, Some(RatingSum { sum: 33, count: 11 })),
+        ];
+
+        for (count, sum, expected) in cases {
+            assert_eq!(RatingSum::from_internal(count, sum), expected, "count {:?}, sum {}", count, sum);
+        }
+    }
+
+    #[test]
+    fn level_rating_from_internal() {
+        let cases = [
+            // Normalized values, as sent by the official servers
+            (None, 0, false, false, LevelRating::NotAvailable),
+            (Some(10), 0, false, false, LevelRating::NotAvailable),
+            (Some(10), 10, false, false, LevelRating::Easy),
+            (Some(10), 50, false, false, LevelRating::Insane),
+            (Some(10), 60, false, false, LevelRating::Unknown(60)),
+            (Some(10), 30, true, false, LevelRating::Demon(DemonRating::Hard)),
+            (Some(10), 0, false, true, LevelRating::Auto),
+            // Raw vote sums, which are mapped to the face closest to the average vote
+            (Some(0), 0, false, false, LevelRating::NotAvailable),
+            (Some(1), 1, false, false, LevelRating::Easy),
+            (Some(2), 2, false, false, LevelRating::Easy),
+            (Some(4), 20, false, false, LevelRating::Insane),
+            (Some(1), 5, true, false, LevelRating::Demon(DemonRating::Extreme)),
+            (Some(3), 5, false, false, LevelRating::Normal),
+            (Some(4), 3, false, false, LevelRating::Easy),
+            (Some(3), 1, false, false, LevelRating::NotAvailable),
+            (Some(5), 2, true, false, LevelRating::Demon(DemonRating::Easy)),
+            (Some(3), 5, false, true, LevelRating::Auto),
+            // Halves are rounded away from zero
+            (Some(2), 3, false, false, LevelRating::Normal),
+            (Some(2), 9, false, false, LevelRating::Insane),
+            // Out of range sums are clamped to the lowest and highest faces
+            (Some(1), 9, false, false, LevelRating::Insane),
+            (Some(4), -3, false, false, LevelRating::NotAvailable),
+            (Some(4), -3, true, false, LevelRating::Demon(DemonRating::Easy)),
+        ];
+
+        for (count, sum, is_demon, is_auto, expected) in cases {
+            assert_eq!(
+                LevelRating::from_internal(count, sum, is_demon, is_auto),
+                expected,
+                "count {:?}, sum {}, demon {}, auto {}",
+                count,
+                sum,
+                is_demon,
+                is_auto
+            );
+        }
+    }
+}
@@ -0,0 +1,292 @@
+//! Module containing utilities for computing and applying compact patches between two versions of
+//! the same level's objects
+//!
+//! Collaborative editing and backup tools repeatedly store versions of the same level, whose level
+//! strings can be several megabytes large, even though usually only a handful of objects changes in
+//! between. Instead of storing every version in full, such tools can store the [`LevelDataPatch`]
+//! computed by [`diff`] and reconstruct the newer version via [`apply`].
+//!
+//! Objects in Geometry Dash have no identity, so the objects of two versions are matched up via
+//! their [`ObjectKey`], a stable hash of all properties modelled by [`LevelObject`]. Objects that
+//! only differ in their position are reported as moved.
+//!
+//! Note that patches are lossy: [`LevelObject`] does not (yet) model all of an object's
+//! properties, for example its groups, colors, scale or trigger settings. Objects differing only in
+//! such properties are considered identical, and changes to them are not contained in a patch.
+//! Patches thus describe changes to the modelled properties only, and are not a replacement for
+//! storing the level string itself.
+
+use crate::model::level::object::{LevelObject, ObjectData};
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A stable hash of all properties of a [`LevelObject`]
+///
+/// The hash is computed via 64-bit FNV-1a and thus identical across platforms and program runs,
+/// meaning it can be persisted as part of a [`LevelDataPatch`]. Two objects with equal keys are
+/// interchangeable as far as the properties modelled by [`LevelObject`] are concerned, see the
+/// [module level documentation](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ObjectKey(pub u64);
+
+impl ObjectKey {
+    /// Computes the key of the given object
+    pub fn of(object: &LevelObject) -> ObjectKey {
+        let mut hasher = identity_hasher(object);
+
+        hasher.write(&object.x.to_bits().to_le_bytes());
+        hasher.write(&object.y.to_bits().to_le_bytes());
+
+        ObjectKey(hasher.finish())
+    }
+}
+
+/// An object whose position changed, see [`LevelDataPatch::moved`]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ObjectMove {
+    /// The key of the object at its old position
+    pub key: ObjectKey,
+
+    /// The new x position of the object
+    pub x: f32,
+
+    /// The new y position of the object
+    pub y: f32,
+}
+
+/// The changes between two versions of the same level's objects, see [`diff`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LevelDataPatch {
+    /// The keys of objects only contained in the older version. If the older version contains
+    /// multiple identical objects, the key occurs once per removed copy.
+    pub removed: Vec<ObjectKey>,
+
+    /// Objects contained in both versions, but at different positions
+    pub moved: Vec<ObjectMove>,
+
+    /// Objects only contained in the newer version
+    pub added: Vec<LevelObject>,
+}
+
+impl LevelDataPatch {
+    /// Whether the objects did not change
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty() && self.moved.is_empty() && self.added.is_empty()
+    }
+}
+
+/// Error returned by [`apply`] if a patch does not fit the objects it is applied to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Error)]
+pub enum PatchError {
+    /// The patch removes or moves an object that does not exist (or was already removed or moved
+    /// by a previous entry of the patch)
+    #[error("patch references object {0:?}, which does not exist")]
+    ObjectNotFound(ObjectKey),
+}
+
+/// Computes the patch turning the `old` objects into the `new` ones
+///
+/// Objects contained in both versions are matched up by their [`ObjectKey`]. Of the remaining
+/// objects, those that only differ in their position are paired up in order and reported as moved.
+/// Everything else is reported as removed or added respectively. The order of the entries in the
+/// returned patch follows the order of the objects in the respective version.
+pub fn diff(old: &[LevelObject], new: &[LevelObject]) -> LevelDataPatch {
+    let mut matched = vec![false; old.len()];
+
+    // Indices are inserted in reverse, so that popping yields the objects in order
+    let mut by_key = BTreeMap::<ObjectKey, Vec<usize>>::new();
+
+    for (idx, object) in old.iter().enumerate().rev() {
+        by_key.entry(ObjectKey::of(object)).or_default().push(idx);
+    }
+
+    let mut unmatched = Vec::new();
+
+    for object in new {
+        match by_key.get_mut(&ObjectKey::of(object)).and_then(Vec::pop) {
+            Some(idx) => matched[idx] = true,
+            None => unmatched.push(object),
+        }
+    }
+
+    let mut by_identity = BTreeMap::<u64, Vec<usize>>::new();
+
+    for (idx, object) in old.iter().enumerate().rev().filter(|&(idx, _)| !matched[idx]) {
+        by_identity.entry(identity_hasher(object).finish()).or_default().push(idx);
+    }
+
+    let mut patch = LevelDataPatch::default();
+
+    for object in unmatched {
+        match by_identity.get_mut(&identity_hasher(object).finish()).and_then(Vec::pop) {
+            Some(idx) => {
+                matched[idx] = true;
+                patch.moved.push(ObjectMove {
+                    key: ObjectKey::of(&old[idx]),
+                    x: object.x,
+                    y: object.y,
+                })
+            },
+            None => patch.added.push(*object),
+        }
+    }
+
+    patch.removed = old
+        .iter()
+        .zip(matched)
+        .filter(|&(_, matched)| !matched)
+        .map(|(object, _)| ObjectKey::of(object))
+        .collect();
+
+    patch
+}
+
+/// Applies the given patch to the given objects
+///
+/// Applying the result of [`diff(old, new)`](diff) to `old` yields the same objects as `new`, but
+/// not necessarily in the same order: Unchanged and moved objects retain their relative order,
+/// while added objects are appended at the end.
+///
+/// Since [`LevelObject`]s only contain the properties dash-rs models, the result lacks all other
+/// properties of the objects in the level string `new` was parsed from.
+///
+/// If the patch does not fit the objects, an error is returned and the objects are left untouched.
+pub fn apply(objects: &mut Vec<LevelObject>, patch: &LevelDataPatch) -> Result<(), PatchError> {
+    let mut by_key = BTreeMap::<ObjectKey, Vec<usize>>::new();
+
+    for (idx, object) in objects.iter().enumerate().rev() {
+        by_key.entry(ObjectKey::of(object)).or_default().push(idx);
+    }
+
+    let mut take = |key: ObjectKey| by_key.get_mut(&key).and_then(Vec::pop).ok_or(PatchError::ObjectNotFound(key));
+
+    let mut removed = vec![false; objects.len()];
+
+    for &key in &patch.removed {
+        removed[take(key)?] = true;
+    }
+
+    let moves = patch
+        .moved
+        .iter()
+        .map(|movement| Ok((take(movement.key)?, movement)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (idx, movement) in moves {
+        objects[idx].x = movement.x;
+        objects[idx].y = movement.y;
+    }
+
+    let mut removed = removed.into_iter();
+
+    objects.retain(|_| !removed.next().unwrap_or_default());
+    objects.extend_from_slice(&patch.added);
+
+    Ok(())
+}
+
+/// Returns a hasher that has consumed all properties of the given object except its position
+fn identity_hasher(object: &LevelObject) -> Fnv {
+    let mut hasher = Fnv::new();
+
+    hasher.write(&object.id.to_le_bytes());
+    hasher.write(&[object.flipped_x as u8, object.flipped_y as u8]);
+    hasher.write(&object.rotation.to_bits().to_le_bytes());
+    hasher.write(&object.editor_layer.to_le_bytes());
+    hasher.write(&object.editor_layer_2.to_le_bytes());
+    hasher.write(&i32::from(object.z_layer).to_le_bytes());
+    hasher.write(&object.z_order.to_le_bytes());
+
+    match object.metadata {
+        ObjectData::None => hasher.write(&[0]),
+        ObjectData::Unknown => hasher.write(&[1]),
+        ObjectData::SpeedPortal { checked, speed } => hasher.write(&[2, checked as u8, u8::from(speed)]),
+    }
+
+    hasher
+}
+
+/// 64-bit FNV-1a, used instead of [`core::hash::Hasher`] implementations whose output is not
+/// guaranteed to be stable
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Fnv {
+        Fnv(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply, diff, ObjectKey, PatchError};
+    use crate::model::level::object::{speed::Speed, LevelObject, ObjectData};
+    use alloc::vec::Vec;
+
+    fn sorted(objects: &[LevelObject]) -> Vec<ObjectKey> {
+        let mut keys = objects.iter().map(ObjectKey::of).collect::<Vec<_>>();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn diff_and_apply() {
+        let portal = LevelObject {
+            metadata: ObjectData::SpeedPortal {
+                checked: true,
+                speed: Speed::Fast,
+            },
+            ..LevelObject::new(201, 100.0, 15.0)
+        };
+        let old = vec![
+            LevelObject::new(1, 15.0, 15.0),
+            LevelObject::new(1, 15.0, 15.0),
+            LevelObject::new(8, 45.0, 15.0),
+            LevelObject::new(8, 75.0, 15.0),
+            portal,
+        ];
+        let new = vec![
+            LevelObject::new(1, 15.0, 15.0),
+            LevelObject::new(8, 45.0, 15.0),
+            LevelObject::new(8, 105.0, 45.0),
+            LevelObject { x: 130.0, ..portal },
+            LevelObject::new(3, 300.0, 15.0),
+        ];
+
+        let patch = diff(&old, &new);
+
+        assert_eq!(patch.removed, vec![ObjectKey::of(&old[1])]);
+        assert_eq!(patch.moved.len(), 2);
+        assert_eq!(patch.moved[0].key, ObjectKey::of(&old[3]));
+        assert_eq!(patch.moved[1].key, ObjectKey::of(&portal));
+        assert_eq!(patch.added, vec![LevelObject::new(3, 300.0, 15.0)]);
+
+        let mut objects = old.clone();
+
+        apply(&mut objects, &patch).unwrap();
+
+        assert_eq!(sorted(&objects), sorted(&new));
+        assert!(diff(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn apply_mismatched_patch() {
+        let old = vec![LevelObject::new(1, 15.0, 15.0)];
+        let patch = diff(&old, &[]);
+        let mut objects = vec![LevelObject::new(1, 45.0, 15.0)];
+
+        assert_eq!(apply(&mut objects, &patch), Err(PatchError::ObjectNotFound(ObjectKey::of(&old[0]))));
+        assert_eq!(objects, vec![LevelObject::new(1, 45.0, 15.0)]);
+    }
+}
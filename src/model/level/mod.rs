@@ -1,40 +1,54 @@
 //! Module containing structs modelling Geometry Dash levels as they are returned from the boomlings
 //! servers
 
-use itoa::Buffer;
-use std::{
+use alloc::{
     borrow::Cow,
-    fmt::{Display, Formatter},
-    io::Read,
+    string::{String, ToString},
+    vec::Vec,
 };
+use core::fmt::{Display, Formatter};
+use itoa::Buffer;
+#[cfg(feature = "std")]
+use std::io::Read;
 use thiserror::Error;
 use variant_partial_eq::VariantPartialEq;
 
 use base64::{engine::general_purpose::URL_SAFE, Engine};
-use flate2::read::{GzDecoder, GzEncoder, ZlibDecoder};
+#[cfg(feature = "std")]
+use flate2::read::{GzDecoder, GzEncoder, ZlibDecoder, ZlibEncoder};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{
     model::{
+        age::GdAge,
         creator::Creator,
         level::{
             metadata::LevelMetadata,
-            object::{speed::Speed, LevelObject, ObjectData},
+            object::{ids, LevelObject},
+            rating::Rating,
         },
         song::{MainSong, NewgroundsSong},
-        GameVersion,
+        GameVersion, IntoOwned,
+    },
+    serde::{
+        limits::{Limit, LimitExceeded, Limits},
+        Base64Decoder, IntoOwnedThunkProcessor, ProcessError, Thunk, ThunkProcessor, UnknownIndices, ROBTOP_BASE64_CONFIG,
+        ROBTOP_STANDARD_BASE64_CONFIG,
     },
-    serde::{Base64Decoder, ProcessError, Thunk, ThunkProcessor},
     util, Dash, GJFormat, SerError,
 };
+#[cfg(feature = "std")]
 use flate2::Compression;
 
-// use flate2::read::GzDecoder;
-// use std::io::Read;
-
+pub mod analysis;
+pub mod builder;
 mod internal;
+pub mod level_data;
 pub mod metadata;
 pub mod object;
+pub mod rating;
+
+pub(crate) use internal::from_gj_str_with_compat;
 
 /// Enum representing the possible level lengths known to dash-rs
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
@@ -159,6 +173,26 @@ impl LevelRating {
     }
 }
 
+/// The sum and count of the community difficulty votes on a level
+///
+/// Each vote is a difficulty face from `1` (easy) to `5` (insane/demon). Some private servers
+/// report these raw values instead of the normalized values used by the official servers.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct RatingSum {
+    /// The sum of all votes
+    pub sum: i32,
+
+    /// The number of votes. Never `0` or `10`.
+    pub count: u32,
+}
+
+impl RatingSum {
+    /// The average difficulty face voted on, from `1.0` (easy) to `5.0` (insane/demon)
+    pub fn average(&self) -> f32 {
+        self.sum as f32 / self.count as f32
+    }
+}
+
 /// Enum representing the possible demon difficulties
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum DemonRating {
@@ -250,8 +284,89 @@ impl From<Featured> for i32 {
     }
 }
 
+impl Featured {
+    /// Returns true iff this [`Featured`] is the [`Featured::Featured`] variant
+    pub fn is_featured(&self) -> bool {
+        matches!(self, Featured::Featured(_))
+    }
+
+    /// The featured weight of the level, or [`None`] if the level is not currently featured
+    ///
+    /// Sorting levels by descending featured weight yields the order in which they appear in the
+    /// in-game featured tab.
+    pub fn weight(&self) -> Option<u32> {
+        match self {
+            Featured::Featured(weight) => Some(*weight),
+            _ => None,
+        }
+    }
+}
+
 crate::into_conversion!(Featured, i32);
 
+/// Struct describing whether a level is a two-player level
+///
+/// The two-player flag reported by the servers is taken from the level's metadata at upload time and
+/// frequently does not match what the level actually does. Once the level's data is available,
+/// [`TwoPlayer::cross_check`] can be used to determine whether the level really uses two-player
+/// controls and dual mode.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct TwoPlayer {
+    /// Whether the servers report this level as a two-player level
+    pub declared: bool,
+
+    /// How the level actually uses two-player mode, or [`None`] if the level's data has not been
+    /// cross-checked yet
+    pub actual: Option<TwoPlayerUsage>,
+}
+
+/// How a level actually uses two-player mode, as determined from its data
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct TwoPlayerUsage {
+    /// Whether the level's settings enable two-player controls
+    pub two_player_controls: bool,
+
+    /// Whether the level ever puts the player into dual mode, either by starting in dual mode or
+    /// through a dual portal
+    pub uses_dual_mode: bool,
+}
+
+impl TwoPlayer {
+    /// Determines how the given level data actually uses two-player mode
+    pub fn cross_check(&mut self, objects: &Objects) {
+        self.actual = Some(TwoPlayerUsage {
+            two_player_controls: objects.meta.two_player_controls,
+            uses_dual_mode: objects.meta.dual_start || objects.objects.iter().any(|object| object.id == ids::DUAL_PORTAL),
+        });
+    }
+
+    /// Whether the reported two-player flag contradicts the level's data.
+    ///
+    /// This is the case if the flag differs from the level's two-player controls setting, or if
+    /// the level is flagged as two-player but never enters dual mode. Returns `false` if the level
+    /// has not been cross-checked.
+    pub fn is_mismatched(&self) -> bool {
+        match self.actual {
+            Some(usage) => usage.two_player_controls != self.declared || (self.declared && !usage.uses_dual_mode),
+            None => false,
+        }
+    }
+}
+
+impl From<bool> for TwoPlayer {
+    fn from(declared: bool) -> Self {
+        TwoPlayer { declared, actual: None }
+    }
+}
+
+impl From<TwoPlayer> for bool {
+    fn from(two_player: TwoPlayer) -> Self {
+        two_player.declared
+    }
+}
+
+crate::into_conversion!(TwoPlayer, bool);
+
 /// Enum representing a level's copyability status
 // FIXME: Find a sane implementation for (de)serialize here
 #[derive(Debug, Clone, Eq, PartialEq, Copy)]
@@ -296,10 +411,12 @@ impl Serialize for Password {
     where
         S: Serializer,
     {
+        // Always serialize as an `Option<i32>` so that non-self-describing formats can deserialize
+        // this again
         match self {
             Password::NoCopy => serializer.serialize_none(),
-            Password::FreeCopy => serializer.serialize_i32(-1),
-            Password::PasswordCopy(password) => serializer.serialize_u32(*password),
+            Password::FreeCopy => serializer.serialize_some(&-1i32),
+            Password::PasswordCopy(password) => serializer.serialize_some(&(*password as i32)),
         }
     }
 }
@@ -319,8 +436,7 @@ impl<'de> Deserialize<'de> for Password {
     }
 }
 
-/// The XOR key the game uses to encode level passwords
-pub const LEVEL_PASSWORD_XOR_KEY: &str = "26364";
+pub use crate::crypto::LEVEL_PASSWORD_XOR_KEY;
 
 /// Encodes the given numerical password by padding it with zeros and applies the XOR-encoding with
 /// [`LEVEL_PASSWORD_XOR_KEY`]
@@ -403,8 +519,14 @@ impl ThunkProcessor for Password {
     }
 }
 
+impl IntoOwnedThunkProcessor for Password {
+    fn output_into_owned(output: Self::Output<'_>) -> Self::Output<'static> {
+        output
+    }
+}
+
 impl Display for Password {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
             Password::NoCopy => write!(f, "No Copy"),
             Password::FreeCopy => write!(f, "Free Copy"),
@@ -415,6 +537,17 @@ impl Display for Password {
 
 pub type ListedLevel<'a> = Level<'a, (), Option<NewgroundsSong<'a>>, Option<Creator<'a>>>;
 
+/// A [`Level`] owning all its data, e.g. for storing it beyond the lifetime of the response it was
+/// parsed from
+pub type OwnedLevel = Level<'static>;
+
+/// A [`ListedLevel`] owning all its data
+pub type OwnedListedLevel = ListedLevel<'static>;
+
+/// A [`ListedLevel`] whose level data might have been included in the listing, see
+/// [`Quirks::inline_level_data`](crate::response::Quirks::inline_level_data)
+pub type ListedLevelWithData<'a> = Level<'a, Option<LevelData<'a>>, Option<NewgroundsSong<'a>>, Option<Creator<'a>>>;
+
 /// Struct representing levels as returned by the boomlings API.
 ///
 /// These can be retrieved using [`LevelRequest`](crate::request::level::LevelRequest)s or
@@ -429,14 +562,14 @@ pub type ListedLevel<'a> = Level<'a, (), Option<NewgroundsSong<'a>>, Option<Crea
 /// ### Unmapped values:
 /// + Index `8`: Index 8 is a boolean value indicating whether the level has a
 ///   difficulty rating that isn't N/A. This is equivalent to checking if
-///   [`Level::difficulty`] is unequal to
+///   [`Rating::level_rating`] is unequal to
 ///   [`LevelRating::NotAvailable`]
 /// + Index `17`: Index 17 is a boolean value indicating whether
 ///   the level is a demon level. This is equivalent to checking if
-///   [`Level::difficulty`] is the [`LevelRating::Demon`] variant.
+///   [`Rating::level_rating`] is the [`LevelRating::Demon`] variant.
 /// + Index `25`: Index 25 is a boolean value indicating
 ///   whether the level is an auto level. This is equivalent to checking if
-///   [`Level::difficulty`] is equal to
+///   [`Rating::level_rating`] is equal to
 ///   [`LevelRating::Auto`]
 /// + Index `43`: This index is an indicator of demon difficulty as follows:
 ///   3 = easy demon,
@@ -491,17 +624,23 @@ pub struct Level<'a, Data = LevelData<'a>, Song = Option<u64>, User = u64> {
     /// This value is provided at index `6`.
     pub creator: User,
 
-    /// The difficulty of this [`Level`]
+    /// How this [`Level`] was rated: its difficulty, stars, featured and epic state and coins.
+    /// See [`Rating`]
     ///
     /// ## GD Internals:
-    /// This value is a construct from the value at the indices `9`, `17` and
-    /// `25`, whereas index 9 is an integer representation of either the
-    /// [`LevelRating`] or the [`DemonRating`]
-    /// struct, depending on the value of index 17.
+    /// This value is a construct from the values at the indices `9`, `17`, `18`, `19`, `25`,
+    /// `37`, `38` and `42`
+    pub rating: Rating,
+
+    /// The sum and count of community difficulty votes, if the server reports them. See
+    /// [`RatingSum`].
     ///
-    /// If index 25 is set to true, the level is an auto level and the value at
-    /// index 9 is some nonsense, in which case it is ignored.
-    pub difficulty: LevelRating,
+    /// ## GD Internals:
+    /// This value is a construct from the values at the indices `8` (count) and `9` (sum). The
+    /// official servers always report a count of `10` and a sum of ten times the face value of the
+    /// level's [`LevelRating`], in which case this field is [`None`]. Otherwise, the level's
+    /// [`LevelRating`] is the face closest to the average vote, clamped to the valid faces.
+    pub rating_sum: Option<RatingSum>,
 
     /// The amount of downloads
     ///
@@ -538,19 +677,6 @@ pub struct Level<'a, Data = LevelData<'a>, Song = Option<u64>, User = u64> {
     /// [`LevelLength`] struct at index `15`
     pub length: LevelLength,
 
-    /// The amount of stars completion of this [`Level`] awards. In the case of a platformer level,
-    /// this is instead the number of "moons" awarded.
-    ///
-    /// ## GD Internals:
-    /// This value is provided at index `18`
-    pub stars: u8,
-
-    /// This [`Level`]s featured state
-    ///
-    /// ## GD Internals:
-    /// This value is provided at index `19`
-    pub featured: Featured,
-
     /// The ID of the level this [`Level`] is a copy of, or [`None`], if
     /// this [`Level`] isn't a copy.
     ///
@@ -558,11 +684,11 @@ pub struct Level<'a, Data = LevelData<'a>, Song = Option<u64>, User = u64> {
     /// This value is provided at index `30`
     pub copy_of: Option<u64>,
 
-    /// Value indicating whether this level is played in two-player mode
+    /// Value indicating whether this level is played in two-player mode. See [`TwoPlayer`]
     ///
     /// ## GD Internals:
     /// This value is provided at index `31` and actually sanely encoded
-    pub two_player: bool,
+    pub two_player: TwoPlayer,
 
     /// The id of the newgrounds song this [`Level`] uses, or [`None`]
     /// if it useds a main song.
@@ -572,19 +698,6 @@ pub struct Level<'a, Data = LevelData<'a>, Song = Option<u64>, User = u64> {
     /// custom song is used.
     pub custom_song: Song,
 
-    /// The amount of coins in this [`Level`]
-    ///
-    /// ## GD Internals:
-    /// This value is provided at index `37`
-    pub coin_amount: u8,
-
-    /// Value indicating whether the user coins (if present) in this
-    /// [`Level`] are verified
-    ///
-    /// ## GD Internals:
-    /// This value is provided at index `38`, as an integer
-    pub coins_verified: bool,
-
     /// The amount of stars the level creator has requested when uploading this
     /// [`Level`], or [`None`] if no stars were requested.
     ///
@@ -593,12 +706,6 @@ pub struct Level<'a, Data = LevelData<'a>, Song = Option<u64>, User = u64> {
     /// were requested
     pub stars_requested: Option<u8>,
 
-    /// Value indicating whether this [`Level`] is epic
-    ///
-    /// ## GD Internals:
-    /// This value is provided at index `42`, as an integer
-    pub is_epic: bool,
-
     /// The amount of objects in this [`Level`]. Note that a value of `None` _does not_ mean
     /// that there are no objects in the level, but rather that the server's didn't provide an
     /// object count.
@@ -622,6 +729,11 @@ pub struct Level<'a, Data = LevelData<'a>, Song = Option<u64>, User = u64> {
     /// This value is provided at index `47` and seems to be an integer
     pub index_47: Option<Cow<'a, str>>,
 
+    /// Indices not known to dash-rs. Only populated if
+    /// parsed via [`GJFormat::from_gj_str_preserving`](crate::GJFormat::from_gj_str_preserving)
+    #[serde(default, skip_serializing_if = "UnknownIndices::is_empty")]
+    pub unknown_indices: UnknownIndices<'a>,
+
     /// Additional data about this level that can be retrieved by downloading the level.
     ///
     /// This is [`None`] for levels retrieved via the "overview" endpoint `getGJLevels`.
@@ -634,33 +746,61 @@ impl<'a, Data, Song, User> Level<'a, Data, Song, User> {
         matches!(self.length, LevelLength::Platformer)
     }
 
+    /// An estimate of the difficulty face (from `1.0` for easy to `5.0` for insane/demon) the
+    /// community voted for this level, or [`None`] if no estimate is possible.
+    ///
+    /// If the server reports the raw vote sum (see [`Level::rating_sum`]), this is the average
+    /// vote. Otherwise it is the face of the level's [`LevelRating`].
+    pub fn community_difficulty(&self) -> Option<f32> {
+        if let Some(rating_sum) = self.rating_sum {
+            return Some(rating_sum.average());
+        }
+
+        match self.rating.level_rating {
+            LevelRating::Easy => Some(1.0),
+            LevelRating::Normal => Some(2.0),
+            LevelRating::Hard => Some(3.0),
+            LevelRating::Harder => Some(4.0),
+            LevelRating::Insane | LevelRating::Demon(_) => Some(5.0),
+            LevelRating::Unknown(value) => Some(value as f32 / 10.0),
+            LevelRating::NotAvailable | LevelRating::Auto => None,
+        }
+    }
+
+    /// The featured weight of this level, or [`None`] if it is not currently featured. See
+    /// [`Featured::weight`]
+    pub fn featured_score(&self) -> Option<u32> {
+        self.rating.featured.weight()
+    }
+
     pub fn with_data<Data2>(self, data: Data2) -> Level<'a, Data2, Song, User> {
+        self.map_data(|_| data)
+    }
+
+    pub fn map_data<Data2>(self, f: impl FnOnce(Data) -> Data2) -> Level<'a, Data2, Song, User> {
         Level {
-            level_data: data,
+            level_data: f(self.level_data),
 
             level_id: self.level_id,
             name: self.name,
             description: self.description,
             version: self.version,
             creator: self.creator,
-            difficulty: self.difficulty,
+            rating: self.rating,
+            rating_sum: self.rating_sum,
             downloads: self.downloads,
             main_song: self.main_song,
             gd_version: self.gd_version,
             likes: self.likes,
             length: self.length,
-            stars: self.stars,
-            featured: self.featured,
             copy_of: self.copy_of,
             two_player: self.two_player,
             custom_song: self.custom_song,
-            coin_amount: self.coin_amount,
-            coins_verified: self.coins_verified,
             stars_requested: self.stars_requested,
-            is_epic: self.is_epic,
             object_amount: self.object_amount,
             index_46: self.index_46,
             index_47: self.index_47,
+            unknown_indices: self.unknown_indices,
         }
     }
 
@@ -673,23 +813,20 @@ impl<'a, Data, Song, User> Level<'a, Data, Song, User> {
             description: self.description,
             version: self.version,
             creator: self.creator,
-            difficulty: self.difficulty,
+            rating: self.rating,
+            rating_sum: self.rating_sum,
             downloads: self.downloads,
             main_song: self.main_song,
             gd_version: self.gd_version,
             likes: self.likes,
             length: self.length,
-            stars: self.stars,
-            featured: self.featured,
             copy_of: self.copy_of,
             two_player: self.two_player,
-            coin_amount: self.coin_amount,
-            coins_verified: self.coins_verified,
             stars_requested: self.stars_requested,
-            is_epic: self.is_epic,
             object_amount: self.object_amount,
             index_46: self.index_46,
             index_47: self.index_47,
+            unknown_indices: self.unknown_indices,
             level_data: self.level_data,
         }
     }
@@ -702,29 +839,63 @@ impl<'a, Data, Song, User> Level<'a, Data, Song, User> {
             name: self.name,
             description: self.description,
             version: self.version,
-            difficulty: self.difficulty,
+            rating: self.rating,
+            rating_sum: self.rating_sum,
             downloads: self.downloads,
             main_song: self.main_song,
             gd_version: self.gd_version,
             likes: self.likes,
             length: self.length,
-            stars: self.stars,
-            featured: self.featured,
             copy_of: self.copy_of,
             two_player: self.two_player,
             custom_song: self.custom_song,
-            coin_amount: self.coin_amount,
-            coins_verified: self.coins_verified,
             stars_requested: self.stars_requested,
-            is_epic: self.is_epic,
             object_amount: self.object_amount,
             index_46: self.index_46,
             index_47: self.index_47,
+            unknown_indices: self.unknown_indices,
             level_data: self.level_data,
         }
     }
 }
 
+impl<'a, Data: IntoOwned, Song: IntoOwned, User: IntoOwned> Level<'a, Data, Song, User> {
+    /// Converts this level into one owning all its data. See [`IntoOwned`]
+    pub fn into_owned(self) -> Level<'static, Data::Owned, Song::Owned, User::Owned> {
+        Level {
+            level_id: self.level_id,
+            name: Cow::Owned(self.name.into_owned()),
+            description: self.description.map(Thunk::into_owned),
+            version: self.version,
+            creator: self.creator.into_owned(),
+            rating: self.rating,
+            rating_sum: self.rating_sum,
+            downloads: self.downloads,
+            main_song: self.main_song,
+            gd_version: self.gd_version,
+            likes: self.likes,
+            length: self.length,
+            copy_of: self.copy_of,
+            two_player: self.two_player,
+            custom_song: self.custom_song.into_owned(),
+            stars_requested: self.stars_requested,
+            object_amount: self.object_amount,
+            index_46: self.index_46.map(|cow| Cow::Owned(cow.into_owned())),
+            index_47: self.index_47.map(|cow| Cow::Owned(cow.into_owned())),
+            unknown_indices: self.unknown_indices.into_owned(),
+            level_data: self.level_data.into_owned(),
+        }
+    }
+}
+
+impl<'a, Data: IntoOwned, Song: IntoOwned, User: IntoOwned> IntoOwned for Level<'a, Data, Song, User> {
+    type Owned = Level<'static, Data::Owned, Song::Owned, User::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        Level::into_owned(self)
+    }
+}
+
 impl<'de, Data, Song, User> GJFormat<'de> for Level<'de, Data, Song, User>
 where
     Level<'de, Data, Song, User>: Dash<'de>,
@@ -751,20 +922,18 @@ pub struct LevelData<'a> {
     pub password: Thunk<'a, Password>,
 
     /// The time passed since the `Level` was uploaded, as a string. Note that these strings are
-    /// very imprecise, as they are only of the form "x months ago", or similar.
-    ///
-    /// TODO: Parse these into an enum
+    /// very imprecise, as they are only of the form "x months ago", or similar. See [`GdAge`]
     ///
     /// ## GD Internals:
     /// This value is provided at index `28`
-    pub time_since_upload: Cow<'a, str>,
+    pub time_since_upload: GdAge<'a>,
 
     /// The time passed since the `Level` was last updated, as a string. Note that these strings are
-    /// very imprecise, as they are only of the form "x months ago", or similar.
+    /// very imprecise, as they are only of the form "x months ago", or similar. See [`GdAge`]
     ///
     /// ## GD Internals:
     /// This value is provided at index `29`
-    pub time_since_update: Cow<'a, str>,
+    pub time_since_update: GdAge<'a>,
 
     /// According to the GDPS source, this is a value called `extraString`
     ///
@@ -781,10 +950,73 @@ pub struct LevelData<'a> {
     pub index_57: Cow<'a, str>,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+impl<'a, Song, User> Level<'a, LevelData<'a>, Song, User> {
+    /// Cross-checks this level's reported two-player flag against its level data, processing the
+    /// level data if necessary. See [`TwoPlayer::cross_check`]
+    pub fn cross_check_two_player(&mut self) -> Result<&TwoPlayer, LevelProcessError> {
+        let objects = self.level_data.level_data.process()?;
+
+        self.two_player.cross_check(objects);
+
+        Ok(&self.two_player)
+    }
+}
+
+impl<'a> LevelData<'a> {
+    pub fn into_owned(self) -> LevelData<'static> {
+        LevelData {
+            level_data: self.level_data.into_owned(),
+            password: self.password.into_owned(),
+            time_since_upload: self.time_since_upload.into_owned(),
+            time_since_update: self.time_since_update.into_owned(),
+            index_36: Cow::Owned(self.index_36.into_owned()),
+            index_40: Cow::Owned(self.index_40.into_owned()),
+            index_52: Cow::Owned(self.index_52.into_owned()),
+            index_53: Cow::Owned(self.index_53.into_owned()),
+            index_57: Cow::Owned(self.index_57.into_owned()),
+        }
+    }
+
+    /// This level's data as a [`LevelString`], which is only decompressed once the returned
+    /// [`Thunk`] is processed. Unlike processing [`LevelData::level_data`], this does not parse the
+    /// level string into [`Objects`].
+    pub fn level_string(&self) -> Result<Thunk<'_, LevelStringDecoder>, LevelProcessError> {
+        Ok(Thunk::Unprocessed(self.level_data.as_unprocessed()?))
+    }
+
+    /// Parses this level's data into the given [`LevelObjectBuffer`], see [`parse_objects_into`]
+    ///
+    /// If the level data has already been processed, its objects are copied into the buffer instead.
+    pub fn parse_objects_into(&self, buffer: &mut LevelObjectBuffer) -> Result<(), LevelProcessError> {
+        match &self.level_data {
+            Thunk::Unprocessed(raw) => parse_objects_into(raw, buffer),
+            Thunk::Processed(objects) => {
+                buffer.clear();
+                buffer.meta = objects.meta;
+                buffer.objects.extend_from_slice(&objects.objects);
+                buffer.compression = objects.compression;
+                buffer.alphabet = objects.alphabet;
+
+                Ok(())
+            },
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct Objects {
     pub meta: LevelMetadata,
     pub objects: Vec<LevelObject>,
+
+    /// The compression scheme the level data was compressed with, which is reused when
+    /// re-encoding these objects
+    #[serde(default)]
+    pub compression: LevelCompression,
+
+    /// The base64 alphabet the level data was encoded with, which is reused when re-encoding these
+    /// objects
+    #[serde(default)]
+    pub alphabet: Base64Alphabet,
 }
 
 #[derive(Debug, Error)]
@@ -795,70 +1027,341 @@ pub enum LevelProcessError {
     #[error("{0}")]
     Serialize(#[from] SerError),
 
+    // base64's errors only implement `Error` with its `std` feature
     #[error("{0}")]
-    Base64(#[from] base64::DecodeError),
+    Base64(#[cfg_attr(feature = "std", source)] base64::DecodeError),
 
     /// Unknown compression format for level data
     #[error("Unknown compression scheme")]
     UnknownCompression,
 
     /// Error during (de)compression
+    #[cfg(feature = "std")]
     #[error("{0}")]
     Compression(#[from] std::io::Error),
 
+    /// Level data (de)compression is only available with the `std` feature
+    #[cfg(not(feature = "std"))]
+    #[error("Level data (de)compression requires the std feature")]
+    CompressionUnsupported,
+
     /// The given level string did not contain a metadata section
     #[error("Missing metadata section in level string")]
     MissingMetadata,
+
+    /// The level data exceeded one of the configured [`limits`](crate::limits)
+    #[error("{0}")]
+    LimitExceeded(#[from] LimitExceeded),
 }
 
-impl ThunkProcessor for Objects {
-    type Error = LevelProcessError;
-    type Output<'a> = Objects;
+impl From<base64::DecodeError> for LevelProcessError {
+    fn from(value: base64::DecodeError) -> Self {
+        LevelProcessError::Base64(value)
+    }
+}
 
-    fn from_unprocessed(unprocessed: Cow<str>) -> Result<Self, LevelProcessError> {
-        // Doing the entire base64 in one go is actually faster than using base64::read::DecoderReader and
-        // having the two readers go back and forth.
-        let decoded = URL_SAFE.decode(&*unprocessed)?;
+/// The compression scheme some level data was compressed with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum LevelCompression {
+    /// gzip compression, recognizable by the magic bytes `0x1f 0x8b`. Used for all levels uploaded
+    /// in recent versions of the game.
+    #[default]
+    Gzip,
+
+    /// zlib compression. Only used for very old levels.
+    Zlib,
+}
 
+impl LevelCompression {
+    #[cfg(not(feature = "std"))]
+    fn decompress_into(_data: &[u8], _decompressed: &mut String, _limits: &Limits) -> Result<LevelCompression, LevelProcessError> {
+        Err(LevelProcessError::CompressionUnsupported)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn compress(self, _data: &[u8]) -> Result<Vec<u8>, LevelProcessError> {
+        Err(LevelProcessError::CompressionUnsupported)
+    }
+
+    fn decompress(data: &[u8], limits: &Limits) -> Result<(LevelCompression, String), LevelProcessError> {
+        let mut decompressed = String::new();
+
+        LevelCompression::decompress_into(data, &mut decompressed, limits).map(|compression| (compression, decompressed))
+    }
+
+    /// Decompresses the given data, appending the result to `decompressed`. Aborts with
+    /// [`LevelProcessError::LimitExceeded`] once the output exceeds
+    /// [`Limits::max_decompressed_level_size`].
+    #[cfg(feature = "std")]
+    fn decompress_into(data: &[u8], decompressed: &mut String, limits: &Limits) -> Result<LevelCompression, LevelProcessError> {
         // Here's the deal: Robtop decompresses all levels by calling the zlib function 'inflateInit2_' with
         // the second argument set to 47. This basically tells zlib "this data might be compressed using
         // zlib or gzip format, with window size at most 15, but you gotta figure it out yourself".
         // However, flate2 doesnt expose this option, so we have to manually determine whether we
         // have gzip or zlib compression.
 
-        let mut decompressed = String::new();
-
-        match &decoded[..2] {
+        let compression = match data.get(..2) {
             // gz magic bytes
-            [0x1f, 0x8b] => {
-                let mut decoder = GzDecoder::new(&decoded[..]);
+            Some([0x1f, 0x8b]) => {
+                read_bounded(GzDecoder::new(data), decompressed, limits)?;
 
-                decoder.read_to_string(&mut decompressed)?;
+                LevelCompression::Gzip
             },
             // There's no such thing as "zlib magic bytes", but the first byte stores some information about how the data is compressed.
             // '0x78' is the first byte for the compression method robtop used (note: this is only used for very old levels, as he switched
             // to gz for newer levels)
-            [0x78, _] => {
-                let mut decoder = ZlibDecoder::new(&decoded[..]);
+            Some([0x78, _]) => {
+                read_bounded(ZlibDecoder::new(data), decompressed, limits)?;
 
-                decoder.read_to_string(&mut decompressed)?;
+                LevelCompression::Zlib
             },
             _ => return Err(LevelProcessError::UnknownCompression),
-        }
+        };
+
+        Ok(compression)
+    }
 
-        let mut iter = decompressed.split_terminator(';');
+    #[cfg(feature = "std")]
+    fn compress(self, data: &[u8]) -> Result<Vec<u8>, LevelProcessError> {
+        let mut compressed = Vec::new();
 
-        let metadata_string = match iter.next() {
-            Some(meta) => meta,
-            None => return Err(LevelProcessError::MissingMetadata),
+        match self {
+            LevelCompression::Gzip => GzEncoder::new(data, Compression::new(9)).read_to_end(&mut compressed)?,
+            LevelCompression::Zlib => ZlibEncoder::new(data, Compression::new(9)).read_to_end(&mut compressed)?,
         };
 
-        let meta = LevelMetadata::from_gj_str(metadata_string).map_err(|err| LevelProcessError::Deserialize(err.to_string()))?;
+        Ok(compressed)
+    }
+}
+
+/// Reads the given decompressing reader to the end, appending at most
+/// [`Limits::max_decompressed_level_size`] bytes to `decompressed`
+#[cfg(feature = "std")]
+fn read_bounded<R: Read>(reader: R, decompressed: &mut String, limits: &Limits) -> Result<(), LevelProcessError> {
+    let max = limits.max_decompressed_level_size;
+    let mut reader = reader.take((max as u64).saturating_add(1));
+    let result = reader.read_to_string(decompressed);
+
+    // If the reader hit its limit, the output was truncated (potentially in the middle of a UTF-8
+    // sequence, which causes an error)
+    if reader.limit() == 0 {
+        return Err(LimitExceeded {
+            limit: Limit::DecompressedLevelSize,
+            max,
+        }
+        .into());
+    }
+
+    result?;
+
+    Ok(())
+}
+
+/// The base64 alphabet some level data was encoded with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Base64Alphabet {
+    /// The urlsafe alphabet, using `-` and `_`. This is what the official servers use.
+    #[default]
+    UrlSafe,
+
+    /// The standard alphabet, using `+` and `/`. Some GDPS implementations use this one.
+    Standard,
+}
+
+impl Base64Alphabet {
+    /// Guesses the alphabet used to encode the given string.
+    ///
+    /// Since both alphabets only differ in two characters, any string containing neither of them
+    /// can be decoded using either alphabet. In that case, [`Base64Alphabet::UrlSafe`] is returned.
+    fn detect(encoded: &str) -> Base64Alphabet {
+        if encoded.contains(|c| c == '+' || c == '/') {
+            Base64Alphabet::Standard
+        } else {
+            Base64Alphabet::UrlSafe
+        }
+    }
+
+    fn decode(self, encoded: &str, limits: &Limits) -> Result<Vec<u8>, LevelProcessError> {
+        limits.check(Limit::Base64Length, encoded.len())?;
+
+        Ok(match self {
+            Base64Alphabet::UrlSafe => ROBTOP_BASE64_CONFIG.decode(encoded),
+            Base64Alphabet::Standard => ROBTOP_STANDARD_BASE64_CONFIG.decode(encoded),
+        }?)
+    }
+
+    fn encode(self, data: &[u8]) -> String {
+        match self {
+            Base64Alphabet::UrlSafe => ROBTOP_BASE64_CONFIG.encode(data),
+            Base64Alphabet::Standard => ROBTOP_STANDARD_BASE64_CONFIG.encode(data),
+        }
+    }
+}
+
+/// A decompressed, but otherwise unprocessed level string, as stored at index `4` of downloaded
+/// levels.
+///
+/// Compared to [`Objects`], this does not parse the level string into individual objects, it
+/// only undoes the base64 encoding and compression. The original encoded data is retained until the
+/// level string is modified, so that re-encoding an unmodified [`LevelString`] reproduces the
+/// server's data byte-for-byte (recompressing would not, as the output of gzip depends on the
+/// compression level and header fields used).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelString<'a> {
+    decompressed: String,
+    compression: LevelCompression,
+    alphabet: Base64Alphabet,
+
+    #[serde(skip)]
+    original: Option<Cow<'a, str>>,
+}
+
+/// Compares the decompressed level strings together with the compression scheme and base64
+/// alphabet, but not the retained original data. Two [`LevelString`]s are thus equal regardless of
+/// whether they were decoded or constructed.
+impl PartialEq for LevelString<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.decompressed == other.decompressed && self.compression == other.compression && self.alphabet == other.alphabet
+    }
+}
+
+impl Eq for LevelString<'_> {}
+
+impl<'a> LevelString<'a> {
+    /// Constructs a new [`LevelString`] from some decompressed level string, which will be gzip
+    /// compressed and urlsafe base64 encoded when serialized
+    pub fn new(decompressed: String) -> LevelString<'static> {
+        LevelString {
+            decompressed,
+            compression: LevelCompression::Gzip,
+            alphabet: Base64Alphabet::UrlSafe,
+            original: None,
+        }
+    }
+
+    /// Base64 decodes and decompresses the given raw level data, automatically detecting the base64
+    /// alphabet and compression scheme used.
+    pub fn decode(raw: Cow<'a, str>) -> Result<Self, LevelProcessError> {
+        LevelString::decode_with_limits(raw, &Limits::DEFAULT)
+    }
+
+    /// Like [`LevelString::decode`], but enforces the given [`Limits`] instead of
+    /// [`Limits::DEFAULT`]
+    pub fn decode_with_limits(raw: Cow<'a, str>, limits: &Limits) -> Result<Self, LevelProcessError> {
+        let alphabet = Base64Alphabet::detect(&raw);
+        let (compression, decompressed) = LevelCompression::decompress(&alphabet.decode(&raw, limits)?, limits)?;
+
+        Ok(LevelString {
+            decompressed,
+            compression,
+            alphabet,
+            original: Some(raw),
+        })
+    }
+
+    /// Compresses and base64 encodes this [`LevelString`] using the same scheme it was originally
+    /// encoded with.
+    ///
+    /// If this [`LevelString`] has not been modified since it was decoded, this returns the
+    /// original data without re-encoding it.
+    pub fn encode(&self) -> Result<Cow<'_, str>, LevelProcessError> {
+        if let Some(ref original) = self.original {
+            return Ok(Cow::Borrowed(original.as_ref()));
+        }
+
+        let compressed = self.compression.compress(self.decompressed.as_bytes())?;
+
+        Ok(Cow::Owned(self.alphabet.encode(&compressed)))
+    }
+
+    /// The decompressed level string
+    pub fn as_str(&self) -> &str {
+        &self.decompressed
+    }
+
+    /// Replaces the level string with the given one.
+    ///
+    /// Subsequent calls to [`LevelString::encode`] will recompress the new data using the same
+    /// compression scheme and base64 alphabet as the original data.
+    pub fn replace(&mut self, decompressed: String) {
+        self.decompressed = decompressed;
+        self.original = None;
+    }
+
+    /// The compression scheme this level string was compressed with
+    pub fn compression(&self) -> LevelCompression {
+        self.compression
+    }
+
+    /// The base64 alphabet this level string was encoded with
+    pub fn alphabet(&self) -> Base64Alphabet {
+        self.alphabet
+    }
+
+    pub fn into_string(self) -> String {
+        self.decompressed
+    }
+
+    pub fn into_owned(self) -> LevelString<'static> {
+        LevelString {
+            decompressed: self.decompressed,
+            compression: self.compression,
+            alphabet: self.alphabet,
+            original: self.original.map(|original| Cow::Owned(original.into_owned())),
+        }
+    }
+}
+
+/// [`ThunkProcessor`] that decompresses level data into a [`LevelString`] without parsing it into
+/// [`Objects`], see [`LevelData::level_string`]
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone, Copy)]
+pub struct LevelStringDecoder;
+
+impl ThunkProcessor for LevelStringDecoder {
+    type Error = LevelProcessError;
+    type Output<'a> = LevelString<'a>;
+
+    fn from_unprocessed(unprocessed: Cow<str>) -> Result<LevelString<'_>, LevelProcessError> {
+        LevelString::decode(unprocessed)
+    }
+
+    fn from_unprocessed_with_limits<'b>(unprocessed: Cow<'b, str>, limits: &Limits) -> Result<LevelString<'b>, LevelProcessError> {
+        LevelString::decode_with_limits(unprocessed, limits)
+    }
+
+    fn as_unprocessed<'b>(processed: &'b LevelString<'_>) -> Result<Cow<'b, str>, LevelProcessError> {
+        processed.encode()
+    }
+
+    fn downcast_output_lifetime<'b: 'c, 'c, 's>(output: &'s Self::Output<'b>) -> &'s Self::Output<'c> {
+        output
+    }
+}
+
+impl IntoOwnedThunkProcessor for LevelStringDecoder {
+    fn output_into_owned(output: Self::Output<'_>) -> Self::Output<'static> {
+        output.into_owned()
+    }
+}
+
+impl ThunkProcessor for Objects {
+    type Error = LevelProcessError;
+    type Output<'a> = Objects;
+
+    fn from_unprocessed(unprocessed: Cow<str>) -> Result<Self, LevelProcessError> {
+        Objects::from_unprocessed_with_limits(unprocessed, &Limits::DEFAULT)
+    }
+
+    fn from_unprocessed_with_limits(unprocessed: Cow<str>, limits: &Limits) -> Result<Self, LevelProcessError> {
+        let level_string = LevelString::decode_with_limits(unprocessed, limits)?;
+        let mut objects = Vec::new();
 
-        iter.map(LevelObject::from_gj_str)
-            .collect::<Result<_, _>>()
-            .map(|objects| Objects { meta, objects })
-            .map_err(|err| LevelProcessError::Deserialize(err.to_string()))
+        parse_objects(level_string.as_str(), &mut objects).map(|meta| Objects {
+            meta,
+            objects,
+            compression: level_string.compression(),
+            alphabet: level_string.alphabet(),
+        })
     }
 
     fn as_unprocessed(processed: &Objects) -> Result<Cow<str>, LevelProcessError> {
@@ -873,16 +1376,9 @@ impl ThunkProcessor for Objects {
             bytes.push(b';');
         }
 
-        // FIXME(game specific): Should we remember the compression scheme (zlib or gz) from above, or just
-        // always re-compress using gz? Since the game dyncamially detects the compression method, we're
-        // compatible either way.
-
-        let mut encoder = GzEncoder::new(&bytes[..], Compression::new(9)); // TODO: idk what these values mean
-        let mut compressed = Vec::new();
-
-        encoder.read_to_end(&mut compressed)?;
+        let compressed = processed.compression.compress(&bytes)?;
 
-        Ok(Cow::Owned(URL_SAFE.encode(compressed)))
+        Ok(Cow::Owned(processed.alphabet.encode(&compressed)))
     }
 
     fn downcast_output_lifetime<'b: 'c, 'c, 's>(output: &'s Self::Output<'b>) -> &'s Self::Output<'c> {
@@ -890,61 +1386,232 @@ impl ThunkProcessor for Objects {
     }
 }
 
-impl Objects {
-    pub fn length_in_seconds(&self) -> f32 {
-        let mut portals = Vec::new();
-        let mut furthest_x = 0.0;
+impl IntoOwnedThunkProcessor for Objects {
+    fn output_into_owned(output: Self::Output<'_>) -> Self::Output<'static> {
+        output
+    }
+}
 
-        for object in &self.objects {
-            if let ObjectData::SpeedPortal { checked: true, speed } = object.metadata {
-                portals.push((object.x, speed))
-            }
+/// Parses the given decompressed level string, appending its objects to `objects` and returning its
+/// metadata
+fn parse_objects(level_string: &str, objects: &mut Vec<LevelObject>) -> Result<LevelMetadata, LevelProcessError> {
+    let mut iter = level_string.split_terminator(';');
+
+    let metadata_string = match iter.next() {
+        Some(meta) => meta,
+        None => return Err(LevelProcessError::MissingMetadata),
+    };
 
-            furthest_x = f32::max(furthest_x, object.x);
+    let meta = LevelMetadata::from_gj_str(metadata_string).map_err(|err| LevelProcessError::Deserialize(err.to_string()))?;
+
+    for object in iter {
+        objects.push(LevelObject::from_gj_str(object).map_err(|err| LevelProcessError::Deserialize(err.to_string()))?);
+    }
+
+    Ok(meta)
+}
+
+/// Reusable storage for the objects of parsed level data, see [`parse_objects_into`]
+///
+/// Parsing level data via [`Thunk::process`] allocates a fresh buffer for the decoded data, the
+/// decompressed level string and the parsed objects of every level, all of which are alive at the
+/// same time. A `LevelObjectBuffer` instead streams the level data through base64 decoding,
+/// decompression and parsing, so that neither the decoded nor the decompressed data is ever held in
+/// memory in full. Only the parsed objects and a small scratch buffer are kept around (and reused),
+/// so that pipelines processing many levels one after another only allocate while the buffer is
+/// still growing.
+#[derive(Debug, Default)]
+pub struct LevelObjectBuffer {
+    meta: LevelMetadata,
+    objects: Vec<LevelObject>,
+    compression: LevelCompression,
+    alphabet: Base64Alphabet,
+    scratch: Vec<u8>,
+    limits: Limits,
+}
+
+impl LevelObjectBuffer {
+    /// Constructs a new, empty `LevelObjectBuffer`
+    pub fn new() -> Self {
+        LevelObjectBuffer::default()
+    }
+
+    /// Constructs a new, empty `LevelObjectBuffer` enforcing the given [`Limits`] (instead of
+    /// [`Limits::DEFAULT`]) when level data is parsed into it
+    pub fn with_limits(limits: Limits) -> Self {
+        LevelObjectBuffer {
+            limits,
+            ..LevelObjectBuffer::default()
         }
+    }
+
+    /// The metadata of the level most recently parsed into this buffer
+    pub fn meta(&self) -> &LevelMetadata {
+        &self.meta
+    }
+
+    /// The objects of the level most recently parsed into this buffer
+    pub fn objects(&self) -> &[LevelObject] {
+        &self.objects
+    }
+
+    /// Empties this buffer, without releasing any of its allocations
+    pub fn clear(&mut self) {
+        self.meta = LevelMetadata::default();
+        self.objects.clear();
+        self.scratch.clear();
+    }
+
+    /// Converts the contents of this buffer into [`Objects`], releasing the scratch space used for
+    /// decompression
+    pub fn into_objects(self) -> Objects {
+        Objects {
+            meta: self.meta,
+            objects: self.objects,
+            compression: self.compression,
+            alphabet: self.alphabet,
+        }
+    }
+}
 
-        portals.sort_by(|(x1, _), (x2, _)| x1.partial_cmp(x2).unwrap());
+/// The number of decompressed bytes [`parse_objects_into`] processes at a time
+#[cfg(feature = "std")]
+const STREAMING_CHUNK_SIZE: usize = 16 * 1024;
 
-        get_seconds_from_x_pos(furthest_x, self.meta.starting_speed, &portals)
+/// Base64 decodes, decompresses and parses the given raw level data (as found at index `4` of a
+/// downloaded level) into the given [`LevelObjectBuffer`], replacing its previous contents.
+///
+/// The data is decoded and decompressed incrementally while being parsed, so apart from the parsed
+/// objects, only a chunk of the decompressed level string (plus the object currently spanning the
+/// chunk boundary) is held in memory at any time. If an error occurs, the buffer is left empty. The
+/// data is subject to the [`Limits`] the buffer was constructed with.
+pub fn parse_objects_into(raw: &str, buffer: &mut LevelObjectBuffer) -> Result<(), LevelProcessError> {
+    buffer.clear();
+
+    let result = stream_objects(raw, buffer);
+
+    if result.is_err() {
+        buffer.clear();
     }
+
+    result
+}
+
+#[cfg(not(feature = "std"))]
+fn stream_objects(_raw: &str, _buffer: &mut LevelObjectBuffer) -> Result<(), LevelProcessError> {
+    Err(LevelProcessError::CompressionUnsupported)
 }
 
-fn get_seconds_from_x_pos(pos: f32, start_speed: Speed, portals: &[(f32, Speed)]) -> f32 {
-    let mut speed: f32 = start_speed.into();
+#[cfg(feature = "std")]
+fn stream_objects(raw: &str, buffer: &mut LevelObjectBuffer) -> Result<(), LevelProcessError> {
+    buffer.limits.check(Limit::Base64Length, raw.len())?;
+
+    buffer.alphabet = Base64Alphabet::detect(raw);
+
+    let engine = match buffer.alphabet {
+        Base64Alphabet::UrlSafe => &ROBTOP_BASE64_CONFIG,
+        Base64Alphabet::Standard => &ROBTOP_STANDARD_BASE64_CONFIG,
+    };
+
+    // The compression scheme is determined by the first two bytes of the decoded data, which are
+    // contained in the first four base64 characters
+    let magic = engine.decode(&raw.as_bytes()[..raw.len().min(4)])?;
+    let decoded = base64::read::DecoderReader::new(raw.as_bytes(), engine);
+
+    // See `LevelCompression::decompress_into` regarding the detection of the compression scheme
+    match magic.get(..2) {
+        Some([0x1f, 0x8b]) => {
+            buffer.compression = LevelCompression::Gzip;
+
+            stream_decompressed_objects(GzDecoder::new(decoded), buffer)
+        },
+        Some([0x78, _]) => {
+            buffer.compression = LevelCompression::Zlib;
 
-    if portals.is_empty() {
-        return pos / speed;
+            stream_decompressed_objects(ZlibDecoder::new(decoded), buffer)
+        },
+        _ => Err(LevelProcessError::UnknownCompression),
     }
+}
 
-    let mut last_obj_pos = 0.0;
-    let mut total_time = 0.0;
+/// Parses the level string read from the given decompressing reader into the given buffer, reading
+/// at most [`Limits::max_decompressed_level_size`] bytes
+#[cfg(feature = "std")]
+fn stream_decompressed_objects<R: Read>(reader: R, buffer: &mut LevelObjectBuffer) -> Result<(), LevelProcessError> {
+    let max = buffer.limits.max_decompressed_level_size;
+    let mut reader = reader.take((max as u64).saturating_add(1));
+    let mut meta = None;
 
-    for (x, portal_speed) in portals {
-        // distance between last portal and this one
-        let current_segment = x - last_obj_pos;
+    loop {
+        let len = buffer.scratch.len();
 
-        // break if we're past the position we want to calculate the position to
-        if pos <= current_segment {
-            break;
+        buffer.scratch.resize(len + STREAMING_CHUNK_SIZE, 0);
+
+        let read = reader.read(&mut buffer.scratch[len..]);
+
+        buffer.scratch.truncate(len + *read.as_ref().unwrap_or(&0));
+
+        let read = match read {
+            Ok(read) => read,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err.into()),
+        };
+
+        if reader.limit() == 0 {
+            return Err(LimitExceeded {
+                limit: Limit::DecompressedLevelSize,
+                max,
+            }
+            .into());
         }
 
-        // Calculate time spent in this segment and add to total time
-        total_time += current_segment / speed;
+        let finished = read == 0;
 
-        speed = (*portal_speed).into();
+        // Only parse up to the last complete object, unless the end of the data has been reached
+        let end = match buffer.scratch.iter().rposition(|&byte| byte == b';') {
+            _ if finished => buffer.scratch.len(),
+            Some(idx) => idx + 1,
+            None => continue,
+        };
 
-        last_obj_pos = *x;
+        let complete = core::str::from_utf8(&buffer.scratch[..end]).map_err(|err| LevelProcessError::Deserialize(err.to_string()))?;
+
+        for segment in complete.split_terminator(';') {
+            match meta {
+                None => meta = Some(LevelMetadata::from_gj_str(segment).map_err(|err| LevelProcessError::Deserialize(err.to_string()))?),
+                Some(_) => buffer
+                    .objects
+                    .push(LevelObject::from_gj_str(segment).map_err(|err| LevelProcessError::Deserialize(err.to_string()))?),
+            }
+        }
+
+        buffer.scratch.drain(..end);
+
+        if finished {
+            break;
+        }
     }
 
-    // add the time spent between end and last portal to total time and return
-    (pos - last_obj_pos) / speed + total_time
+    buffer.meta = meta.ok_or(LevelProcessError::MissingMetadata)?;
+
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use base64::{engine::general_purpose::URL_SAFE, Engine};
 
-    use crate::model::level::{robtop_encode_level_password, Password};
+    use crate::{
+        model::level::{
+            metadata::LevelMetadata, parse_objects_into, robtop_encode_level_password, Base64Alphabet, DemonRating, Featured, Level,
+            LevelCompression, LevelObjectBuffer, LevelRating, LevelString, LevelStringDecoder, Objects, Password, RatingSum, TwoPlayer,
+        },
+        Thunk, ThunkProcessor,
+    };
+    use std::borrow::Cow;
+
+    const GZIP_URLSAFE_LEVEL_DATA: &str = "H4sIAAAAAAACA8t2NNExtDbUMdQx0jE01TEGEkCeBZBnAuUBAA3b16giAAAA";
+    const ZLIB_STANDARD_LEVEL_DATA: &str = "eJzLdjTRMbA21DHUMdIxNDLQMdYxNLUGADH+BE8=";
 
     #[test]
     fn deserialize_password() {
@@ -955,6 +1622,34 @@ mod tests {
         assert_eq!(Password::from_robtop("0").unwrap(), Password::NoCopy);
     }
 
+    #[test]
+    fn featured_weight() {
+        assert_eq!(Featured::from(-1).weight(), None);
+        assert_eq!(Featured::from(0).weight(), None);
+        assert_eq!(Featured::from(1482).weight(), Some(1482));
+    }
+
+    #[test]
+    fn two_player_without_dual_mode_is_mismatched() {
+        let objects = Objects {
+            meta: LevelMetadata {
+                two_player_controls: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut two_player = TwoPlayer::from(true);
+        assert!(!two_player.is_mismatched());
+
+        two_player.cross_check(&objects);
+        assert!(two_player.is_mismatched());
+
+        let mut two_player = TwoPlayer::from(false);
+        two_player.cross_check(&objects);
+        assert!(two_player.is_mismatched());
+    }
+
     #[test]
     fn serialize_password() {
         let encoded = robtop_encode_level_password(123456);
@@ -973,4 +1668,124 @@ mod tests {
         // password of 'Breakthrough' by Hinds1324
         assert_eq!(URL_SAFE.encode(robtop_encode_level_password(0)), "AwYDBgQCBg==")
     }
+
+    #[test]
+    fn decode_gzip_level_string() {
+        let level_string = LevelString::decode(Cow::Borrowed(GZIP_URLSAFE_LEVEL_DATA)).unwrap();
+
+        assert_eq!(level_string.as_str(), "kA4,1;1,1,2,15,3,15;1,8,2,45,3,15;");
+        assert_eq!(level_string.compression(), LevelCompression::Gzip);
+        assert_eq!(level_string.alphabet(), Base64Alphabet::UrlSafe);
+        assert_eq!(level_string.encode().unwrap(), GZIP_URLSAFE_LEVEL_DATA);
+    }
+
+    #[test]
+    fn decode_zlib_standard_base64_level_string() {
+        let level_string = LevelString::decode(Cow::Borrowed(ZLIB_STANDARD_LEVEL_DATA)).unwrap();
+
+        assert_eq!(level_string.as_str(), "kA4,0;1,1,2,120,3,15;");
+        assert_eq!(level_string.compression(), LevelCompression::Zlib);
+        assert_eq!(level_string.alphabet(), Base64Alphabet::Standard);
+        assert_eq!(level_string.encode().unwrap(), ZLIB_STANDARD_LEVEL_DATA);
+    }
+
+    #[test]
+    fn modified_level_string_is_recompressed() {
+        let mut level_string = LevelString::decode(Cow::Borrowed(ZLIB_STANDARD_LEVEL_DATA)).unwrap();
+
+        level_string.replace("kA4,2;1,1,2,15,3,15;".to_string());
+
+        let reencoded = level_string.encode().unwrap();
+        let decoded = LevelString::decode(reencoded).unwrap();
+
+        assert_eq!(decoded.as_str(), "kA4,2;1,1,2,15,3,15;");
+        assert_eq!(decoded.compression(), LevelCompression::Zlib);
+
+        // Only `decoded` retains its encoded data, which does not affect equality
+        assert_eq!(decoded, level_string);
+    }
+
+    #[test]
+    fn process_objects_from_standard_base64() {
+        let objects = Objects::from_unprocessed(Cow::Borrowed(ZLIB_STANDARD_LEVEL_DATA)).unwrap();
+
+        assert_eq!(objects.objects.len(), 1);
+        assert_eq!(objects.objects[0].x, 120.0);
+    }
+
+    #[test]
+    fn objects_are_reencoded_with_original_scheme() {
+        let objects = Objects::from_unprocessed(Cow::Borrowed(ZLIB_STANDARD_LEVEL_DATA)).unwrap();
+
+        assert_eq!(objects.compression, LevelCompression::Zlib);
+        assert_eq!(objects.alphabet, Base64Alphabet::Standard);
+
+        let reencoded = Objects::as_unprocessed(&objects).unwrap();
+        let level_string = LevelString::decode(reencoded).unwrap();
+
+        assert_eq!(level_string.compression(), LevelCompression::Zlib);
+        assert_eq!(level_string.alphabet(), Base64Alphabet::Standard);
+        assert_eq!(Objects::from_unprocessed(level_string.encode().unwrap()).unwrap(), objects);
+
+        let mut buffer = LevelObjectBuffer::new();
+
+        parse_objects_into(ZLIB_STANDARD_LEVEL_DATA, &mut buffer).unwrap();
+
+        assert_eq!(buffer.into_objects(), objects);
+    }
+
+    #[test]
+    fn level_string_is_decoded_lazily() {
+        let mut thunk = Thunk::<LevelStringDecoder>::Unprocessed(Cow::Borrowed(ZLIB_STANDARD_LEVEL_DATA));
+
+        assert_eq!(thunk.process().unwrap().compression(), LevelCompression::Zlib);
+        assert!(matches!(thunk, Thunk::Processed(_)));
+        assert_eq!(thunk.as_unprocessed().unwrap(), ZLIB_STANDARD_LEVEL_DATA);
+    }
+
+    #[test]
+    fn parse_objects_into_reused_buffer() {
+        let mut buffer = LevelObjectBuffer::new();
+
+        parse_objects_into(ZLIB_STANDARD_LEVEL_DATA, &mut buffer).unwrap();
+
+        assert_eq!(
+            buffer.objects(),
+            &Objects::from_unprocessed(Cow::Borrowed(ZLIB_STANDARD_LEVEL_DATA)).unwrap().objects[..]
+        );
+
+        parse_objects_into(GZIP_URLSAFE_LEVEL_DATA, &mut buffer).unwrap();
+
+        assert_eq!(
+            buffer.objects(),
+            &Objects::from_unprocessed(Cow::Borrowed(GZIP_URLSAFE_LEVEL_DATA)).unwrap().objects[..]
+        );
+        assert!(parse_objects_into("not level data", &mut buffer).is_err());
+        assert!(buffer.objects().is_empty());
+    }
+
+    #[test]
+    fn community_difficulty() {
+        let cases = [
+            (LevelRating::NotAvailable, None, None),
+            (LevelRating::Auto, None, None),
+            (LevelRating::Easy, None, Some(1.0)),
+            (LevelRating::Harder, None, Some(4.0)),
+            (LevelRating::Insane, None, Some(5.0)),
+            (LevelRating::Demon(DemonRating::Easy), None, Some(5.0)),
+            (LevelRating::Unknown(25), None, Some(2.5)),
+            // The raw vote sum takes precedence over the level rating
+            (LevelRating::NotAvailable, Some(RatingSum { sum: 7, count: 2 }), Some(3.5)),
+            (LevelRating::Easy, Some(RatingSum { sum: 5, count: 1 }), Some(5.0)),
+        ];
+
+        for (level_rating, rating_sum, expected) in cases {
+            let mut level = Level::builder(1, "Test").build_listed();
+
+            level.rating.level_rating = level_rating;
+            level.rating_sum = rating_sum;
+
+            assert_eq!(level.community_difficulty(), expected, "{:?}, {:?}", level_rating, rating_sum);
+        }
+    }
 }
@@ -3,3 +3,37 @@ pub const NORMAL_PORTAL: u16 = 201;
 pub const MEDIUM_PORTAL: u16 = 202;
 pub const FAST_PORTAL: u16 = 203;
 pub const VERY_FAST_PORTAL: u16 = 1334;
+
+pub const DUAL_PORTAL: u16 = 286;
+pub const SINGLE_PORTAL: u16 = 287;
+
+pub const YELLOW_PAD: u16 = 35;
+pub const BLUE_PAD: u16 = 67;
+pub const PINK_PAD: u16 = 140;
+pub const RED_PAD: u16 = 1332;
+pub const SPIDER_PAD: u16 = 3005;
+
+pub const YELLOW_ORB: u16 = 36;
+pub const BLUE_ORB: u16 = 84;
+pub const PINK_ORB: u16 = 141;
+pub const GREEN_ORB: u16 = 1022;
+pub const BLACK_ORB: u16 = 1330;
+pub const RED_ORB: u16 = 1333;
+pub const GREEN_DASH_ORB: u16 = 1704;
+pub const PINK_DASH_ORB: u16 = 1751;
+pub const SPIDER_ORB: u16 = 3004;
+pub const TELEPORT_ORB: u16 = 3027;
+
+pub const PADS: [u16; 5] = [YELLOW_PAD, BLUE_PAD, PINK_PAD, RED_PAD, SPIDER_PAD];
+pub const ORBS: [u16; 10] = [
+    YELLOW_ORB,
+    BLUE_ORB,
+    PINK_ORB,
+    GREEN_ORB,
+    BLACK_ORB,
+    RED_ORB,
+    GREEN_DASH_ORB,
+    PINK_DASH_ORB,
+    SPIDER_ORB,
+    TELEPORT_ORB,
+];
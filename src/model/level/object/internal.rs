@@ -24,6 +24,19 @@ pub struct InternalLevelObject {
     #[serde(rename = "6", default)]
     rotation: f32,
 
+    // Layer related fields. These are omitted by the game if they have their default value
+    #[serde(rename = "20", default, skip_serializing_if = "Option::is_none")]
+    editor_layer: Option<u16>,
+
+    #[serde(rename = "24", default, skip_serializing_if = "Option::is_none")]
+    z_layer: Option<i32>,
+
+    #[serde(rename = "25", default, skip_serializing_if = "Option::is_none")]
+    z_order: Option<i32>,
+
+    #[serde(rename = "61", default, skip_serializing_if = "Option::is_none")]
+    editor_layer_2: Option<u16>,
+
     // ... other common fields
 
     // portal related fields
@@ -44,6 +57,10 @@ impl<'de> Dash<'de> for LevelObject {
                 checked: internal.checked,
                 speed: Speed::Normal,
             },
+            ids::MEDIUM_PORTAL => ObjectData::SpeedPortal {
+                checked: internal.checked,
+                speed: Speed::Medium,
+            },
             ids::FAST_PORTAL => ObjectData::SpeedPortal {
                 checked: internal.checked,
                 speed: Speed::Fast,
@@ -62,6 +79,10 @@ impl<'de> Dash<'de> for LevelObject {
             flipped_x: internal.flipped_x,
             flipped_y: internal.flipped_y,
             rotation: internal.rotation,
+            editor_layer: internal.editor_layer.unwrap_or_default(),
+            editor_layer_2: internal.editor_layer_2.unwrap_or_default(),
+            z_layer: internal.z_layer.unwrap_or_default().into(),
+            z_order: internal.z_order.unwrap_or_default(),
             metadata,
         })
     }
@@ -74,6 +95,10 @@ impl<'de> Dash<'de> for LevelObject {
             flipped_x: self.flipped_x,
             flipped_y: self.flipped_y,
             rotation: self.rotation,
+            editor_layer: Some(self.editor_layer).filter(|&layer| layer != 0),
+            z_layer: Some(self.z_layer.into()).filter(|&layer| layer != 0),
+            z_order: Some(self.z_order).filter(|&order| order != 0),
+            editor_layer_2: Some(self.editor_layer_2).filter(|&layer| layer != 0),
             ..InternalLevelObject::default()
         };
 
@@ -0,0 +1,86 @@
+use core::cmp::Ordering;
+use serde::{Deserialize, Serialize};
+
+/// Enum modelling the z layer an object is rendered on
+///
+/// Layers prefixed with `B` are rendered behind the player, layers prefixed with `T` in front of
+/// it. Within a layer, objects are ordered by their [z order](super::LevelObject::z_order).
+///
+/// ## GD Internals:
+/// The z layer is stored at index `24` of an object. The value `0` means the object is rendered on
+/// its object-type specific default layer. Ordering of [`ZLayer`]s follows their internal value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum ZLayer {
+    /// The object's default layer
+    #[default]
+    Default,
+    B5,
+    B4,
+    B3,
+    B2,
+    B1,
+    T1,
+    T2,
+    T3,
+    T4,
+    Unknown(i32),
+}
+
+impl ZLayer {
+    /// Whether this layer is rendered behind the player. Returns `None` for [`ZLayer::Default`],
+    /// since the default layer depends on the object type.
+    pub fn is_background(self) -> Option<bool> {
+        match self {
+            ZLayer::Default => None,
+            layer => Some(i32::from(layer) < i32::from(ZLayer::T1)),
+        }
+    }
+}
+
+impl From<i32> for ZLayer {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => ZLayer::Default,
+            -5 => ZLayer::B5,
+            -3 => ZLayer::B4,
+            -1 => ZLayer::B3,
+            1 => ZLayer::B2,
+            3 => ZLayer::B1,
+            5 => ZLayer::T1,
+            7 => ZLayer::T2,
+            9 => ZLayer::T3,
+            11 => ZLayer::T4,
+            unknown => ZLayer::Unknown(unknown),
+        }
+    }
+}
+
+impl From<ZLayer> for i32 {
+    fn from(layer: ZLayer) -> Self {
+        match layer {
+            ZLayer::Default => 0,
+            ZLayer::B5 => -5,
+            ZLayer::B4 => -3,
+            ZLayer::B3 => -1,
+            ZLayer::B2 => 1,
+            ZLayer::B1 => 3,
+            ZLayer::T1 => 5,
+            ZLayer::T2 => 7,
+            ZLayer::T3 => 9,
+            ZLayer::T4 => 11,
+            ZLayer::Unknown(unknown) => unknown,
+        }
+    }
+}
+
+impl PartialOrd for ZLayer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ZLayer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        i32::from(*self).cmp(&i32::from(*other))
+    }
+}
@@ -1,8 +1,9 @@
-use crate::model::level::object::speed::Speed;
+use crate::model::level::object::{layer::ZLayer, speed::Speed};
 use serde::{Deserialize, Serialize};
 
 pub mod ids;
 mod internal;
+pub mod layer;
 pub mod speed;
 
 #[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
@@ -13,10 +14,58 @@ pub struct LevelObject {
     pub flipped_x: bool,
     pub flipped_y: bool,
     pub rotation: f32,
+
+    /// The editor layer this object is on
+    ///
+    /// ## GD Internals:
+    /// This value is stored at index `20`
+    pub editor_layer: u16,
+
+    /// The second editor layer this object is on. A value of `0` means the object is not on a
+    /// second editor layer.
+    ///
+    /// ## GD Internals:
+    /// This value is stored at index `61`
+    pub editor_layer_2: u16,
+
+    /// The z layer this object is rendered on
+    pub z_layer: ZLayer,
+
+    /// The order in which this object is rendered relative to other objects on the same
+    /// [`ZLayer`]. Objects with higher z order are rendered on top.
+    ///
+    /// ## GD Internals:
+    /// This value is stored at index `25`
+    pub z_order: i32,
     // ... other fields they all have ...
     pub metadata: ObjectData,
 }
 
+impl LevelObject {
+    /// Constructs a new object with the given id at the given position, with all other properties
+    /// set to their default values
+    pub fn new(id: u16, x: f32, y: f32) -> LevelObject {
+        LevelObject {
+            id,
+            x,
+            y,
+            flipped_x: false,
+            flipped_y: false,
+            rotation: 0.0,
+            editor_layer: 0,
+            editor_layer_2: 0,
+            z_layer: ZLayer::Default,
+            z_order: 0,
+            metadata: ObjectData::None,
+        }
+    }
+
+    /// Whether this object is on the given editor layer (either as its first or second layer)
+    pub fn is_on_editor_layer(&self, layer: u16) -> bool {
+        self.editor_layer == layer || (self.editor_layer_2 != 0 && self.editor_layer_2 == layer)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Copy, Serialize, Deserialize)]
 pub enum ObjectData {
     None,
@@ -0,0 +1,222 @@
+//! Module containing the [`Rating`] struct, which bundles everything related to how a level was
+//! rated by RobTop
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::level::{DemonRating, Featured, LevelRating};
+
+/// Struct describing how a [`Level`](super::Level) was rated
+///
+/// The fields of this struct are the values as the servers provide them. Use the methods to get at
+/// their in-game interpretation, e.g. [`Rating::difficulty`] and [`Rating::feature_tier`] together
+/// determine the difficulty icon displayed for the level.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Rating {
+    /// The difficulty rating of the level
+    ///
+    /// ## GD Internals:
+    /// This value is a construct from the value at the indices `9`, `17` and
+    /// `25`, whereas index 9 is an integer representation of either the
+    /// [`LevelRating`] or the [`DemonRating`]
+    /// struct, depending on the value of index 17.
+    ///
+    /// If index 25 is set to true, the level is an auto level and the value at
+    /// index 9 is some nonsense, in which case it is ignored.
+    pub level_rating: LevelRating,
+
+    /// The amount of stars completion of the level awards. In the case of a platformer level,
+    /// this is instead the number of "moons" awarded.
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `18`
+    pub stars: u8,
+
+    /// The level's featured state
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `19`
+    pub featured: Featured,
+
+    /// The level's epic tier. See [`Rating::feature_tier`]
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `42`. Before update 2.2 this was a boolean indicating
+    /// whether the level is epic.
+    pub epic: u8,
+
+    /// The amount of user coins in the level
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `37`
+    pub coin_amount: u8,
+
+    /// Value indicating whether the user coins (if present) in the level are verified
+    ///
+    /// ## GD Internals:
+    /// This value is provided at index `38`, as an integer
+    pub coins_verified: bool,
+}
+
+/// The highest honor a level has received, which determines the frame drawn around its difficulty
+/// icon
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum FeatureTier {
+    /// The level is not currently featured
+    None,
+
+    /// The level is featured, but not epic
+    Featured,
+
+    /// The level is epic
+    ///
+    /// ## GD Internals:
+    /// Represented by the value `1` at index `42`
+    Epic,
+
+    /// The level is legendary
+    ///
+    /// ## GD Internals:
+    /// Represented by the value `2` at index `42`
+    Legendary,
+
+    /// The level is mythic
+    ///
+    /// ## GD Internals:
+    /// Represented by the value `3` at index `42`
+    Mythic,
+}
+
+/// The state of the user coins in a level
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum CoinState {
+    /// The level does not contain any user coins
+    None,
+
+    /// The level contains the given amount of user coins, which have not been verified by RobTop
+    /// (and are displayed bronze in-game)
+    Unverified(u8),
+
+    /// The level contains the given amount of verified user coins (which are displayed silver
+    /// in-game)
+    Verified(u8),
+}
+
+impl CoinState {
+    /// The amount of user coins
+    pub fn amount(&self) -> u8 {
+        match self {
+            CoinState::None => 0,
+            CoinState::Unverified(amount) | CoinState::Verified(amount) => *amount,
+        }
+    }
+}
+
+impl Rating {
+    /// Returns `true` iff the level awards stars (or moons)
+    pub fn is_rated(&self) -> bool {
+        self.stars > 0
+    }
+
+    /// The difficulty displayed in-game for the level
+    ///
+    /// This is [`Rating::level_rating`], unless the servers sent a difficulty value dash-rs does not
+    /// know, in which case the difficulty of a rated level is derived from its star count the same
+    /// way RobTop assigns star counts to difficulties.
+    pub fn difficulty(&self) -> LevelRating {
+        match self.level_rating {
+            LevelRating::Unknown(value) => match self.stars {
+                1 => LevelRating::Auto,
+                2 => LevelRating::Easy,
+                3 => LevelRating::Normal,
+                4 | 5 => LevelRating::Hard,
+                6 | 7 => LevelRating::Harder,
+                8 | 9 => LevelRating::Insane,
+                10 => LevelRating::Demon(DemonRating::Unknown(value)),
+                _ => LevelRating::Unknown(value),
+            },
+            rating => rating,
+        }
+    }
+
+    /// The highest honor the level has received. See [`FeatureTier`]
+    ///
+    /// Epic tiers take precedence over the featured state, since the game only displays the
+    /// highest one. Values above `3` at index `42` are treated as [`FeatureTier::Mythic`].
+    pub fn feature_tier(&self) -> FeatureTier {
+        match self.epic {
+            0 if self.featured.is_featured() => FeatureTier::Featured,
+            0 => FeatureTier::None,
+            1 => FeatureTier::Epic,
+            2 => FeatureTier::Legendary,
+            _ => FeatureTier::Mythic,
+        }
+    }
+
+    /// The state of the user coins in the level. See [`CoinState`]
+    pub fn coin_state(&self) -> CoinState {
+        match (self.coin_amount, self.coins_verified) {
+            (0, _) => CoinState::None,
+            (amount, true) => CoinState::Verified(amount),
+            (amount, false) => CoinState::Unverified(amount),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CoinState, FeatureTier, Rating};
+    use crate::model::level::{DemonRating, Featured, LevelRating};
+
+    const RATING: Rating = Rating {
+        level_rating: LevelRating::Unknown(60),
+        stars: 10,
+        featured: Featured::Featured(1000),
+        epic: 0,
+        coin_amount: 3,
+        coins_verified: false,
+    };
+
+    #[test]
+    fn difficulty_derived_from_stars() {
+        assert_eq!(RATING.difficulty(), LevelRating::Demon(DemonRating::Unknown(60)));
+        assert_eq!(Rating { stars: 7, ..RATING }.difficulty(), LevelRating::Harder);
+        assert_eq!(Rating { stars: 0, ..RATING }.difficulty(), LevelRating::Unknown(60));
+        assert_eq!(
+            Rating {
+                level_rating: LevelRating::Easy,
+                ..RATING
+            }
+            .difficulty(),
+            LevelRating::Easy
+        );
+    }
+
+    #[test]
+    fn feature_tier() {
+        assert_eq!(RATING.feature_tier(), FeatureTier::Featured);
+        assert_eq!(Rating { epic: 2, ..RATING }.feature_tier(), FeatureTier::Legendary);
+        assert_eq!(
+            Rating {
+                featured: Featured::Unfeatured,
+                ..RATING
+            }
+            .feature_tier(),
+            FeatureTier::None
+        );
+    }
+
+    #[test]
+    fn coin_state() {
+        assert_eq!(RATING.coin_state(), CoinState::Unverified(3));
+        assert_eq!(
+            Rating {
+                coins_verified: true,
+                ..RATING
+            }
+            .coin_state()
+            .amount(),
+            3
+        );
+        assert_eq!(Rating { coin_amount: 0, ..RATING }.coin_state(), CoinState::None);
+    }
+}
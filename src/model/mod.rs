@@ -13,40 +13,186 @@
 //!   representations with zero allocations.
 //!
 //! These versions can be converted to and from each other, simply by borrowing.
+//!
+//! The [`Serialize`]/[`Deserialize`] implementations of the public models are independent of
+//! RobTop's data format (which is handled by the [`Dash`](crate::Dash) trait) and use plain field
+//! names. They work with both self-describing formats such as JSON and non-self-describing ones such
+//! as bincode, making them suitable for persisting models, e.g. in caching layers. In these formats,
+//! [`Thunk`](crate::Thunk)s are always stored in their processed form.
 
+use crate::model::{
+    comment::{
+        level::{CommentUser, LevelComment},
+        profile::ProfileComment,
+    },
+    creator::Creator,
+    level::LevelData,
+    song::NewgroundsSong,
+    user::{profile::Profile, searched::SearchedUser},
+};
+use core::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
 use serde::{Deserialize, Serialize};
-use std::fmt::{Display, Formatter};
+use thiserror::Error;
 
+pub mod age;
 pub mod comment;
 pub mod creator;
+pub mod icon;
 pub mod level;
+pub mod reward;
 pub mod song;
 pub mod user;
 
+/// Trait for converting a model borrowing from its deserialization source into one owning all its
+/// data.
+///
+/// All [`Cow`](std::borrow::Cow)s are converted into their owned variant, and all
+/// [`Thunk`](crate::Thunk)s are converted without processing (or unprocessing) them.
+///
+/// Every model also has an inherent `into_owned` method. This trait exists so that generic models
+/// such as [`Level`](level::Level) can be converted independently of their type parameters.
+pub trait IntoOwned {
+    /// The owned version of this type, generally `Self` with all lifetimes set to `'static`
+    type Owned: 'static;
+
+    fn into_owned(self) -> Self::Owned;
+}
+
+macro_rules! into_owned_identity {
+    ($($t:ty),*) => {
+        $(
+            impl IntoOwned for $t {
+                type Owned = $t;
+
+                fn into_owned(self) -> $t {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+into_owned_identity!((), u64);
+
+impl<T: IntoOwned> IntoOwned for Option<T> {
+    type Owned = Option<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.map(IntoOwned::into_owned)
+    }
+}
+
+macro_rules! into_owned_inherent {
+    ($($t:ident),*) => {
+        $(
+            impl<'a> IntoOwned for $t<'a> {
+                type Owned = $t<'static>;
+
+                fn into_owned(self) -> $t<'static> {
+                    $t::into_owned(self)
+                }
+            }
+        )*
+    };
+}
+
+into_owned_inherent!(
+    Creator,
+    NewgroundsSong,
+    LevelData,
+    Profile,
+    SearchedUser,
+    LevelComment,
+    CommentUser,
+    ProfileComment
+);
+
 /// Enum modelling the version of a Geometry Dash client
+///
+/// Versions are ordered chronologically, with [`GameVersion::Unknown`] preceding all known
+/// versions. This allows gating features on the version a level was uploaded in:
+///
+/// ```
+/// use dash_rs::model::GameVersion;
+///
+/// let platformer_introduced = GameVersion::new(2, 2);
+///
+/// assert!(GameVersion::from(22) >= platformer_introduced);
+/// assert!(GameVersion::from(21) < platformer_introduced);
+/// ```
+///
+/// ## GD Internals:
+/// Versions are encoded as a single integer. Starting with update 1.7, this is simply
+/// `major * 10 + minor` (so `22` for update 2.2). Versions 1.0 to 1.6 are encoded as `minor + 1`
+/// (so `1` to `7`). The values `10` to `16` (as well as `0`) do not match this scheme. Some of them
+/// are found on very old levels, but do not correspond to any known version.
+///
+/// Consequently, only versions `1.0` to `25.5` with a single digit minor version can be encoded,
+/// see [`GameVersion::try_new`].
 #[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(into = "u8", from = "u8")]
 pub enum GameVersion {
-    /// Variant representing an unknown version. This variant is only used for
-    /// levels that were uploaded before the game started tracking the
-    /// version. This variant's string
-    /// representation is `"10"`
-    Unknown,
+    /// Variant representing an unknown version, holding the raw encoded value. This variant is
+    /// only used for levels that were uploaded before the game started tracking the version.
+    Unknown(u8),
 
     /// Variant representing a the version represented by the given minor/major
     /// values in the form `major.minor`
-    Version { minor: u8, major: u8 },
+    Version { major: u8, minor: u8 },
+}
+
+/// Error returned when constructing or parsing a [`GameVersion`] fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Error)]
+pub enum GameVersionError {
+    /// The string was neither of the form `"22"` nor of the form `"2.2"`
+    #[error("malformed game version")]
+    Malformed,
+
+    /// The version cannot be encoded into a single integer, see [`GameVersion::try_new`]
+    #[error("game version {major}.{minor} cannot be encoded")]
+    Unrepresentable { major: u8, minor: u8 },
+}
+
+impl GameVersion {
+    /// Constructs the [`GameVersion`] `major.minor`
+    ///
+    /// ## Panics
+    /// Panics if the version cannot be encoded, see [`GameVersion::try_new`]
+    pub const fn new(major: u8, minor: u8) -> Self {
+        match GameVersion::try_new(major, minor) {
+            Ok(version) => version,
+            Err(_) => panic!("game version cannot be encoded"),
+        }
+    }
+
+    /// Constructs the [`GameVersion`] `major.minor`, or returns an error if it cannot be encoded.
+    ///
+    /// A version can be encoded if its minor version is a single digit, and its major version is
+    /// between `1` and `25` (with `25.5` being the highest version whose encoding fits into a
+    /// `u8`).
+    pub const fn try_new(major: u8, minor: u8) -> Result<Self, GameVersionError> {
+        match (major, minor) {
+            (1..=24, 0..=9) | (25, 0..=5) => Ok(GameVersion::Version { major, minor }),
+            _ => Err(GameVersionError::Unrepresentable { major, minor }),
+        }
+    }
 }
 
 impl From<u8> for GameVersion {
     fn from(version: u8) -> Self {
-        if version == 10 {
-            GameVersion::Unknown
-        } else {
-            GameVersion::Version {
-                major: (version / 10),
-                minor: (version % 10),
-            }
+        match version {
+            1..=7 => GameVersion::Version {
+                major: 1,
+                minor: version - 1,
+            },
+            17.. => GameVersion::Version {
+                major: version / 10,
+                minor: version % 10,
+            },
+            _ => GameVersion::Unknown(version),
         }
     }
 }
@@ -54,20 +200,110 @@ impl From<u8> for GameVersion {
 impl From<GameVersion> for u8 {
     fn from(version: GameVersion) -> Self {
         match version {
-            GameVersion::Unknown => 10,
-            GameVersion::Version { minor, major } => major * 10 + minor,
+            GameVersion::Unknown(version) => version,
+            GameVersion::Version { major: 1, minor } if minor <= 6 => minor + 1,
+            // Only reachable via the variant's public fields, see `GameVersion::try_new`
+            GameVersion::Version { major, minor } => major.saturating_mul(10).saturating_add(minor),
         }
     }
 }
 
 impl Display for GameVersion {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         match self {
-            GameVersion::Unknown => write!(f, "Pre 1.6"),
-            GameVersion::Version { minor: 7, major: 0 } => write!(f, "1.6"),
-            GameVersion::Version { minor, major } => write!(f, "{}.{}", major, minor),
+            GameVersion::Unknown(_) => write!(f, "Unknown"),
+            GameVersion::Version { major, minor } => write!(f, "{}.{}", major, minor),
+        }
+    }
+}
+
+impl FromStr for GameVersion {
+    type Err = GameVersionError;
+
+    /// Parses either the encoded form of a version (such as `"22"`), or its `major.minor` form
+    /// (such as `"2.2"`).
+    ///
+    /// Patch versions (such as `"2.206"`) are accepted, but discarded, as the game does not encode
+    /// them.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('.') {
+            None => s.parse::<u8>().map(GameVersion::from).map_err(|_| GameVersionError::Malformed),
+            Some((major, minor)) => {
+                if minor.is_empty() || !minor.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(GameVersionError::Malformed);
+                }
+
+                let major = major.parse().map_err(|_| GameVersionError::Malformed)?;
+
+                GameVersion::try_new(major, minor.as_bytes()[0] - b'0')
+            },
         }
     }
 }
 
 crate::into_conversion!(GameVersion, u8);
+
+#[cfg(test)]
+mod tests {
+    use super::{GameVersion, GameVersionError};
+    use alloc::string::ToString;
+
+    #[test]
+    fn game_version_encoding_round_trips() {
+        for encoded in 0..=u8::MAX {
+            assert_eq!(u8::from(GameVersion::from(encoded)), encoded);
+        }
+
+        assert_eq!(GameVersion::from(7), GameVersion::new(1, 6));
+        assert_eq!(GameVersion::from(10), GameVersion::Unknown(10));
+        assert_eq!(GameVersion::from(22).to_string(), "2.2");
+
+        for major in 1..=25 {
+            for minor in 0..=9 {
+                if let Ok(version) = GameVersion::try_new(major, minor) {
+                    assert_eq!(GameVersion::from(u8::from(version)), version);
+                }
+            }
+        }
+
+        assert_eq!(u8::from(GameVersion::new(1, 7)), 17);
+        assert_eq!(GameVersion::from(17), GameVersion::new(1, 7));
+        assert_eq!(u8::from(GameVersion::new(25, 5)), 255);
+    }
+
+    #[test]
+    fn unrepresentable_game_versions() {
+        assert_eq!(
+            GameVersion::try_new(25, 6),
+            Err(GameVersionError::Unrepresentable { major: 25, minor: 6 })
+        );
+        assert!(GameVersion::try_new(26, 0).is_err());
+        assert!(GameVersion::try_new(2, 10).is_err());
+        assert!(GameVersion::try_new(0, 5).is_err());
+        assert_eq!(u8::from(GameVersion::Version { major: 26, minor: 0 }), u8::MAX);
+    }
+
+    #[test]
+    fn game_version_ordering() {
+        assert!(GameVersion::Unknown(11) < GameVersion::new(1, 0));
+        assert!(GameVersion::new(1, 9) < GameVersion::new(2, 0));
+        assert!(GameVersion::new(2, 1) < GameVersion::new(2, 2));
+        assert!(GameVersion::new(3, 8) > GameVersion::new(2, 2));
+    }
+
+    #[test]
+    fn parse_game_version() {
+        assert_eq!("22".parse(), Ok(GameVersion::new(2, 2)));
+        assert_eq!("2.2".parse(), Ok(GameVersion::new(2, 2)));
+        assert_eq!("2.206".parse(), Ok(GameVersion::new(2, 2)));
+        assert_eq!("10".parse(), Ok(GameVersion::Unknown(10)));
+        assert_eq!("2.".parse::<GameVersion>(), Err(GameVersionError::Malformed));
+        assert_eq!("2.x".parse::<GameVersion>(), Err(GameVersionError::Malformed));
+        assert_eq!("two".parse::<GameVersion>(), Err(GameVersionError::Malformed));
+        assert_eq!(
+            "26.0".parse::<GameVersion>(),
+            Err(GameVersionError::Unrepresentable { major: 26, minor: 0 })
+        );
+        assert!("300.0".parse::<GameVersion>().is_err());
+    }
+}
@@ -0,0 +1,182 @@
+//! Module containing types modelling the reward chests and quests of a player, as returned by the
+//! `getGJRewards` and `getGJChallenges` endpoints
+
+use alloc::{string::String, vec::Vec};
+use core::{ops::Add, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+/// The time remaining until something (such as a chest) becomes available, as reported by the
+/// servers
+///
+/// Since the servers only report the remaining time at the time of the request, this does not
+/// represent a point in time on its own. Use [`Cooldown::deadline`] with the time the response was
+/// received to turn it into one, using whatever clock the caller schedules on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Cooldown {
+    /// The remaining time in seconds
+    pub seconds: u32,
+}
+
+impl Cooldown {
+    pub const fn new(seconds: u32) -> Self {
+        Cooldown { seconds }
+    }
+
+    /// Whether the cooldown has already elapsed at the time of the request
+    pub const fn is_ready(&self) -> bool {
+        self.seconds == 0
+    }
+
+    /// The remaining time as a [`Duration`]
+    pub const fn duration(&self) -> Duration {
+        Duration::from_secs(self.seconds as u64)
+    }
+
+    /// The point in time at which this cooldown elapses, given the time `fetched_at` the response
+    /// it is contained in was received.
+    ///
+    /// Works with any clock whose time points can be offset by a [`Duration`], such as
+    /// `std::time::Instant`, `std::time::SystemTime`, `chrono::DateTime` and
+    /// `time::OffsetDateTime`.
+    pub fn deadline<T: Add<Duration, Output = T>>(&self, fetched_at: T) -> T {
+        fetched_at + self.duration()
+    }
+}
+
+impl From<Duration> for Cooldown {
+    fn from(duration: Duration) -> Self {
+        Cooldown::new(duration.as_secs().min(u32::MAX as u64) as u32)
+    }
+}
+
+/// The contents of a reward chest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChestContent {
+    pub orbs: u32,
+    pub diamonds: u32,
+
+    /// The IDs of the items (shards or keys) contained in the chest, `0` if the slot is empty
+    pub items: [u32; 2],
+}
+
+/// One of the two reward chests a player can open periodically
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Chest {
+    /// The time until this chest can be opened again
+    pub cooldown: Cooldown,
+
+    /// The contents of this chest
+    ///
+    /// ## GD Internals:
+    /// The servers determine the contents of a chest at the time it is opened, meaning this only
+    /// holds meaningful values in the response to the request opening the chest.
+    pub content: ChestContent,
+
+    /// The amount of times this chest has been opened
+    pub times_opened: u32,
+}
+
+/// The state of both reward chests of a player, as returned by the `getGJRewards` endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Rewards {
+    pub user_id: u64,
+    pub account_id: u64,
+
+    /// The chest that is available every 4 hours
+    pub small_chest: Chest,
+
+    /// The chest that is available every 24 hours
+    pub large_chest: Chest,
+
+    /// Which chest was opened by the request, if any
+    ///
+    /// ## GD Internals:
+    /// `0` means no chest was opened, `1` the small chest and `2` the large chest
+    pub reward_type: u8,
+}
+
+impl Rewards {
+    /// The cooldown of whichever chest becomes available first
+    pub fn next_chest(&self) -> Cooldown {
+        self.small_chest.cooldown.min(self.large_chest.cooldown)
+    }
+}
+
+/// The objective of a [`Quest`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum QuestKind {
+    /// Collect the given amount of mana orbs
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `1`
+    Orbs,
+
+    /// Collect the given amount of user coins
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `2`
+    Coins,
+
+    /// Collect the given amount of stars
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `3`
+    Stars,
+
+    /// Unknown or invalid value. This variant will be constructed if robtop ever adds more quest
+    /// kinds and will hold the raw value
+    Unknown(u8),
+}
+
+impl From<u8> for QuestKind {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => QuestKind::Orbs,
+            2 => QuestKind::Coins,
+            3 => QuestKind::Stars,
+            value => QuestKind::Unknown(value),
+        }
+    }
+}
+
+impl From<QuestKind> for u8 {
+    fn from(kind: QuestKind) -> Self {
+        match kind {
+            QuestKind::Orbs => 1,
+            QuestKind::Coins => 2,
+            QuestKind::Stars => 3,
+            QuestKind::Unknown(value) => value,
+        }
+    }
+}
+
+/// A single quest
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Quest {
+    pub quest_id: u64,
+    pub kind: QuestKind,
+
+    /// The amount of orbs/coins/stars that need to be collected to complete this quest
+    pub amount: u32,
+
+    /// The amount of diamonds rewarded for completing this quest
+    pub reward: u32,
+    pub name: String,
+}
+
+/// The quests currently available to a player, as returned by the `getGJChallenges` endpoint
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Quests {
+    pub user_id: u64,
+    pub account_id: u64,
+
+    /// The time until a new quest becomes available
+    pub next_quest: Cooldown,
+
+    /// The currently available quests
+    ///
+    /// ## GD Internals:
+    /// The servers always return three quests, however some GDPS implementations return fewer.
+    pub quests: Vec<Quest>,
+}
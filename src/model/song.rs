@@ -1,9 +1,16 @@
-use crate::serde::{GJFormat, PercentDecoder, ProcessError, Thunk};
-use dash_rs_derive::Dash;
-use serde::{Deserialize, Serialize};
-use std::{
-    borrow::Cow,
+use crate::serde::{GJFormat, IntoOwnedThunkProcessor, ProcessError, Thunk, ThunkProcessor, UnknownIndices, ROBTOP_SET};
+use alloc::borrow::Cow;
+use core::{
+    convert::TryFrom,
     fmt::{Display, Formatter},
+    hash::{Hash, Hasher},
+};
+use dash_rs_derive::Dash;
+use percent_encoding::{percent_decode_str, utf8_percent_encode};
+use serde::{
+    de::{self, IgnoredAny, MapAccess, Unexpected, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Deserializer, Serialize, Serializer,
 };
 use variant_partial_eq::VariantPartialEq;
 
@@ -46,9 +53,18 @@ pub struct NewgroundsSong<'a> {
     pub index_8: Cow<'a, str>,
 
     /// The direct `audio.ngfiles.com` download link for this [`NewgroundsSong`]
+    ///
+    /// ## GD Internals:
+    /// The link is percent-encoded, see [`SongLink`]
     #[serde(borrow)]
     #[dash(index = 10)]
-    pub link: Thunk<'a, PercentDecoder>,
+    pub link: Thunk<'a, SongLink<'static>>,
+
+    /// Indices not known to dash-rs. Only populated if
+    /// parsed via [`GJFormat::from_gj_str_preserving`](crate::GJFormat::from_gj_str_preserving)
+    #[dash(unknown_indices)]
+    #[serde(default, skip_serializing_if = "UnknownIndices::is_empty")]
+    pub unknown_indices: UnknownIndices<'a>,
 }
 
 impl<'de> GJFormat<'de> for NewgroundsSong<'de> {
@@ -57,8 +73,8 @@ impl<'de> GJFormat<'de> for NewgroundsSong<'de> {
 }
 
 impl<'a> NewgroundsSong<'a> {
-    pub fn into_owned(self) -> Result<NewgroundsSong<'static>, ProcessError> {
-        Ok(NewgroundsSong {
+    pub fn into_owned(self) -> NewgroundsSong<'static> {
+        NewgroundsSong {
             song_id: self.song_id,
             name: Cow::Owned(self.name.into_owned()),
             index_3: self.index_3,
@@ -67,30 +83,198 @@ impl<'a> NewgroundsSong<'a> {
             index_6: self.index_6.map(|cow| Cow::Owned(cow.into_owned())),
             index_7: self.index_7.map(|cow| Cow::Owned(cow.into_owned())),
             index_8: Cow::Owned(self.index_8.into_owned()),
-            link: Thunk::Processed(Cow::Owned(self.link.into_processed()?.into_owned())),
+            link: self.link.into_owned(),
+            unknown_indices: self.unknown_indices.into_owned(),
+        }
+    }
+}
+
+/// The (percent-decoded) download link of a [`NewgroundsSong`]
+///
+/// Constructing a `SongLink` validates that it is an absolute `http` or `https` URL with a
+/// well-formed host. It does not check that the host actually belongs to Newgrounds, since private
+/// servers (and songs from the 2.2 music library) are hosted elsewhere, see
+/// [`SongLink::is_newgrounds`].
+///
+/// ## GD Internals:
+/// Song links are percent-encoded in responses (e.g. `https%3A%2F%2Faudio.ngfiles.com%2F...`). A
+/// `SongLink` obtained by processing a [`Thunk`] remembers the exact encoding it was decoded from,
+/// and reproduces it when serialized back into RobTop's format. Links constructed via
+/// [`SongLink::new`] are encoded like the official servers do.
+#[derive(Debug, Clone)]
+pub struct SongLink<'a> {
+    url: Cow<'a, str>,
+    encoded: Option<Cow<'a, str>>,
+}
+
+impl<'a> SongLink<'a> {
+    /// Constructs a new `SongLink` from the given (not percent-encoded) URL, returning
+    /// [`ProcessError::InvalidUrl`] if it is malformed
+    pub fn new(url: impl Into<Cow<'a, str>>) -> Result<Self, ProcessError> {
+        let url = url.into();
+
+        split_url(&url)?;
+
+        Ok(SongLink { url, encoded: None })
+    }
+
+    /// The full URL
+    pub fn as_str(&self) -> &str {
+        &self.url
+    }
+
+    /// The URL's scheme, either `http` or `https`
+    pub fn scheme(&self) -> &str {
+        self.parts().0
+    }
+
+    /// The URL's host, including the port if one is specified
+    pub fn host(&self) -> &str {
+        self.parts().1
+    }
+
+    /// Everything following the host, meaning the path, query and fragment of the URL. Empty if
+    /// none of these are present.
+    pub fn path(&self) -> &str {
+        self.parts().2
+    }
+
+    /// Whether this link points to Newgrounds' servers
+    pub fn is_newgrounds(&self) -> bool {
+        let host = self.host();
+
+        host == "ngfiles.com" || host.ends_with(".ngfiles.com") || host == "newgrounds.com" || host.ends_with(".newgrounds.com")
+    }
+
+    pub fn into_owned(self) -> SongLink<'static> {
+        SongLink {
+            url: Cow::Owned(self.url.into_owned()),
+            encoded: self.encoded.map(|encoded| Cow::Owned(encoded.into_owned())),
+        }
+    }
+
+    fn parts(&self) -> (&str, &str, &str) {
+        // Validated on construction
+        split_url(&self.url).unwrap_or_default()
+    }
+}
+
+/// Splits the given URL into scheme, host and remainder, validating the former two
+fn split_url(url: &str) -> Result<(&str, &str, &str), ProcessError> {
+    let (scheme, rest) = url.split_once("://").ok_or(ProcessError::InvalidUrl("missing scheme"))?;
+
+    if !scheme.eq_ignore_ascii_case("http") && !scheme.eq_ignore_ascii_case("https") {
+        return Err(ProcessError::InvalidUrl("scheme is neither http nor https"));
+    }
+
+    let host_end = rest.find(|c| matches!(c, '/' | '?' | '#')).unwrap_or(rest.len());
+    let (host, path) = rest.split_at(host_end);
+    let (hostname, port) = match host.rsplit_once(':') {
+        Some((hostname, port)) => (hostname, Some(port)),
+        None => (host, None),
+    };
+
+    if hostname.is_empty() || hostname.starts_with('.') || hostname.ends_with('.') || hostname.contains("..") {
+        return Err(ProcessError::InvalidUrl("empty or malformed host"));
+    }
+
+    if !hostname.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.') {
+        return Err(ProcessError::InvalidUrl("host contains invalid characters"));
+    }
+
+    if let Some(port) = port {
+        port.parse::<u16>().map_err(|_| ProcessError::InvalidUrl("invalid port"))?;
+    }
+
+    Ok((scheme, host, path))
+}
+
+impl Display for SongLink<'_> {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        f.write_str(&self.url)
+    }
+}
+
+// Equality and hashing only consider the URL itself, not how it was encoded
+impl<'b> PartialEq<SongLink<'b>> for SongLink<'_> {
+    fn eq(&self, other: &SongLink<'b>) -> bool {
+        self.url == other.url
+    }
+}
+
+impl Eq for SongLink<'_> {}
+
+impl Hash for SongLink<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.url.hash(state)
+    }
+}
+
+impl Serialize for SongLink<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.url)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for SongLink<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        SongLink::new(Cow::<'de, str>::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+impl ThunkProcessor for SongLink<'static> {
+    type Error = ProcessError;
+    type Output<'a> = SongLink<'a>;
+
+    fn from_unprocessed(unprocessed: Cow<str>) -> Result<Self::Output<'_>, Self::Error> {
+        let url = match unprocessed {
+            Cow::Borrowed(unprocessed) => percent_decode_str(unprocessed).decode_utf8()?,
+            Cow::Owned(ref unprocessed) => Cow::Owned(percent_decode_str(unprocessed).decode_utf8()?.into_owned()),
+        };
+
+        split_url(&url)?;
+
+        Ok(SongLink {
+            url,
+            encoded: Some(unprocessed),
         })
     }
+
+    fn as_unprocessed<'b>(processed: &'b Self::Output<'_>) -> Result<Cow<'b, str>, Self::Error> {
+        match processed.encoded {
+            Some(ref encoded) => Ok(Cow::Borrowed(encoded)),
+            None => Ok(utf8_percent_encode(&processed.url, ROBTOP_SET).into()),
+        }
+    }
+
+    fn downcast_output_lifetime<'b: 'c, 'c, 's>(output: &'s Self::Output<'b>) -> &'s Self::Output<'c> {
+        output
+    }
+}
+
+impl IntoOwnedThunkProcessor for SongLink<'static> {
+    fn output_into_owned(output: Self::Output<'_>) -> Self::Output<'static> {
+        output.into_owned()
+    }
 }
 
 /// Struct representing Geometry Dash's main songs.
 ///
 /// This data is not provided by the API and needs to be manually kept up to
 /// date
-#[derive(Debug, Eq, PartialEq, Clone, Copy, Serialize, Deserialize)]
-#[serde(from = "u8")]
-#[serde(into = "u8")]
+///
+/// In human readable formats (such as JSON), a [`MainSong`] is serialized as a struct including its
+/// name and artist. Otherwise, only its ID is serialized. Deserialization accepts both forms, but
+/// always looks up name and artist by ID.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
 pub struct MainSong {
     /// The ID of this [`MainSong`]
     pub main_song_id: u8,
 
     /// The name of this [`MainSong`]
-    // even though we (de)serialize using From and Into, we have to mark these as skip so that the 'de lifetime isn't constrained by
-    // 'static
-    #[serde(skip)]
     pub name: &'static str,
 
     /// The artist of this [`MainSong`]
-    #[serde(skip)]
     pub artist: &'static str,
 }
 
@@ -141,11 +325,85 @@ pub const UNKNOWN: MainSong = MainSong::new(
 );
 
 impl Display for NewgroundsSong<'_> {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "NewgroundsSong({}, {} by {})", self.song_id, self.name, self.artist)
     }
 }
 
+impl MainSong {
+    /// Looks up the [`MainSong`] with the given ID, returning [`None`] if no such song is known
+    pub fn by_id(main_song_id: u8) -> Option<MainSong> {
+        MAIN_SONGS.get(main_song_id as usize).copied()
+    }
+
+    /// Whether this [`MainSong`] is a known main song, as opposed to [`UNKNOWN`]
+    pub fn is_known(&self) -> bool {
+        MainSong::by_id(self.main_song_id).is_some()
+    }
+}
+
+impl Serialize for MainSong {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let mut state = serializer.serialize_struct("MainSong", 3)?;
+            state.serialize_field("main_song_id", &self.main_song_id)?;
+            state.serialize_field("name", self.name)?;
+            state.serialize_field("artist", self.artist)?;
+            state.end()
+        } else {
+            serializer.serialize_u8(self.main_song_id)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for MainSong {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MainSongVisitor;
+
+        impl<'de> Visitor<'de> for MainSongVisitor {
+            type Value = MainSong;
+
+            fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+                formatter.write_str("a main song ID or a main song struct")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<MainSong, E> {
+                u8::try_from(v)
+                    .map(MainSong::from)
+                    .map_err(|_| E::invalid_value(Unexpected::Unsigned(v), &self))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<MainSong, E> {
+                u8::try_from(v)
+                    .map(MainSong::from)
+                    .map_err(|_| E::invalid_value(Unexpected::Signed(v), &self))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<MainSong, A::Error> {
+                let mut main_song_id = None;
+
+                while let Some(key) = map.next_key::<Cow<str>>()? {
+                    if key == "main_song_id" {
+                        main_song_id = Some(map.next_value::<u8>()?);
+                    } else {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                }
+
+                main_song_id
+                    .map(MainSong::from)
+                    .ok_or_else(|| de::Error::missing_field("main_song_id"))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(MainSongVisitor)
+        } else {
+            deserializer.deserialize_u8(MainSongVisitor)
+        }
+    }
+}
+
 impl From<u8> for MainSong {
     fn from(song_id: u8) -> Self {
         *MAIN_SONGS.get(song_id as usize).unwrap_or(&UNKNOWN)
@@ -157,3 +415,34 @@ impl From<MainSong> for u8 {
         song.main_song_id
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{MainSong, MAIN_SONGS, UNKNOWN};
+
+    #[test]
+    fn main_song_by_id() {
+        let stereo_madness = MainSong::by_id(0).unwrap();
+
+        assert_eq!(stereo_madness.name, "Stereo Madness");
+        assert!(stereo_madness.is_known());
+
+        let last = MainSong::by_id(21).unwrap();
+
+        assert_eq!(last.main_song_id, 21);
+        assert_eq!(last, MAIN_SONGS[MAIN_SONGS.len() - 1]);
+        assert!(last.is_known());
+
+        assert_eq!(MainSong::by_id(22), None);
+        assert_eq!(MainSong::by_id(0xFF), None);
+        assert!(!MainSong::from(22).is_known());
+        assert!(!UNKNOWN.is_known());
+    }
+
+    #[test]
+    fn main_song_ids_match_positions() {
+        for (position, song) in MAIN_SONGS.iter().enumerate() {
+            assert_eq!(song.main_song_id as usize, position);
+        }
+    }
+}
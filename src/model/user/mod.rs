@@ -1,8 +1,78 @@
+use core::{
+    fmt::{Display, Formatter},
+    num::NonZeroU32,
+};
 use serde::{Deserialize, Serialize};
 
 pub mod profile;
 pub mod searched;
 
+/// A user's position on the global (stars) leaderboard, starting at `1` for the top player
+///
+/// Fields holding a rank are of type `Option<Rank>`, and are [`None`] for users that do not appear
+/// on the leaderboard, either because they have not collected any stars yet, or because they were
+/// banned from it.
+///
+/// ## GD Internals:
+/// Unranked users have a rank of `0` (some private servers use `-1` instead)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Rank(NonZeroU32);
+
+impl Rank {
+    /// Constructs the rank with the given position on the leaderboard. Returns [`None`] for `0`,
+    /// which denotes unranked users.
+    pub const fn new(position: u32) -> Option<Rank> {
+        match NonZeroU32::new(position) {
+            Some(position) => Some(Rank(position)),
+            None => None,
+        }
+    }
+
+    /// This rank's position on the leaderboard
+    pub const fn get(self) -> u32 {
+        self.0.get()
+    }
+}
+
+impl Display for Rank {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+impl From<Rank> for u32 {
+    fn from(rank: Rank) -> Self {
+        rank.get()
+    }
+}
+
+impl From<Rank> for i64 {
+    fn from(rank: Rank) -> Self {
+        rank.get().into()
+    }
+}
+
+impl crate::serde::InternalProxy for Rank {
+    // Signed, since private servers might send `-1` for unranked users
+    type DeserializeProxy = i64;
+    type SerializeProxy<'a>
+        = u32
+    where
+        Self: 'a;
+
+    fn to_serialize_proxy(&self) -> u32 {
+        self.get()
+    }
+
+    fn from_deserialize_proxy(from: i64) -> Self {
+        // Fields of this type map non-positive values to `None` before this is called
+        let position = from.clamp(1, u32::MAX.into()) as u32;
+
+        Rank::new(position).expect("position was clamped to be non-zero")
+    }
+}
+
 /// Enum representing the different types of moderator a user can be
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize)]
 pub enum ModLevel {
@@ -46,7 +116,7 @@ impl From<u8> for ModLevel {
 crate::into_conversion!(ModLevel, u8);
 
 /// The type of icon displayed next a user's comment of next to their search result
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum IconType {
     Cube,
     Ship,
@@ -55,6 +125,8 @@ pub enum IconType {
     Wave,
     Robot,
     Spider,
+    Swing,
+    Jetpack,
     Unknown(u8),
 }
 
@@ -68,6 +140,8 @@ impl From<u8> for IconType {
             4 => IconType::Wave,
             5 => IconType::Robot,
             6 => IconType::Spider,
+            7 => IconType::Swing,
+            8 => IconType::Jetpack,
             i => IconType::Unknown(i),
         }
     }
@@ -83,12 +157,14 @@ impl From<IconType> for u8 {
             IconType::Wave => 4,
             IconType::Robot => 5,
             IconType::Spider => 6,
+            IconType::Swing => 7,
+            IconType::Jetpack => 8,
             IconType::Unknown(idx) => idx,
         }
     }
 }
 
-// Enum representing an in-game icon color
+/// Enum representing an in-game icon color
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize)]
 pub enum Color {
     /// A color whose index was known to dash-rs which could be converted to RGB values
@@ -99,6 +175,22 @@ pub enum Color {
     Unknown(u8),
 }
 
+impl Color {
+    /// The RGB values of this color, or [`None`] if its index is not known to dash-rs
+    pub fn rgb(self) -> Option<(u8, u8, u8)> {
+        match self {
+            Color::Known(r, g, b) => Some((r, g, b)),
+            Color::Unknown(_) => None,
+        }
+    }
+
+    /// The RGB values of this color packed into a single integer of the form `0xRRGGBB`, or
+    /// [`None`] if its index is not known to dash-rs
+    pub fn rgb_u32(self) -> Option<u32> {
+        self.rgb().map(|(r, g, b)| ((r as u32) << 16) | ((g as u32) << 8) | b as u32)
+    }
+}
+
 impl From<u8> for Color {
     fn from(idx: u8) -> Self {
         // This match expression is listing the colors in order of the in-game selection menu!
@@ -1,13 +1,12 @@
 use crate::{
-    model::user::{Color, ModLevel},
+    model::user::{Color, ModLevel, Rank},
+    serde::{IntoOwnedThunkProcessor, ProcessError, Thunk, ThunkProcessor, UnknownIndices},
     GJFormat,
 };
+use alloc::{borrow::Cow, format, vec::Vec};
+use core::fmt::{Display, Formatter};
 use dash_rs_derive::Dash;
 use serde::{Deserialize, Serialize};
-use std::{
-    borrow::Cow,
-    fmt::{Display, Formatter},
-};
 use variant_partial_eq::VariantPartialEq;
 
 crate::dash_rs_newtype!(Youtube);
@@ -15,23 +14,350 @@ crate::dash_rs_newtype!(Twitch);
 crate::dash_rs_newtype!(Twitter);
 
 impl Display for Youtube<'_> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "https://www.youtube.com/channel/{}", self.0)
     }
 }
 
 impl Display for Twitch<'_> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "https://www.twitch.tv/{}", self.0)
     }
 }
 
 impl Display for Twitter<'_> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "https://www.twitter.com/{}", self.0)
     }
 }
 
+/// Enum describing who can send friend requests to a [`Profile`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FriendRequestPrivacy {
+    /// Everyone can send friend requests
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `0`
+    Everyone,
+
+    /// Nobody can send friend requests
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `1`
+    Nobody,
+
+    /// Enum variant that's used by the [`From<u8>`](From) impl for when an unrecognized value is
+    /// passed
+    Unknown(u8),
+}
+
+impl From<u8> for FriendRequestPrivacy {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => FriendRequestPrivacy::Everyone,
+            1 => FriendRequestPrivacy::Nobody,
+            _ => FriendRequestPrivacy::Unknown(value),
+        }
+    }
+}
+
+impl From<FriendRequestPrivacy> for u8 {
+    fn from(privacy: FriendRequestPrivacy) -> Self {
+        match privacy {
+            FriendRequestPrivacy::Everyone => 0,
+            FriendRequestPrivacy::Nobody => 1,
+            FriendRequestPrivacy::Unknown(value) => value,
+        }
+    }
+}
+
+crate::into_conversion!(FriendRequestPrivacy, u8);
+
+/// Enum describing who can send messages to a [`Profile`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MessagePrivacy {
+    /// Everyone can send messages
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `0`
+    Everyone,
+
+    /// Only friends can send messages
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `1`
+    FriendsOnly,
+
+    /// Nobody can send messages
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `2`
+    Nobody,
+
+    /// Enum variant that's used by the [`From<u8>`](From) impl for when an unrecognized value is
+    /// passed
+    Unknown(u8),
+}
+
+impl From<u8> for MessagePrivacy {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => MessagePrivacy::Everyone,
+            1 => MessagePrivacy::FriendsOnly,
+            2 => MessagePrivacy::Nobody,
+            _ => MessagePrivacy::Unknown(value),
+        }
+    }
+}
+
+impl From<MessagePrivacy> for u8 {
+    fn from(privacy: MessagePrivacy) -> Self {
+        match privacy {
+            MessagePrivacy::Everyone => 0,
+            MessagePrivacy::FriendsOnly => 1,
+            MessagePrivacy::Nobody => 2,
+            MessagePrivacy::Unknown(value) => value,
+        }
+    }
+}
+
+crate::into_conversion!(MessagePrivacy, u8);
+
+/// Enum describing who can view the comment history of a [`Profile`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CommentHistoryPrivacy {
+    /// Everyone can view the comment history
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `0`
+    Everyone,
+
+    /// Only friends can view the comment history
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `1`
+    FriendsOnly,
+
+    /// Nobody can view the comment history
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `2`
+    Nobody,
+
+    /// Enum variant that's used by the [`From<u8>`](From) impl for when an unrecognized value is
+    /// passed
+    Unknown(u8),
+}
+
+impl From<u8> for CommentHistoryPrivacy {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => CommentHistoryPrivacy::Everyone,
+            1 => CommentHistoryPrivacy::FriendsOnly,
+            2 => CommentHistoryPrivacy::Nobody,
+            _ => CommentHistoryPrivacy::Unknown(value),
+        }
+    }
+}
+
+impl From<CommentHistoryPrivacy> for u8 {
+    fn from(privacy: CommentHistoryPrivacy) -> Self {
+        match privacy {
+            CommentHistoryPrivacy::Everyone => 0,
+            CommentHistoryPrivacy::FriendsOnly => 1,
+            CommentHistoryPrivacy::Nobody => 2,
+            CommentHistoryPrivacy::Unknown(value) => value,
+        }
+    }
+}
+
+crate::into_conversion!(CommentHistoryPrivacy, u8);
+
+/// The amount of demons of each difficulty a [`Profile`] has beaten
+///
+/// ## GD Internals:
+/// Provided as a comma separated list of the classic easy, medium, hard, insane and extreme demon
+/// counts, followed by the same for platformer demons, and the amount of weekly and gauntlet
+/// demons.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct DemonStats {
+    pub easy: u16,
+    pub medium: u16,
+    pub hard: u16,
+    pub insane: u16,
+    pub extreme: u16,
+    pub platformer_easy: u16,
+    pub platformer_medium: u16,
+    pub platformer_hard: u16,
+    pub platformer_insane: u16,
+    pub platformer_extreme: u16,
+    pub weekly: u16,
+    pub gauntlet: u16,
+}
+
+impl DemonStats {
+    /// The amount of classic (non-platformer) demons beaten, excluding weekly and gauntlet demons
+    pub fn classic(&self) -> u32 {
+        [self.easy, self.medium, self.hard, self.insane, self.extreme]
+            .iter()
+            .map(|&count| count as u32)
+            .sum()
+    }
+
+    /// The amount of platformer demons beaten
+    pub fn platformer(&self) -> u32 {
+        [
+            self.platformer_easy,
+            self.platformer_medium,
+            self.platformer_hard,
+            self.platformer_insane,
+            self.platformer_extreme,
+        ]
+        .iter()
+        .map(|&count| count as u32)
+        .sum()
+    }
+}
+
+/// The amount of non-demon levels of each difficulty a [`Profile`] has completed
+///
+/// ## GD Internals:
+/// Provided as a comma separated list of the auto, easy, normal, hard, harder and insane level
+/// counts, followed by the amount of daily levels (classic only) and gauntlet levels.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct CompletionStats {
+    pub auto: u16,
+    pub easy: u16,
+    pub normal: u16,
+    pub hard: u16,
+    pub harder: u16,
+    pub insane: u16,
+
+    /// The amount of daily levels completed. Always [`None`] for platformer levels, as these have
+    /// no dailies.
+    pub daily: Option<u16>,
+    pub gauntlet: u16,
+}
+
+fn parse_counts(unprocessed: &str) -> Result<Vec<u16>, ProcessError> {
+    unprocessed
+        .split(',')
+        .map(|count| count.parse().map_err(ProcessError::from))
+        .collect()
+}
+
+fn join_counts(counts: &[u16]) -> Cow<'static, str> {
+    let counts: Vec<_> = counts.iter().map(|count| format!("{}", count)).collect();
+
+    Cow::Owned(counts.join(","))
+}
+
+impl ThunkProcessor for DemonStats {
+    type Error = ProcessError;
+    type Output<'a> = DemonStats;
+
+    fn from_unprocessed(unprocessed: Cow<str>) -> Result<Self::Output<'_>, Self::Error> {
+        match parse_counts(&unprocessed)?[..] {
+            [easy, medium, hard, insane, extreme, platformer_easy, platformer_medium, platformer_hard, platformer_insane, platformer_extreme, weekly, gauntlet] => {
+                Ok(DemonStats {
+                    easy,
+                    medium,
+                    hard,
+                    insane,
+                    extreme,
+                    platformer_easy,
+                    platformer_medium,
+                    platformer_hard,
+                    platformer_insane,
+                    platformer_extreme,
+                    weekly,
+                    gauntlet,
+                })
+            },
+            _ => Err(ProcessError::IncorrectLength { expected: 12 }),
+        }
+    }
+
+    fn as_unprocessed<'b>(processed: &'b Self::Output<'_>) -> Result<Cow<'b, str>, Self::Error> {
+        Ok(join_counts(&[
+            processed.easy,
+            processed.medium,
+            processed.hard,
+            processed.insane,
+            processed.extreme,
+            processed.platformer_easy,
+            processed.platformer_medium,
+            processed.platformer_hard,
+            processed.platformer_insane,
+            processed.platformer_extreme,
+            processed.weekly,
+            processed.gauntlet,
+        ]))
+    }
+
+    fn downcast_output_lifetime<'b: 'c, 'c, 's>(output: &'s Self::Output<'b>) -> &'s Self::Output<'c> {
+        output
+    }
+}
+
+impl IntoOwnedThunkProcessor for DemonStats {
+    fn output_into_owned(output: Self::Output<'_>) -> Self::Output<'static> {
+        output
+    }
+}
+
+impl ThunkProcessor for CompletionStats {
+    type Error = ProcessError;
+    type Output<'a> = CompletionStats;
+
+    fn from_unprocessed(unprocessed: Cow<str>) -> Result<Self::Output<'_>, Self::Error> {
+        let (auto, easy, normal, hard, harder, insane, daily, gauntlet) = match parse_counts(&unprocessed)?[..] {
+            [auto, easy, normal, hard, harder, insane, daily, gauntlet] => {
+                (auto, easy, normal, hard, harder, insane, Some(daily), gauntlet)
+            },
+            [auto, easy, normal, hard, harder, insane, gauntlet] => (auto, easy, normal, hard, harder, insane, None, gauntlet),
+            _ => return Err(ProcessError::IncorrectLength { expected: 8 }),
+        };
+
+        Ok(CompletionStats {
+            auto,
+            easy,
+            normal,
+            hard,
+            harder,
+            insane,
+            daily,
+            gauntlet,
+        })
+    }
+
+    fn as_unprocessed<'b>(processed: &'b Self::Output<'_>) -> Result<Cow<'b, str>, Self::Error> {
+        let mut counts = Vec::from([
+            processed.auto,
+            processed.easy,
+            processed.normal,
+            processed.hard,
+            processed.harder,
+            processed.insane,
+        ]);
+
+        counts.extend(processed.daily);
+        counts.push(processed.gauntlet);
+
+        Ok(join_counts(&counts))
+    }
+
+    fn downcast_output_lifetime<'b: 'c, 'c, 's>(output: &'s Self::Output<'b>) -> &'s Self::Output<'c> {
+        output
+    }
+}
+
+impl IntoOwnedThunkProcessor for CompletionStats {
+    fn output_into_owned(output: Self::Output<'_>) -> Self::Output<'static> {
+        output
+    }
+}
+
 /// Struct representing a Geometry Dash User's profile, as seen after clicking their name in the
 /// official client
 ///
@@ -96,9 +422,9 @@ pub struct Profile<'a> {
     #[dash(index = 18)]
     pub index_18: Cow<'a, str>,
 
-    // TODO: figure this value out
+    /// Who can send friend requests to this [`Profile`]
     #[dash(index = 19)]
-    pub index_19: Cow<'a, str>,
+    pub friend_requests: FriendRequestPrivacy,
 
     /// The link to the [`Profile`]'s [YouTube](https://youtube.com) channel, if provided
     ///
@@ -145,12 +471,11 @@ pub struct Profile<'a> {
     #[dash(index = 29)]
     pub index_29: Cow<'a, str>,
 
-    /// This [`Profile`]'s global rank. [`None`] if he is banned or not ranked.
-    ///
-    /// ## GD Internals:
-    /// For unranked/banned users it's `0`. TODO: Why is this an option?
+    /// This [`Profile`]'s global rank. [`None`] if they are banned from the leaderboard or not
+    /// ranked, see [`Rank`].
     #[dash(index = 30)]
-    pub global_rank: Option<u32>,
+    #[dash(with = "crate::util::non_positive_to_none")]
+    pub global_rank: Option<Rank>,
 
     // TODO: figure this value out
     #[dash(index = 31)]
@@ -206,9 +531,9 @@ pub struct Profile<'a> {
     #[dash(index = 49)]
     pub mod_level: ModLevel,
 
-    // TODO: figure this value out
+    /// Who can view this [`Profile`]'s comment history
     #[dash(index = 50)]
-    pub index_50: Cow<'a, str>,
+    pub comment_history: CommentHistoryPrivacy,
 
     #[dash(index = 51)]
     pub index_51: Cow<'a, str>,
@@ -226,9 +551,265 @@ pub struct Profile<'a> {
     /// at the top left corner and then goes left-to-right and top-to-bottom
     #[dash(index = 54)]
     pub jetpack_index: u8,
+
+    /// The amount of demons of each difficulty this [`Profile`] has beaten. Only provided by 2.2
+    /// servers
+    #[dash(index = 55)]
+    #[dash(default)]
+    #[dash(skip_serializing_if = "Option::is_none")]
+    #[variant_compare = "crate::util::option_variant_eq"]
+    pub demon_stats: Option<Thunk<'a, DemonStats>>,
+
+    /// The amount of non-demon classic levels of each difficulty this [`Profile`] has completed.
+    /// Only provided by 2.2 servers
+    #[dash(index = 56)]
+    #[dash(default)]
+    #[dash(skip_serializing_if = "Option::is_none")]
+    #[variant_compare = "crate::util::option_variant_eq"]
+    pub classic_completions: Option<Thunk<'a, CompletionStats>>,
+
+    /// The amount of non-demon platformer levels of each difficulty this [`Profile`] has completed.
+    /// Only provided by 2.2 servers
+    #[dash(index = 57)]
+    #[dash(default)]
+    #[dash(skip_serializing_if = "Option::is_none")]
+    #[variant_compare = "crate::util::option_variant_eq"]
+    pub platformer_completions: Option<Thunk<'a, CompletionStats>>,
+
+    /// Indices not known to dash-rs. Only populated if
+    /// parsed via [`GJFormat::from_gj_str_preserving`](crate::GJFormat::from_gj_str_preserving)
+    #[dash(unknown_indices)]
+    #[serde(default, skip_serializing_if = "UnknownIndices::is_empty")]
+    pub unknown_indices: UnknownIndices<'a>,
 }
 
 impl<'de> GJFormat<'de> for Profile<'de> {
     const DELIMITER: &'static str = ":";
     const MAP_LIKE: bool = true;
 }
+
+impl<'a> Profile<'a> {
+    pub fn into_owned(self) -> Profile<'static> {
+        Profile {
+            name: Cow::Owned(self.name.into_owned()),
+            user_id: self.user_id,
+            stars: self.stars,
+            demons: self.demons,
+            creator_points: self.creator_points,
+            primary_color: self.primary_color,
+            secondary_color: self.secondary_color,
+            secret_coins: self.secret_coins,
+            account_id: self.account_id,
+            user_coins: self.user_coins,
+            index_18: Cow::Owned(self.index_18.into_owned()),
+            friend_requests: self.friend_requests,
+            youtube_url: self.youtube_url.map(Youtube::into_owned),
+            cube_index: self.cube_index,
+            ship_index: self.ship_index,
+            ball_index: self.ball_index,
+            ufo_index: self.ufo_index,
+            wave_index: self.wave_index,
+            robot_index: self.robot_index,
+            has_glow: self.has_glow,
+            index_29: Cow::Owned(self.index_29.into_owned()),
+            global_rank: self.global_rank,
+            index_31: Cow::Owned(self.index_31.into_owned()),
+            index_38: self.index_38.map(|cow| Cow::Owned(cow.into_owned())),
+            index_39: self.index_39.map(|cow| Cow::Owned(cow.into_owned())),
+            index_40: self.index_40.map(|cow| Cow::Owned(cow.into_owned())),
+            spider_index: self.spider_index,
+            twitter_url: self.twitter_url.map(Twitter::into_owned),
+            twitch_url: self.twitch_url.map(Twitch::into_owned),
+            diamonds: self.diamonds,
+            death_effect_index: self.death_effect_index,
+            mod_level: self.mod_level,
+            comment_history: self.comment_history,
+            index_51: Cow::Owned(self.index_51.into_owned()),
+            moons: self.moons,
+            swing_index: self.swing_index,
+            jetpack_index: self.jetpack_index,
+            demon_stats: self.demon_stats.map(Thunk::into_owned),
+            classic_completions: self.classic_completions.map(Thunk::into_owned),
+            platformer_completions: self.platformer_completions.map(Thunk::into_owned),
+            unknown_indices: self.unknown_indices.into_owned(),
+        }
+    }
+
+    /// Whether this [`Profile`] has been awarded any creator points
+    pub fn is_creator(&self) -> bool {
+        self.creator_points > 0
+    }
+
+    /// Whether this [`Profile`] appears to be banned from the leaderboard, meaning it has collected
+    /// stars, but is still unranked.
+    ///
+    /// This is a heuristic, as the servers do not distinguish between unranked and banned users.
+    pub fn is_leaderboard_banned(&self) -> bool {
+        self.stars > 0 && self.global_rank.is_none()
+    }
+}
+
+macro_rules! profile_setter {
+    ($(#[$attr:meta])* $name: ident: $t: ty) => {
+        $(#[$attr])*
+        pub fn $name(mut self, $name: $t) -> Self {
+            self.profile.$name = $name;
+            self
+        }
+    };
+}
+
+/// Builder for [`Profile`]s, constructed via [`Profile::builder`]
+///
+/// Values not explicitly set default to those of a freshly registered account using the default
+/// icons and colors.
+#[derive(Debug, Clone)]
+pub struct ProfileBuilder<'a> {
+    profile: Profile<'a>,
+}
+
+impl<'a> Profile<'a> {
+    /// Constructs a [`ProfileBuilder`] for the account with the given name, user ID and account ID
+    pub fn builder(name: impl Into<Cow<'a, str>>, user_id: u64, account_id: u64) -> ProfileBuilder<'a> {
+        ProfileBuilder::new(name, user_id, account_id)
+    }
+}
+
+impl<'a> ProfileBuilder<'a> {
+    /// Constructs a [`ProfileBuilder`] for the account with the given name, user ID and account ID
+    pub fn new(name: impl Into<Cow<'a, str>>, user_id: u64, account_id: u64) -> Self {
+        ProfileBuilder {
+            profile: Profile {
+                name: name.into(),
+                user_id,
+                stars: 0,
+                demons: 0,
+                creator_points: 0,
+                primary_color: Color::from(0),
+                secondary_color: Color::from(3),
+                secret_coins: 0,
+                account_id,
+                user_coins: 0,
+                index_18: Cow::Borrowed("0"),
+                friend_requests: FriendRequestPrivacy::Everyone,
+                youtube_url: None,
+                cube_index: 1,
+                ship_index: 1,
+                ball_index: 1,
+                ufo_index: 1,
+                wave_index: 1,
+                robot_index: 1,
+                has_glow: false,
+                index_29: Cow::Borrowed("1"),
+                global_rank: None,
+                index_31: Cow::Borrowed("0"),
+                index_38: None,
+                index_39: None,
+                index_40: None,
+                spider_index: 1,
+                twitter_url: None,
+                twitch_url: None,
+                diamonds: 0,
+                death_effect_index: 1,
+                mod_level: ModLevel::None,
+                comment_history: CommentHistoryPrivacy::Everyone,
+                index_51: Cow::Borrowed("0"),
+                moons: 0,
+                swing_index: 1,
+                jetpack_index: 1,
+                demon_stats: None,
+                classic_completions: None,
+                platformer_completions: None,
+                unknown_indices: UnknownIndices::new(),
+            },
+        }
+    }
+
+    profile_setter!(stars: u32);
+
+    profile_setter!(moons: u32);
+
+    profile_setter!(demons: u16);
+
+    profile_setter!(diamonds: u16);
+
+    profile_setter!(secret_coins: u8);
+
+    profile_setter!(user_coins: u16);
+
+    profile_setter!(creator_points: u16);
+
+    profile_setter!(primary_color: Color);
+
+    profile_setter!(secondary_color: Color);
+
+    profile_setter!(has_glow: bool);
+
+    profile_setter!(cube_index: u16);
+
+    profile_setter!(ship_index: u8);
+
+    profile_setter!(ball_index: u8);
+
+    profile_setter!(ufo_index: u8);
+
+    profile_setter!(wave_index: u8);
+
+    profile_setter!(robot_index: u8);
+
+    profile_setter!(spider_index: u8);
+
+    profile_setter!(swing_index: u8);
+
+    profile_setter!(jetpack_index: u8);
+
+    profile_setter!(death_effect_index: u8);
+
+    profile_setter!(mod_level: ModLevel);
+
+    profile_setter!(friend_requests: FriendRequestPrivacy);
+
+    profile_setter!(comment_history: CommentHistoryPrivacy);
+
+    /// Sets the profile's demon breakdown. See [`DemonStats`]
+    pub fn demon_stats(mut self, demon_stats: DemonStats) -> Self {
+        self.profile.demon_stats = Some(Thunk::Processed(demon_stats));
+        self
+    }
+
+    /// Sets the profile's classic and platformer level completion counts. See [`CompletionStats`]
+    pub fn completions(mut self, classic: CompletionStats, platformer: CompletionStats) -> Self {
+        self.profile.classic_completions = Some(Thunk::Processed(classic));
+        self.profile.platformer_completions = Some(Thunk::Processed(platformer));
+        self
+    }
+
+    /// Sets the global rank of the profile. Unranked profiles have no rank, which can be set by
+    /// passing `0`.
+    pub fn global_rank(mut self, rank: u32) -> Self {
+        self.profile.global_rank = Rank::new(rank);
+        self
+    }
+
+    /// Sets the profile's YouTube channel, given as only the channel ID (not a full URL)
+    pub fn youtube(mut self, channel: impl Into<Cow<'a, str>>) -> Self {
+        self.profile.youtube_url = Some(Youtube(channel.into()));
+        self
+    }
+
+    /// Sets the profile's Twitter account, given as only the username (not a full URL)
+    pub fn twitter(mut self, username: impl Into<Cow<'a, str>>) -> Self {
+        self.profile.twitter_url = Some(Twitter(username.into()));
+        self
+    }
+
+    /// Sets the profile's Twitch channel, given as only the username (not a full URL)
+    pub fn twitch(mut self, username: impl Into<Cow<'a, str>>) -> Self {
+        self.profile.twitch_url = Some(Twitch(username.into()));
+        self
+    }
+
+    pub fn build(self) -> Profile<'a> {
+        self.profile
+    }
+}
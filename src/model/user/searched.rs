@@ -2,9 +2,9 @@ use crate::{
     model::user::{Color, IconType},
     GJFormat,
 };
+use alloc::borrow::Cow;
 use dash_rs_derive::Dash;
 use serde::{Deserialize, Serialize};
-use std::borrow::Cow;
 use variant_partial_eq::VariantPartialEq;
 
 /// Struct modelling the partial user data returned by the `getGJUsers` endpoint.
@@ -90,3 +90,30 @@ impl<'de> GJFormat<'de> for SearchedUser<'de> {
     const DELIMITER: &'static str = ":";
     const MAP_LIKE: bool = true;
 }
+
+impl<'a> SearchedUser<'a> {
+    pub fn into_owned(self) -> SearchedUser<'static> {
+        SearchedUser {
+            name: Cow::Owned(self.name.into_owned()),
+            user_id: self.user_id,
+            stars: self.stars,
+            demons: self.demons,
+            index_6: self.index_6.map(|cow| Cow::Owned(cow.into_owned())),
+            creator_points: self.creator_points,
+            icon_index: self.icon_index,
+            primary_color: self.primary_color,
+            secondary_color: self.secondary_color,
+            secret_coins: self.secret_coins,
+            icon_type: self.icon_type,
+            has_glow: self.has_glow,
+            account_id: self.account_id,
+            user_coins: self.user_coins,
+            moons: self.moons,
+        }
+    }
+
+    /// Whether this [`SearchedUser`] has been awarded any creator points
+    pub fn is_creator(&self) -> bool {
+        self.creator_points > 0
+    }
+}
@@ -2,13 +2,17 @@
 
 use crate::{
     model::level::Level,
-    request::{endpoint_base_url, BaseRequest, GD_22},
+    request::{endpoint_url, AuthenticatedUser, BaseRequest, GD_22},
 };
+use alloc::{format, string::String};
+use core::fmt::Display;
 use serde::Serialize;
-use std::fmt::Display;
 
 pub const LEVEL_COMMENTS_ENDPOINT: &str = "getGJComments21.php";
 pub const PROFILE_COMMENT_ENDPOINT: &str = "getGJAccountComments20.php";
+pub const DELETE_PROFILE_COMMENT_ENDPOINT: &str = "deleteGJAccComment20.php";
+pub const DELETE_LEVEL_COMMENT_ENDPOINT: &str = "deleteGJComment20.php";
+pub const COMMENT_HISTORY_ENDPOINT: &str = "getGJCommentHistory.php";
 
 /// The different orderings that can be requested for level comments
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
@@ -85,7 +89,7 @@ impl<'a> LevelCommentsRequest<'a> {
     const_setter!(page: u32);
 
     pub fn to_url(&self) -> String {
-        format!("{}{}", endpoint_base_url(), LEVEL_COMMENTS_ENDPOINT)
+        endpoint_url(LEVEL_COMMENTS_ENDPOINT)
     }
 
     pub const fn new(level: u64) -> Self {
@@ -114,9 +118,11 @@ impl<'a> LevelCommentsRequest<'a> {
     }
 }
 
+request!(LevelCommentsRequest, LEVEL_COMMENTS_ENDPOINT, Common);
+
 impl Display for LevelCommentsRequest<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", super::to_string(self))
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self).map_err(|_| core::fmt::Error)?)
     }
 }
 
@@ -132,6 +138,101 @@ impl<S, U> From<Level<'_, S, U>> for LevelCommentsRequest<'_> {
     }
 }
 
+/// Struct modelled after a request to `getGJCommentHistory.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to retrieve all level comments made by a
+/// specific user. Each returned comment has its [`level_id`](crate::model::comment::level::LevelComment::level_id)
+/// set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Hash)]
+pub struct CommentHistoryRequest<'a> {
+    /// The base request data
+    pub base: BaseRequest<'a>,
+
+    /// Unknown, probably related to pagination
+    ///
+    /// ## GD Internals:
+    /// This field is called `total` in the boomlings API
+    pub total: u32,
+
+    /// The page of comments to retrieve. The first page is page `0`
+    ///
+    /// ## GD Internals:
+    /// This field is called `page` in the boomlings API
+    pub page: u32,
+
+    /// What to sort by comments by
+    ///
+    /// ## GD Internals:
+    /// This field is called `mode` in the boomlings API.
+    #[serde(rename = "mode")]
+    pub sort_mode: SortMode,
+
+    /// The **user ID** (_not_ account ID) of the user whose comments to retrieve
+    ///
+    /// ## GD Internals:
+    /// This field is called `userID` in the boomlings API
+    #[serde(rename = "userID")]
+    pub user_id: u64,
+
+    /// The amount of comments to retrieve.
+    ///
+    /// ## GD Internals:
+    /// This field is called `count` in the boomlings API
+    #[serde(rename = "count")]
+    pub limit: u32,
+}
+
+impl<'a> CommentHistoryRequest<'a> {
+    const_setter!(total: u32);
+
+    const_setter!(limit: u32);
+
+    const_setter!(page: u32);
+
+    pub fn to_url(&self) -> String {
+        endpoint_url(COMMENT_HISTORY_ENDPOINT)
+    }
+
+    pub const fn new(user_id: u64) -> Self {
+        Self::with_base(GD_22, user_id)
+    }
+
+    pub const fn with_base(base: BaseRequest<'a>, user_id: u64) -> Self {
+        CommentHistoryRequest {
+            base,
+            total: 0,
+            page: 0,
+            sort_mode: SortMode::Recent,
+            user_id,
+            limit: 10,
+        }
+    }
+
+    pub const fn most_liked(mut self) -> Self {
+        self.sort_mode = SortMode::Liked;
+        self
+    }
+
+    pub const fn most_recent(mut self) -> Self {
+        self.sort_mode = SortMode::Recent;
+        self
+    }
+}
+
+request!(CommentHistoryRequest, COMMENT_HISTORY_ENDPOINT, Common);
+
+impl Display for CommentHistoryRequest<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self).map_err(|_| core::fmt::Error)?)
+    }
+}
+
+impl From<u64> for CommentHistoryRequest<'_> {
+    fn from(user_id: u64) -> Self {
+        CommentHistoryRequest::new(user_id)
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash)]
 pub struct ProfileCommentsRequest<'a> {
     /// The base request data
@@ -165,7 +266,7 @@ impl<'a> ProfileCommentsRequest<'a> {
     const_setter!(account_id: u64);
 
     pub fn to_url(&self) -> String {
-        format!("{}{}", endpoint_base_url(), PROFILE_COMMENT_ENDPOINT)
+        endpoint_url(PROFILE_COMMENT_ENDPOINT)
     }
 
     pub const fn new(account: u64) -> Self {
@@ -182,15 +283,157 @@ impl<'a> ProfileCommentsRequest<'a> {
     }
 }
 
+request!(ProfileCommentsRequest, PROFILE_COMMENT_ENDPOINT, Common);
+
 impl Display for ProfileCommentsRequest<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", super::to_string(self))
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self).map_err(|_| core::fmt::Error)?)
+    }
+}
+
+/// Struct modelled after a request to `deleteGJAccComment20.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to delete a comment from the profile of the
+/// authenticated account. Use [`parse_delete_gj_acc_comment_response`](crate::response::parse_delete_gj_acc_comment_response)
+/// to interpret the server's response.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash)]
+pub struct DeleteProfileCommentRequest<'a> {
+    /// The base request data
+    pub base: BaseRequest<'a>,
+
+    /// The account whose comment should be deleted
+    pub authenticated_user: AuthenticatedUser<'a>,
+
+    /// The id of the comment to delete
+    ///
+    /// ## GD Internals:
+    /// This field is called `commentID` in the boomlings API
+    #[serde(rename = "commentID")]
+    pub comment_id: u64,
+}
+
+impl<'a> DeleteProfileCommentRequest<'a> {
+    const_setter!(comment_id: u64);
+
+    pub fn to_url(&self) -> String {
+        endpoint_url(DELETE_PROFILE_COMMENT_ENDPOINT)
+    }
+
+    pub const fn new(authenticated_user: AuthenticatedUser<'a>, comment_id: u64) -> Self {
+        Self::with_base(GD_22, authenticated_user, comment_id)
+    }
+
+    pub const fn with_base(base: BaseRequest<'a>, authenticated_user: AuthenticatedUser<'a>, comment_id: u64) -> Self {
+        DeleteProfileCommentRequest {
+            base,
+            authenticated_user,
+            comment_id,
+        }
+    }
+
+    /// Constructs one request per given comment id, to delete multiple profile comments in
+    /// sequence.
+    ///
+    /// The boomlings API does not support deleting multiple comments with a single request. With
+    /// the `blocking` feature, `blocking::Client::delete_profile_comments` sends such a batch and
+    /// collects the outcome of each deletion.
+    pub fn batch<I>(self, comment_ids: I) -> impl Iterator<Item = DeleteProfileCommentRequest<'a>>
+    where
+        I: IntoIterator<Item = u64>,
+    {
+        comment_ids.into_iter().map(move |comment_id| self.comment_id(comment_id))
+    }
+}
+
+request!(DeleteProfileCommentRequest, DELETE_PROFILE_COMMENT_ENDPOINT, Common);
+
+impl Display for DeleteProfileCommentRequest<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self).map_err(|_| core::fmt::Error)?)
+    }
+}
+
+/// Alias for [`DeleteProfileCommentRequest`], named after the endpoint. Profile comments are called
+/// "account comments" in the boomlings API.
+pub type DeleteAccountCommentRequest<'a> = DeleteProfileCommentRequest<'a>;
+
+/// Struct modelled after a request to `deleteGJComment20.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to delete a comment posted by the authenticated
+/// account, or any comment on a level uploaded by the authenticated account. Use
+/// [`parse_delete_gj_comment_response`](crate::response::parse_delete_gj_comment_response) to
+/// interpret the server's response.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash)]
+pub struct DeleteLevelCommentRequest<'a> {
+    /// The base request data
+    pub base: BaseRequest<'a>,
+
+    /// The account deleting the comment
+    pub authenticated_user: AuthenticatedUser<'a>,
+
+    /// The id of the comment to delete
+    ///
+    /// ## GD Internals:
+    /// This field is called `commentID` in the boomlings API
+    #[serde(rename = "commentID")]
+    pub comment_id: u64,
+
+    /// The ID of the level the comment was posted on
+    ///
+    /// ## GD Internals:
+    /// This field is called `levelID` in the boomlings API
+    #[serde(rename = "levelID")]
+    pub level_id: u64,
+}
+
+impl<'a> DeleteLevelCommentRequest<'a> {
+    const_setter!(comment_id: u64);
+
+    pub fn to_url(&self) -> String {
+        endpoint_url(DELETE_LEVEL_COMMENT_ENDPOINT)
+    }
+
+    pub const fn new(authenticated_user: AuthenticatedUser<'a>, level_id: u64, comment_id: u64) -> Self {
+        Self::with_base(GD_22, authenticated_user, level_id, comment_id)
+    }
+
+    pub const fn with_base(base: BaseRequest<'a>, authenticated_user: AuthenticatedUser<'a>, level_id: u64, comment_id: u64) -> Self {
+        DeleteLevelCommentRequest {
+            base,
+            authenticated_user,
+            comment_id,
+            level_id,
+        }
+    }
+
+    /// Constructs one request per given comment id, to delete multiple comments on the same level
+    /// in sequence.
+    ///
+    /// The boomlings API does not support deleting multiple comments with a single request.
+    pub fn batch<I>(self, comment_ids: I) -> impl Iterator<Item = DeleteLevelCommentRequest<'a>>
+    where
+        I: IntoIterator<Item = u64>,
+    {
+        comment_ids.into_iter().map(move |comment_id| self.comment_id(comment_id))
+    }
+}
+
+request!(DeleteLevelCommentRequest, DELETE_LEVEL_COMMENT_ENDPOINT, Common);
+
+impl Display for DeleteLevelCommentRequest<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self).map_err(|_| core::fmt::Error)?)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::request::comment::{LevelCommentsRequest, ProfileCommentsRequest};
+    use crate::request::{
+        comment::{
+            CommentHistoryRequest, DeleteLevelCommentRequest, DeleteProfileCommentRequest, LevelCommentsRequest, ProfileCommentsRequest,
+        },
+        AuthenticatedUser,
+    };
 
     #[test]
     fn serialize_level_comments() {
@@ -202,7 +445,7 @@ mod tests {
         let request = LevelCommentsRequest::new(1234).most_liked().page(2).limit(15);
 
         assert_eq!(
-            super::super::to_string(request),
+            super::super::to_string(request).unwrap(),
             "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&total=0&page=2&mode=1&levelID=1234&count=15"
         );
     }
@@ -217,8 +460,48 @@ mod tests {
         let request = ProfileCommentsRequest::new(1710032).page(2);
 
         assert_eq!(
-            super::super::to_string(request),
+            super::super::to_string(request).unwrap(),
             "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&total=0&page=2&accountID=1710032"
         );
     }
+
+    #[test]
+    fn serialize_comment_history() {
+        let request = CommentHistoryRequest::new(4170784).most_liked().page(2);
+
+        assert_eq!(
+            super::super::to_string(request).unwrap(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&total=0&page=2&mode=1&userID=4170784&count=10"
+        );
+    }
+
+    #[test]
+    fn serialize_delete_profile_comment() {
+        let request = DeleteProfileCommentRequest::new(AuthenticatedUser::new(1710032, "hash"), 12345);
+
+        assert_eq!(
+            super::super::to_string(request).unwrap(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&accountID=1710032&gjp2=hash&commentID=12345"
+        );
+    }
+
+    #[test]
+    fn serialize_delete_level_comment() {
+        let request = DeleteLevelCommentRequest::new(AuthenticatedUser::new(1710032, "hash"), 10565740, 12345);
+
+        assert_eq!(
+            super::super::to_string(request).unwrap(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&accountID=1710032&gjp2=hash&commentID=12345&levelID=10565740"
+        );
+    }
+
+    #[test]
+    fn batch_delete_profile_comments() {
+        let requests = DeleteProfileCommentRequest::new(AuthenticatedUser::new(1710032, "hash"), 0)
+            .batch(vec![1, 2, 3])
+            .map(|request| request.comment_id)
+            .collect::<Vec<_>>();
+
+        assert_eq!(requests, vec![1, 2, 3]);
+    }
 }
@@ -1,16 +1,26 @@
 use crate::{
+    crypto::base64_encode,
     model::{
         level::{DemonRating, LevelLength, LevelRating},
         song::MainSong,
     },
-    request::{endpoint_base_url, BaseRequest, GD_22},
+    request::{endpoint_url, AuthenticatedUser, BaseRequest, Secrets, GD_22},
 };
+use alloc::{
+    borrow::Cow,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::Display;
 use serde::{Deserialize, Serialize, Serializer};
-use std::borrow::Cow;
-use std::fmt::Display;
+use thiserror::Error;
 
 pub const DOWNLOAD_LEVEL_ENDPOINT: &str = "downloadGJLevel22.php";
 pub const SEARCH_LEVEL_ENDPOINT: &str = "getGJLevels21.php";
+pub const DELETE_LEVEL_ENDPOINT: &str = "deleteGJLevelUser20.php";
+pub const UPDATE_DESCRIPTION_ENDPOINT: &str = "updateGJDesc20.php";
 
 /// Struct modelled after a request to `downloadGJLevel22.php`.
 ///
@@ -87,13 +97,15 @@ impl<'a> LevelRequest<'a> {
     }
 
     pub fn to_url(&self) -> String {
-        format!("{}{}", endpoint_base_url(), DOWNLOAD_LEVEL_ENDPOINT)
+        endpoint_url(DOWNLOAD_LEVEL_ENDPOINT)
     }
 }
 
+request!(LevelRequest, DOWNLOAD_LEVEL_ENDPOINT, Common);
+
 impl Display for LevelRequest<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", super::to_string(self))
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self).map_err(|_| core::fmt::Error)?)
     }
 }
 
@@ -102,47 +114,162 @@ impl Display for LevelRequest<'_> {
 ///
 /// We can abuse this to either exclude a set of levels from a search or limit our search to a given
 /// set of levels.
-#[derive(Debug, Clone, Hash, Serialize, Deserialize, Default)]
-pub struct CompletionFilter {
-    /// The list of level ids to filter
-    #[serde(rename = "completedLevels", default, skip_serializing_if = "Option::is_none")]
-    // TODO: we have to get this wrapped inside parenthesis somehow
-    ids: Option<Vec<u64>>,
+///
+/// ## GD Internals:
+/// The boomlings API represents this filter using three fields: The list of level IDs
+/// (`completedLevels`, wrapped in parenthesis), and the mutually exclusive flags `onlyCompleted`
+/// (only search the given levels) and `uncompleted` (exclude the given levels from the search).
+/// If no completion filtering is desired, both flags are set to `0` and `completedLevels` is
+/// omitted.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(from = "RawCompletionFilter", into = "RawCompletionFilter")]
+pub enum CompletionFilter {
+    /// Do not filter by completion
+    #[default]
+    None,
 
-    /// if `true`, only the levels matching the ids in [`ids`](CompletionFilter.ids) will
-    /// be searched, if `false`, the levels in [`ids`](CompletionFilter.ids) will
-    /// be excluded.
-    ///
-    /// Mutually exclusive with [`CompletionFilter::exclude_given`]
-    #[serde(rename = "onlyCompleted")]
-    only_search_given: bool,
+    /// Only search the levels with the given IDs
+    LimitSearch(Vec<u64>),
 
-    /// if `true`, the levels in [`ids`](CompletionFilter.ids) will be excluded, if `false`, only
-    /// the levels matching the ids in [`ids`](CompletionFilter.ids) will be searched.
-    ///
-    /// Mutually exclusive with [`CompletionFilter::only_search_given`]
-    #[serde(rename = "uncompleted")]
-    exclude_given: bool,
+    /// Exclude the levels with the given IDs from the search
+    Exclude(Vec<u64>),
 }
 
 impl CompletionFilter {
+    /// Constructs a [`CompletionFilter`] that doesn't filter by completion at all
+    pub const fn none() -> CompletionFilter {
+        CompletionFilter::None
+    }
+
     /// Constructs a [`CompletionFilter`] that'll restrict the search to the
     /// list of provided ids
     pub const fn limit_search(ids: Vec<u64>) -> CompletionFilter {
-        CompletionFilter {
-            ids: Some(ids),
-            only_search_given: true,
-            exclude_given: false,
-        }
+        CompletionFilter::LimitSearch(ids)
     }
 
     /// Constructs a [`CompletionFilter`] that'll exclude the list of given ids
     /// from the search
     pub const fn exclude(ids: Vec<u64>) -> CompletionFilter {
-        CompletionFilter {
-            ids: Some(ids),
-            only_search_given: false,
-            exclude_given: true,
+        CompletionFilter::Exclude(ids)
+    }
+}
+
+/// The representation of a [`CompletionFilter`] in the boomlings API
+#[derive(Serialize, Deserialize)]
+struct RawCompletionFilter {
+    #[serde(rename = "completedLevels", default, skip_serializing_if = "Option::is_none")]
+    ids: Option<Vec<u64>>,
+
+    #[serde(rename = "onlyCompleted")]
+    only_search_given: bool,
+
+    #[serde(rename = "uncompleted")]
+    exclude_given: bool,
+}
+
+impl From<RawCompletionFilter> for CompletionFilter {
+    fn from(raw: RawCompletionFilter) -> Self {
+        match (raw.ids, raw.only_search_given, raw.exclude_given) {
+            (ids, true, _) => CompletionFilter::LimitSearch(ids.unwrap_or_default()),
+            (ids, false, true) => CompletionFilter::Exclude(ids.unwrap_or_default()),
+            _ => CompletionFilter::None,
+        }
+    }
+}
+
+impl From<CompletionFilter> for RawCompletionFilter {
+    fn from(filter: CompletionFilter) -> Self {
+        match filter {
+            CompletionFilter::None => RawCompletionFilter {
+                ids: None,
+                only_search_given: false,
+                exclude_given: false,
+            },
+            CompletionFilter::LimitSearch(ids) => RawCompletionFilter {
+                ids: Some(ids),
+                only_search_given: true,
+                exclude_given: false,
+            },
+            CompletionFilter::Exclude(ids) => RawCompletionFilter {
+                ids: Some(ids),
+                only_search_given: false,
+                exclude_given: true,
+            },
+        }
+    }
+}
+
+/// The difficulties to include in the results of a [`LevelsRequest`]
+///
+/// In the Geometry Dash client, the "N/A", "Auto" and "Demon" buttons of the search screen cannot
+/// be combined with any other difficulty, while any selection of the five regular difficulties
+/// can be searched for at once.
+///
+/// ## GD Internals:
+/// The boomlings API represents this filter using two fields: `diff`, a comma separated list of
+/// integers (or a single dash (`-`) if filtering by difficulty isn't wanted), and `demonFilter`,
+/// which is only included when searching for a specific demon difficulty:
+///
+/// | Filter                             | Request data                 |
+/// |------------------------------------|------------------------------|
+/// | [`DifficultyFilter::Any`]          | `diff=-`                     |
+/// | [`DifficultyFilter::NotAvailable`] | `diff=-1`                    |
+/// | [`DifficultyFilter::Auto`]         | `diff=-3`                    |
+/// | [`DifficultyFilter::Ratings`]      | e.g. `diff=1,2`              |
+/// | [`DifficultyFilter::AnyDemon`]     | `diff=-2`                    |
+/// | [`DifficultyFilter::Demon`]        | e.g. `diff=-2&demonFilter=3` |
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Default)]
+#[serde(into = "RawDifficultyFilter")]
+pub enum DifficultyFilter {
+    /// Do not filter by difficulty
+    #[default]
+    Any,
+
+    /// Only search for levels without a difficulty rating
+    NotAvailable,
+
+    /// Only search for auto levels
+    Auto,
+
+    /// Only search for levels with one of the given ratings
+    ///
+    /// Meant to hold any combination of [`LevelRating::Easy`], [`LevelRating::Normal`],
+    /// [`LevelRating::Hard`], [`LevelRating::Harder`] and [`LevelRating::Insane`]. For the other
+    /// ratings, use the dedicated variants of this enum instead.
+    Ratings(Vec<LevelRating>),
+
+    /// Only search for demons, regardless of their difficulty
+    AnyDemon,
+
+    /// Only search for demons of the given difficulty
+    Demon(DemonRating),
+}
+
+/// The representation of a [`DifficultyFilter`] in the boomlings API
+#[derive(Serialize)]
+struct RawDifficultyFilter {
+    #[serde(rename = "diff")]
+    ratings: Vec<RatingFilter>,
+
+    #[serde(rename = "demonFilter")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    demon_rating: Option<DemonFilter>,
+}
+
+impl From<DifficultyFilter> for RawDifficultyFilter {
+    fn from(filter: DifficultyFilter) -> Self {
+        let (ratings, demon_rating) = match filter {
+            DifficultyFilter::Any => (Vec::new(), None),
+            DifficultyFilter::NotAvailable => (vec![LevelRating::NotAvailable], None),
+            DifficultyFilter::Auto => (vec![LevelRating::Auto], None),
+            DifficultyFilter::Ratings(ratings) => (ratings, None),
+            DifficultyFilter::AnyDemon => (vec![LevelRating::Demon(DemonRating::Unknown(0))], None),
+            DifficultyFilter::Demon(demon_rating) => (vec![LevelRating::Demon(demon_rating)], Some(DemonFilter(demon_rating))),
+        };
+
+        RawDifficultyFilter {
+            ratings: ratings.into_iter().map(RatingFilter).collect(),
+            demon_rating,
         }
     }
 }
@@ -228,6 +355,20 @@ pub struct SearchFilters {
 }
 
 impl SearchFilters {
+    /// Constructs a new `SearchFilters` instance with all filters turned off
+    pub const fn new() -> SearchFilters {
+        SearchFilters {
+            featured: false,
+            original: false,
+            two_player: false,
+            coins: false,
+            epic: false,
+            rated: false,
+            song: None,
+            completion: CompletionFilter::none(),
+        }
+    }
+
     /// Limit search results to star rated levels
     pub const fn rated(mut self) -> Self {
         self.rated = true;
@@ -271,7 +412,7 @@ impl SearchFilters {
     }
 
     /// Limit search results to levels with the given [`MainSong`]
-    pub fn main_song(mut self, main_song: MainSong) -> Self {
+    pub const fn main_song(mut self, main_song: MainSong) -> Self {
         self.song = Some(SongFilter {
             song_id: main_song.main_song_id as u64,
             is_custom: false,
@@ -280,19 +421,26 @@ impl SearchFilters {
     }
 
     /// Limit search results to levels that use a custom song matching the given id.
-    pub fn custom_song(mut self, song_id: u64) -> Self {
+    pub const fn custom_song(mut self, song_id: u64) -> Self {
         self.song = Some(SongFilter { song_id, is_custom: true });
         self
     }
+
+    /// Whether none of the filters are turned on
+    pub fn is_empty(&self) -> bool {
+        !(self.featured || self.original || self.two_player || self.coins || self.epic || self.rated)
+            && self.song.is_none()
+            && self.completion == CompletionFilter::None
+    }
 }
 
 /// Enum containing the various types of [`LevelsRequest`] possible
 ///
 /// ## GD Internals:
-/// + Unused values: `8`, `9`, `14`
+/// + Unused values: `8`, `9`, `14`, `18`, `19`, `20`, `24`, `26`
 /// + The values `15` and `17` are only used in Geometry Dash World and are the
 ///   same as `0` ([`LevelRequestType::Search`]) and `6` ([`LevelRequestType::Featured`]) respectively
-#[derive(Debug, Copy, Clone, PartialEq, Hash, Serialize, Deserialize, Default)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 #[serde(from = "i32", into = "i32")]
 pub enum LevelRequestType {
     /// A search request.
@@ -365,16 +513,19 @@ pub enum LevelRequestType {
     /// This variant is represented by the value `11` in requests
     Awarded,
 
-    /// Unknown how this works
+    /// Request to retrieve levels by the creators whose account IDs are given in
+    /// [`LevelsRequest::followed`], see [`LevelsRequest::followed_creators`]
     ///
     /// ## GD Internals:
     /// This variant is represented by the value `12` in requests
     Followed,
 
-    /// Unknown what this is
+    /// Request to retrieve levels by the friends of the authenticated account, see
+    /// [`LevelsRequest::friends`]
     ///
     /// ## GD Internals:
-    /// This variant is represented by the value `13` in requests
+    /// This variant is represented by the value `13` in requests, and requires the `accountID` and
+    /// `gjp2` fields to be set
     Friends,
 
     /// Request to retrieve the levels in the hall of fame
@@ -383,10 +534,76 @@ pub enum LevelRequestType {
     /// This variant is represented by the value `16` in requests.
     HallOfFame,
 
+    /// Request to retrieve the levels that were previously the daily level ("Daily Safe")
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `21` in requests.
+    DailyHistory,
+
+    /// Request to retrieve the levels that were previously the weekly demon ("Weekly Safe")
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `22` in requests.
+    WeeklyHistory,
+
+    /// Request to retrieve the levels that were previously the event level ("Event Safe")
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `23` in requests.
+    EventHistory,
+
+    /// Request to retrieve specific levels, e.g. the ones contained in a level list. The search
+    /// string is set to a comma separated list of level IDs, see [`LevelsRequest::levels`]
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `25` in requests.
+    LevelList,
+
+    /// Request to retrieve the levels moderators sent for a rating
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `27` in requests.
+    Sent,
+
     /// Unknown variant not yet mapped by dash-rs
     Unknown(i32),
 }
 
+impl LevelRequestType {
+    /// Whether the filters of a [`LevelsRequest`] (lengths, ratings and [`SearchFilters`]) are
+    /// applied to requests of this type.
+    ///
+    /// In the Geometry Dash client, these are the level lists with a filter button.
+    /// [`LevelRequestType::Unknown`] is assumed to support filtering.
+    pub const fn supports_filters(self) -> bool {
+        matches!(
+            self,
+            LevelRequestType::Search
+                | LevelRequestType::MostDownloaded
+                | LevelRequestType::MostLiked
+                | LevelRequestType::Trending
+                | LevelRequestType::Recent
+                | LevelRequestType::Magic
+                | LevelRequestType::Awarded
+                | LevelRequestType::Unknown(_)
+        )
+    }
+
+    /// Whether [`LevelsRequest::search_string`] is used by requests of this type.
+    ///
+    /// [`LevelRequestType::Unknown`] is assumed to use the search string.
+    pub const fn uses_search_string(self) -> bool {
+        matches!(
+            self,
+            LevelRequestType::Search
+                | LevelRequestType::User
+                | LevelRequestType::MapPack
+                | LevelRequestType::LevelList
+                | LevelRequestType::Unknown(_)
+        )
+    }
+}
+
 impl From<i32> for LevelRequestType {
     fn from(value: i32) -> Self {
         use LevelRequestType::*;
@@ -405,6 +622,11 @@ impl From<i32> for LevelRequestType {
             12 => Followed,
             13 => Friends,
             16 => HallOfFame,
+            21 => DailyHistory,
+            22 => WeeklyHistory,
+            23 => EventHistory,
+            25 => LevelList,
+            27 => Sent,
             _ => Unknown(value),
         }
     }
@@ -426,6 +648,11 @@ impl From<LevelRequestType> for i32 {
             LevelRequestType::Followed => 12,
             LevelRequestType::Friends => 13,
             LevelRequestType::HallOfFame => 16,
+            LevelRequestType::DailyHistory => 21,
+            LevelRequestType::WeeklyHistory => 22,
+            LevelRequestType::EventHistory => 23,
+            LevelRequestType::LevelList => 25,
+            LevelRequestType::Sent => 27,
             LevelRequestType::Unknown(value) => value,
         }
     }
@@ -444,6 +671,39 @@ fn is_false(b: &bool) -> bool {
     !*b
 }
 
+/// The first page of the "Featured" tab
+pub const FEATURED_PAGE_0: LevelsRequest<'static> = LevelsRequest::new().request_type(LevelRequestType::Featured);
+
+/// The first page of the "Most Downloaded" tab
+pub const MOST_DOWNLOADED_PAGE_0: LevelsRequest<'static> = LevelsRequest::new().request_type(LevelRequestType::MostDownloaded);
+
+/// The first page of the "Most Liked" tab
+pub const MOST_LIKED_PAGE_0: LevelsRequest<'static> = LevelsRequest::new().request_type(LevelRequestType::MostLiked);
+
+/// The first page of the "Trending" tab
+pub const TRENDING_PAGE_0: LevelsRequest<'static> = LevelsRequest::new().request_type(LevelRequestType::Trending);
+
+/// The first page of the "Recent" tab
+pub const RECENT_PAGE_0: LevelsRequest<'static> = LevelsRequest::new().request_type(LevelRequestType::Recent);
+
+/// The first page of the "Magic" tab
+pub const MAGIC_PAGE_0: LevelsRequest<'static> = LevelsRequest::new().request_type(LevelRequestType::Magic);
+
+/// The first page of the "Awarded" tab
+pub const AWARDED_PAGE_0: LevelsRequest<'static> = LevelsRequest::new().request_type(LevelRequestType::Awarded);
+
+/// The first page of the "Hall of Fame" tab
+pub const HALL_OF_FAME_PAGE_0: LevelsRequest<'static> = LevelsRequest::new().request_type(LevelRequestType::HallOfFame);
+
+/// The first page of the "Sent" tab
+pub const SENT_PAGE_0: LevelsRequest<'static> = LevelsRequest::new().request_type(LevelRequestType::Sent);
+
+/// The first page of the "Daily Safe"
+pub const DAILY_HISTORY_PAGE_0: LevelsRequest<'static> = LevelsRequest::new().request_type(LevelRequestType::DailyHistory);
+
+/// The first page of the "Weekly Safe"
+pub const WEEKLY_HISTORY_PAGE_0: LevelsRequest<'static> = LevelsRequest::new().request_type(LevelRequestType::WeeklyHistory);
+
 /// Struct modelled after a request to `getGJLevels21.php`
 ///
 /// In the Geometry Dash API, this endpoint is used to retrieve a list of
@@ -466,8 +726,8 @@ pub struct LevelsRequest<'a> {
 
     /// A search string to filter the levels by
     ///
-    /// This value is ignored unless [`LevelsRequest::request_type`] is set to
-    /// [`LevelRequestType::Search`] or [`LevelRequestType::User`]
+    /// This value is ignored unless [`LevelsRequest::request_type`] uses it (see
+    /// [`LevelRequestType::uses_search_string`])
     ///
     /// ## GD Internals:
     /// This field is called `str` in the boomlings API
@@ -476,8 +736,8 @@ pub struct LevelsRequest<'a> {
 
     /// A list of level lengths to filter by
     ///
-    /// This value is ignored unless [`LevelsRequest::request_type`] is set to
-    /// [`LevelRequestType::Search`]
+    /// This value is ignored unless [`LevelsRequest::request_type`] supports filtering (see
+    /// [`LevelRequestType::supports_filters`])
     ///
     /// ## GD Internals:
     /// This field is called `len` in the boomlings API and needs to be
@@ -486,37 +746,11 @@ pub struct LevelsRequest<'a> {
     #[serde(rename = "len")]
     lengths: Vec<LengthFilter>,
 
-    /// A list of level ratings to filter by.
-    ///
-    /// To filter by any demon, add [`LevelRating::Demon`] with any arbitrary [`DemonRating`] value.
-    ///
-    /// `ratings` and [`LevelsRequest::demon_rating`] are mutually exlusive.
-    ///
-    /// This value is ignored unless [`LevelsRequest::request_type`] is set to
-    /// [`LevelRequestType::Search`]
+    /// The difficulties to filter by, see [`DifficultyFilter`]
     ///
-    /// ## GD Internals:
-    /// This field is called `diff` in the boomlings API and needs to be
-    /// converted to a comma separated list of integers, or a single dash
-    /// (`-`) if filtering by level rating isn't wanted.
-    #[serde(rename = "diff")]
-    ratings: Vec<RatingFilter>,
-
-    /// Optionally, a single demon rating to filter by. To filter by any demon
-    /// rating, use [`LevelsRequest::ratings`]
-    ///
-    /// `demon_rating` and `ratings` are mutually exlusive.
-    ///
-    /// This value is ignored unless [`LevelsRequest::request_type`] is set to
-    /// [`LevelRequestType::Search`]
-    ///
-    /// ## GD Internals:
-    /// This field is called `demonFilter` in the boomlings API and needs to be
-    /// converted to an integer. If filtering by demon rating isn't wanted,
-    /// the value has to be omitted from the request.
-    #[serde(rename = "demonFilter")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    demon_rating: Option<DemonFilter>,
+    /// This value is ignored unless [`LevelsRequest::request_type`] supports filtering (see
+    /// [`LevelRequestType::supports_filters`])
+    pub difficulty: DifficultyFilter,
 
     /// The page of results to retrieve
     pub page: u32,
@@ -526,9 +760,27 @@ pub struct LevelsRequest<'a> {
 
     /// Search filters to apply.
     ///
-    /// This value is ignored unless [`LevelsRequest::request_type`] is set to
-    /// [`LevelRequestType::Search`]
+    /// This value is ignored unless [`LevelsRequest::request_type`] supports filtering (see
+    /// [`LevelRequestType::supports_filters`])
     pub search_filters: SearchFilters,
+
+    /// The account IDs of the creators whose levels to retrieve
+    ///
+    /// This value is ignored unless [`LevelsRequest::request_type`] is set to
+    /// [`LevelRequestType::Followed`]
+    ///
+    /// ## GD Internals:
+    /// This field is called `followed` in the boomlings API and needs to be converted to a comma
+    /// separated list of integers. It is omitted if empty.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub followed: Vec<u64>,
+
+    /// The account on whose behalf this request is made
+    ///
+    /// This value is only required if [`LevelsRequest::request_type`] is set to
+    /// [`LevelRequestType::Friends`], and omitted if not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authenticated_user: Option<AuthenticatedUser<'a>>,
 }
 
 impl<'a> LevelsRequest<'a> {
@@ -539,13 +791,26 @@ impl<'a> LevelsRequest<'a> {
     const_setter!(request_type: LevelRequestType);
 
     pub fn to_url(&self) -> String {
-        format!("{}{}", endpoint_base_url(), SEARCH_LEVEL_ENDPOINT)
+        endpoint_url(SEARCH_LEVEL_ENDPOINT)
     }
 
-    pub fn with_base(base: BaseRequest<'a>) -> Self {
+    /// Constructs a new `LevelsRequest` using a default [`BaseRequest`] and no filters
+    pub const fn new() -> LevelsRequest<'static> {
+        LevelsRequest::with_base(GD_22)
+    }
+
+    pub const fn with_base(base: BaseRequest<'a>) -> Self {
         LevelsRequest {
             base,
-            ..Default::default()
+            request_type: LevelRequestType::Search,
+            search_string: Cow::Borrowed(""),
+            lengths: Vec::new(),
+            difficulty: DifficultyFilter::Any,
+            page: 0,
+            total: 0,
+            search_filters: SearchFilters::new(),
+            followed: Vec::new(),
+            authenticated_user: None,
         }
     }
 
@@ -564,24 +829,38 @@ impl<'a> LevelsRequest<'a> {
         self
     }
 
+    /// Restricts the search results to platformer levels, like the "Plat." length filter of the
+    /// Geometry Dash client. Resets any level lengths added beforehand.
+    pub fn platformer(mut self) -> Self {
+        self.lengths = vec![LengthFilter(LevelLength::Platformer)];
+        self
+    }
+
+    /// Sets the [`DifficultyFilter`] to use
+    pub fn difficulty(mut self, difficulty: DifficultyFilter) -> Self {
+        self.difficulty = difficulty;
+        self
+    }
+
     /// Turns on filtering by level rating (if not already on) and adds the given level rating to
-    /// the list of ratings to include in the search results
+    /// the list of ratings to include in the search results (see [`DifficultyFilter::Ratings`])
     ///
-    /// Passing [`LevelRating::Demon`] here will turn on filtering by _any_ demon difficulty. The
-    /// filter `demon_rating` for specific demon difficulties is reset to `None` when this method is
-    /// called, as these modes are mutually exclusive.
+    /// Passing [`LevelRating::Demon`] here will turn on filtering by _any_ demon difficulty. Any
+    /// filter for a specific demon difficulty is reset when this method is called, as these modes
+    /// are mutually exclusive.
     pub fn with_rating(mut self, rating: LevelRating) -> Self {
-        self.demon_rating = None;
-        self.ratings.push(RatingFilter(rating));
+        match self.difficulty {
+            DifficultyFilter::Ratings(ref mut ratings) => ratings.push(rating),
+            _ => self.difficulty = DifficultyFilter::Ratings(vec![rating]),
+        }
         self
     }
 
-    /// Turns on filtering by demon difficulty
+    /// Turns on filtering by demon difficulty (see [`DifficultyFilter::Demon`])
     ///
     /// Resets any [`LevelRating`] filters set beforehand, as these modes are mutually exclusive.
     pub fn demon_rating(mut self, demon_rating: DemonRating) -> Self {
-        self.ratings.clear();
-        self.demon_rating = Some(DemonFilter(demon_rating));
+        self.difficulty = DifficultyFilter::Demon(demon_rating);
         self
     }
 
@@ -589,14 +868,235 @@ impl<'a> LevelsRequest<'a> {
         self.search_filters = filters;
         self
     }
+
+    /// Turns this request into a [`LevelRequestType::Followed`]-type request, retrieving levels by
+    /// the creators with the given account IDs
+    pub fn followed_creators(mut self, account_ids: impl IntoIterator<Item = u64>) -> Self {
+        self.followed = account_ids.into_iter().collect();
+        self.request_type = LevelRequestType::Followed;
+        self
+    }
+
+    /// Turns this request into a [`LevelRequestType::Friends`]-type request, retrieving levels by
+    /// the friends of the given account
+    pub fn friends(mut self, authenticated_user: AuthenticatedUser<'a>) -> Self {
+        self.authenticated_user = Some(authenticated_user);
+        self.request_type = LevelRequestType::Friends;
+        self
+    }
+
+    /// Turns this request into a [`LevelRequestType::LevelList`]-type request, retrieving the
+    /// levels with the given IDs
+    pub fn levels(mut self, level_ids: impl IntoIterator<Item = u64>) -> Self {
+        let ids: Vec<String> = level_ids.into_iter().map(|id| id.to_string()).collect();
+
+        self.search_string = Cow::Owned(ids.join(","));
+        self.request_type = LevelRequestType::LevelList;
+        self
+    }
+
+    /// Checks whether this request combines filters in a way that cannot produce any results (or
+    /// will cause them to be silently ignored)
+    pub fn validate(&self) -> Result<(), RequestBuildError> {
+        let has_filters = !self.lengths.is_empty() || self.difficulty != DifficultyFilter::Any || !self.search_filters.is_empty();
+
+        if has_filters && !self.request_type.supports_filters() {
+            return Err(RequestBuildError::FiltersUnsupported(self.request_type));
+        }
+
+        if !self.search_string.is_empty() && !self.request_type.uses_search_string() {
+            return Err(RequestBuildError::SearchStringUnused(self.request_type));
+        }
+
+        if let DifficultyFilter::Ratings(ratings) = &self.difficulty {
+            if ratings.len() > 1 && ratings.iter().any(|rating| matches!(rating, LevelRating::Demon(_))) {
+                return Err(RequestBuildError::DemonWithOtherRatings);
+            }
+        }
+
+        if self.request_type == LevelRequestType::User && self.search_string.parse::<u64>().is_err() {
+            return Err(RequestBuildError::InvalidUserId(self.search_string.to_string()));
+        }
+
+        if self.request_type == LevelRequestType::Followed && self.followed.is_empty() {
+            return Err(RequestBuildError::NoFollowedCreators);
+        }
+
+        if self.request_type == LevelRequestType::Friends && self.authenticated_user.is_none() {
+            return Err(RequestBuildError::Unauthenticated(self.request_type));
+        }
+
+        Ok(())
+    }
+
+    /// [Validates](LevelsRequest::validate) this request, returning it if it is valid
+    pub fn build(self) -> Result<Self, RequestBuildError> {
+        self.validate().map(|_| self)
+    }
+}
+
+/// Error returned by [`LevelsRequest::validate`] and [`LevelsRequest::build`] if the request
+/// combines filters in a way the servers cannot handle
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RequestBuildError {
+    /// Filters were set, but the request type does not support filtering (see
+    /// [`LevelRequestType::supports_filters`])
+    #[error("requests of type {0:?} do not support filtering")]
+    FiltersUnsupported(LevelRequestType),
+
+    /// A search string was set, but the request type does not use it (see
+    /// [`LevelRequestType::uses_search_string`])
+    #[error("requests of type {0:?} do not use a search string")]
+    SearchStringUnused(LevelRequestType),
+
+    /// Filtering by any demon difficulty was combined with filtering by other level ratings, which
+    /// always produces an empty result
+    #[error("filtering by any demon cannot be combined with other level ratings")]
+    DemonWithOtherRatings,
+
+    /// A [`LevelRequestType::User`] request was made with a search string that is not a user ID
+    #[error("user searches require a user ID as search string, got {0:?}")]
+    InvalidUserId(String),
+
+    /// A [`LevelRequestType::Followed`] request was made without specifying any creators
+    #[error("no followed creators specified")]
+    NoFollowedCreators,
+
+    /// The request type requires authentication, but [`LevelsRequest::authenticated_user`] was
+    /// not set
+    #[error("requests of type {0:?} require authentication")]
+    Unauthenticated(LevelRequestType),
 }
 
+request!(LevelsRequest, SEARCH_LEVEL_ENDPOINT, Common);
+
 impl Display for LevelsRequest<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", super::to_string(self))
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self).map_err(|_| core::fmt::Error)?)
+    }
+}
+
+/// Struct modelled after a request to `deleteGJLevelUser20.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to delete a level uploaded by the authenticated
+/// account. Use [`parse_delete_gj_level_user_response`](crate::response::parse_delete_gj_level_user_response)
+/// to interpret the server's response.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash)]
+pub struct DeleteLevelRequest<'a> {
+    /// The base request data
+    pub base: BaseRequest<'a>,
+
+    /// The account that uploaded the level
+    pub authenticated_user: AuthenticatedUser<'a>,
+
+    /// The ID of the level to delete
+    ///
+    /// ## GD Internals:
+    /// This field is called `levelID` in the boomlings API
+    #[serde(rename = "levelID")]
+    pub level_id: u64,
+}
+
+impl<'a> DeleteLevelRequest<'a> {
+    pub fn to_url(&self) -> String {
+        endpoint_url(DELETE_LEVEL_ENDPOINT)
+    }
+
+    /// Constructs a new `DeleteLevelRequest` deleting the level with the given id.
+    ///
+    /// Uses a [`BaseRequest`] with the secret the Boomlings servers expect for level deletion,
+    /// which differs from the one used by most other endpoints.
+    pub const fn new(authenticated_user: AuthenticatedUser<'a>, level_id: u64) -> Self {
+        Self::with_base(
+            BaseRequest {
+                secret: Secrets::BOOMLINGS.level,
+                ..GD_22
+            },
+            authenticated_user,
+            level_id,
+        )
+    }
+
+    pub const fn with_base(base: BaseRequest<'a>, authenticated_user: AuthenticatedUser<'a>, level_id: u64) -> Self {
+        DeleteLevelRequest {
+            base,
+            authenticated_user,
+            level_id,
+        }
+    }
+}
+
+request!(DeleteLevelRequest, DELETE_LEVEL_ENDPOINT, Level);
+
+impl Display for DeleteLevelRequest<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self).map_err(|_| core::fmt::Error)?)
+    }
+}
+
+/// Struct modelled after a request to `updateGJDesc20.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to change the description of a level uploaded
+/// by the authenticated account. Use [`parse_update_gj_desc_response`](crate::response::parse_update_gj_desc_response)
+/// to interpret the server's response.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, Hash)]
+pub struct UpdateLevelDescriptionRequest<'a> {
+    /// The base request data
+    pub base: BaseRequest<'a>,
+
+    /// The account that uploaded the level
+    pub authenticated_user: AuthenticatedUser<'a>,
+
+    /// The ID of the level whose description should be changed
+    ///
+    /// ## GD Internals:
+    /// This field is called `levelID` in the boomlings API
+    #[serde(rename = "levelID")]
+    pub level_id: u64,
+
+    /// The new description of the level
+    ///
+    /// ## GD Internals:
+    /// This field is called `levelDesc` in the boomlings API, and is urlsafe base64 encoded
+    #[serde(rename = "levelDesc", serialize_with = "serialize_base64")]
+    pub description: Cow<'a, str>,
+}
+
+impl<'a> UpdateLevelDescriptionRequest<'a> {
+    pub fn to_url(&self) -> String {
+        endpoint_url(UPDATE_DESCRIPTION_ENDPOINT)
+    }
+
+    /// Constructs a new `UpdateLevelDescriptionRequest` setting the description of the level with
+    /// the given id
+    pub fn new(authenticated_user: AuthenticatedUser<'a>, level_id: u64, description: impl Into<Cow<'a, str>>) -> Self {
+        Self::with_base(GD_22, authenticated_user, level_id, description)
+    }
+
+    pub fn with_base(
+        base: BaseRequest<'a>, authenticated_user: AuthenticatedUser<'a>, level_id: u64, description: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        UpdateLevelDescriptionRequest {
+            base,
+            authenticated_user,
+            level_id,
+            description: description.into(),
+        }
     }
 }
 
+request!(UpdateLevelDescriptionRequest, UPDATE_DESCRIPTION_ENDPOINT, Common);
+
+impl Display for UpdateLevelDescriptionRequest<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self).map_err(|_| core::fmt::Error)?)
+    }
+}
+
+fn serialize_base64<S: Serializer>(value: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&base64_encode(value.as_bytes()))
+}
+
 /// Newtype struct for [`DemonRating`] to implement robtop's serialization for requests on
 #[derive(Debug, Clone, Copy, Hash)]
 struct DemonFilter(DemonRating);
@@ -671,10 +1171,157 @@ impl Serialize for RatingFilter {
 #[cfg(test)]
 mod tests {
     use crate::{
-        model::level::LevelLength,
-        request::level::{CompletionFilter, LevelRequestType, LevelsRequest, SearchFilters},
+        model::level::{DemonRating, LevelLength, LevelRating},
+        request::{
+            level::{
+                CompletionFilter, DeleteLevelRequest, DifficultyFilter, LevelRequestType, LevelsRequest, RequestBuildError, SearchFilters,
+                UpdateLevelDescriptionRequest, FEATURED_PAGE_0, HALL_OF_FAME_PAGE_0,
+            },
+            AuthenticatedUser,
+        },
     };
 
+    #[test]
+    fn serialize_extended_search_types() {
+        assert_eq!(
+            super::super::to_string(LevelsRequest::new().levels([10565740, 11774780])).unwrap(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&type=25&str=10565740,11774780&len=-&diff=-&page=0&total=0&featured=0&\
+             original=0&twoPlayer=0&coins=0&epic=0&star=0&onlyCompleted=0&uncompleted=0"
+        );
+        assert_eq!(
+            super::super::to_string(LevelsRequest::new().followed_creators([71, 1710032])).unwrap(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&type=12&str=&len=-&diff=-&page=0&total=0&featured=0&original=0&\
+             twoPlayer=0&coins=0&epic=0&star=0&onlyCompleted=0&uncompleted=0&followed=71,1710032"
+        );
+        assert_eq!(
+            super::super::to_string(LevelsRequest::new().friends(AuthenticatedUser::new(1710032, "hash"))).unwrap(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&type=13&str=&len=-&diff=-&page=0&total=0&featured=0&original=0&\
+             twoPlayer=0&coins=0&epic=0&star=0&onlyCompleted=0&uncompleted=0&accountID=1710032&gjp2=hash"
+        );
+        assert_eq!(LevelRequestType::from(27), LevelRequestType::Sent);
+        assert_eq!(i32::from(LevelRequestType::WeeklyHistory), 22);
+    }
+
+    #[test]
+    fn serialize_difficulty_filters() {
+        let prefix = "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&type=0&str=";
+        let suffix = "&page=0&total=0&featured=0&original=0&twoPlayer=0&coins=0&epic=0&star=0&onlyCompleted=0&uncompleted=0";
+
+        for (request, filters) in [
+            (LevelsRequest::new(), "&len=-&diff=-"),
+            (LevelsRequest::new().difficulty(DifficultyFilter::NotAvailable), "&len=-&diff=-1"),
+            (LevelsRequest::new().difficulty(DifficultyFilter::Auto), "&len=-&diff=-3"),
+            (
+                LevelsRequest::new().with_rating(LevelRating::Easy).with_rating(LevelRating::Harder),
+                "&len=-&diff=1,4",
+            ),
+            (LevelsRequest::new().difficulty(DifficultyFilter::AnyDemon), "&len=-&diff=-2"),
+            (
+                LevelsRequest::new().demon_rating(DemonRating::Extreme),
+                "&len=-&diff=-2&demonFilter=5",
+            ),
+            (
+                LevelsRequest::new().with_length(LevelLength::Tiny).demon_rating(DemonRating::Easy),
+                "&len=0&diff=-2&demonFilter=1",
+            ),
+            (LevelsRequest::new().platformer(), "&len=5&diff=-"),
+            (
+                LevelsRequest::new()
+                    .with_length(LevelLength::Long)
+                    .platformer()
+                    .difficulty(DifficultyFilter::Auto),
+                "&len=5&diff=-3",
+            ),
+        ]
+        .iter()
+        {
+            assert_eq!(
+                super::super::to_string(request).unwrap(),
+                format!("{}{}{}", prefix, filters, suffix)
+            );
+        }
+
+        assert_eq!(
+            LevelsRequest::new()
+                .demon_rating(DemonRating::Hard)
+                .with_rating(LevelRating::Insane)
+                .difficulty,
+            DifficultyFilter::Ratings(vec![LevelRating::Insane])
+        );
+    }
+
+    #[test]
+    fn validate_levels_request() {
+        assert_eq!(FEATURED_PAGE_0.validate(), Ok(()));
+        assert_eq!(
+            LevelsRequest::new().search("Bloodbath").with_rating(LevelRating::Insane).validate(),
+            Ok(())
+        );
+        assert_eq!(
+            HALL_OF_FAME_PAGE_0.with_length(LevelLength::Long).validate(),
+            Err(RequestBuildError::FiltersUnsupported(LevelRequestType::HallOfFame))
+        );
+        assert_eq!(
+            FEATURED_PAGE_0
+                .search("Bloodbath")
+                .request_type(LevelRequestType::Featured)
+                .validate(),
+            Err(RequestBuildError::SearchStringUnused(LevelRequestType::Featured))
+        );
+        assert_eq!(
+            LevelsRequest::new()
+                .with_rating(LevelRating::Demon(DemonRating::Easy))
+                .with_rating(LevelRating::Easy)
+                .build()
+                .unwrap_err(),
+            RequestBuildError::DemonWithOtherRatings
+        );
+        assert_eq!(
+            LevelsRequest::new()
+                .search("stadust")
+                .request_type(LevelRequestType::User)
+                .build()
+                .unwrap_err(),
+            RequestBuildError::InvalidUserId("stadust".to_string())
+        );
+        assert_eq!(
+            LevelsRequest::new().request_type(LevelRequestType::Followed).validate(),
+            Err(RequestBuildError::NoFollowedCreators)
+        );
+        assert_eq!(
+            LevelsRequest::new().request_type(LevelRequestType::Friends).validate(),
+            Err(RequestBuildError::Unauthenticated(LevelRequestType::Friends))
+        );
+    }
+
+    #[test]
+    fn serialize_delete_level() {
+        let request = DeleteLevelRequest::new(AuthenticatedUser::new(1710032, "hash"), 10565740);
+
+        assert_eq!(
+            super::super::to_string(request).unwrap(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfv2898gc9&accountID=1710032&gjp2=hash&levelID=10565740"
+        );
+    }
+
+    #[test]
+    fn serialize_update_description() {
+        let request = UpdateLevelDescriptionRequest::new(AuthenticatedUser::new(1710032, "hash"), 10565740, "Hello World");
+
+        assert_eq!(
+            super::super::to_string(request).unwrap(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&accountID=1710032&gjp2=hash&levelID=10565740&levelDesc=SGVsbG8gV29ybGQ="
+        );
+    }
+
+    #[test]
+    fn const_presets_match_builder() {
+        assert_eq!(
+            super::super::to_string(&FEATURED_PAGE_0).unwrap(),
+            super::super::to_string(LevelsRequest::default().request_type(LevelRequestType::Featured)).unwrap()
+        );
+    }
+
     #[test]
     fn serialize_levels_request() {
         let request =
@@ -689,7 +1336,7 @@ mod tests {
                 ));
 
         assert_eq!(
-            super::super::to_string(request),
+            super::super::to_string(request).unwrap(),
             "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&type=2&str=&len=2,3&diff=-&page=0&total=0&featured=1&original=0&\
              twoPlayer=1&coins=0&epic=1&star=1&completedLevels=(18018958,21373201,22057275,22488444,22008823,23144971,17382902,87600,\
              22031889,22390740,22243264,21923305)&onlyCompleted=0&uncompleted=1"
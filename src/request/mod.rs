@@ -5,11 +5,39 @@
 //! making/proxying requests for the Boomlings servers seems rather useless to me, as they already
 //! contain a lot of Boomlings-specific fields.
 //! This can also be edited for a specific GDPS, e.g 1.9 GDPS. (hi absowute :3)
+//!
+//! ## Field order
+//! The key-value pairs of a request are emitted in the order in which the fields are declared in
+//! the request struct, with nested structs (such as [`BaseRequest`]) inlined at the position of
+//! the field containing them. Optional fields that are not set are omitted entirely. This matches
+//! the order the official client uses, and is pinned down by the serialization tests of each
+//! request. Some GDPS backends are sensitive to this order, in which case it can be overridden
+//! per [`ServerConfig`] via [`FieldOrder`].
+//!
+//! ## Older servers
+//! Private servers running older versions of the game use different endpoint names (e.g.
+//! `getGJLevels19.php` instead of `getGJLevels21.php`) and omit the level indices introduced in
+//! later versions from their responses. Both are handled via a [`CompatProfile`], either per
+//! [`ServerConfig`] (whose [`ServerConfig::quirks`] carry it into the response parsers), or
+//! globally via [`GD_SERVER_COMPAT_PROFILE`] for the `to_url` methods of the requests and the
+//! parsers not taking [`Quirks`].
+//!
+//! ## Secrets
+//! Every endpoint requires one of a few fixed secret strings (see [`SecretKind`]) to be included in
+//! the request. Attaching the wrong one makes the server respond with a generic `-1`. A
+//! [`ServerConfig`] knows the secrets of its server (which GDPSes may change), and validates
+//! requests against them before serializing them via [`ServerConfig::build_request`].
 
-use std::sync::OnceLock;
+use alloc::{format, string::String, vec::Vec};
+use core::{ops::Range, str::FromStr};
 
-use crate::{model::GameVersion, serde::RequestSerializer};
+use crate::{
+    model::GameVersion,
+    response::Quirks,
+    serde::{RequestSerializer, SerError},
+};
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 macro_rules! const_setter {
     ($name: ident, $field: ident, $t: ty) => {
@@ -43,14 +71,62 @@ macro_rules! const_setter {
     }
 }
 
+macro_rules! request {
+    ($request: ident, $endpoint: ident, $secret: ident) => {
+        impl<'a> $crate::request::Request<'a> for $request<'a> {
+            const ENDPOINT: &'static str = $endpoint;
+            const SECRET: $crate::request::SecretKind = $crate::request::SecretKind::$secret;
+
+            fn base(&self) -> &$crate::request::BaseRequest<'a> {
+                &self.base
+            }
+
+            fn base_mut(&mut self) -> &mut $crate::request::BaseRequest<'a> {
+                &mut self.base
+            }
+        }
+    };
+}
+
 pub mod comment;
 pub mod level;
 pub mod user;
 
-pub static GD_SERVER_ENDPOINT_BASE_URL: OnceLock<String> = OnceLock::new();
+/// Overrides the base URL returned by [`endpoint_base_url`]. Only available with the `std` feature.
+#[cfg(feature = "std")]
+pub static GD_SERVER_ENDPOINT_BASE_URL: std::sync::OnceLock<String> = std::sync::OnceLock::new();
 
+/// The base URL all endpoint URLs are relative to.
+///
+/// Without the `std` feature, this is always [`BOOMLINGS_ENDPOINTS_BASE`].
 pub fn endpoint_base_url() -> &'static str {
-    GD_SERVER_ENDPOINT_BASE_URL.get_or_init(|| BOOMLINGS_ENDPOINTS_BASE.to_string())
+    #[cfg(feature = "std")]
+    return GD_SERVER_ENDPOINT_BASE_URL.get_or_init(|| BOOMLINGS_ENDPOINTS_BASE.into());
+
+    #[cfg(not(feature = "std"))]
+    BOOMLINGS_ENDPOINTS_BASE
+}
+
+/// Overrides the [`CompatProfile`] returned by [`compat_profile`]. Only available with the `std`
+/// feature.
+#[cfg(feature = "std")]
+pub static GD_SERVER_COMPAT_PROFILE: std::sync::OnceLock<CompatProfile> = std::sync::OnceLock::new();
+
+/// The [`CompatProfile`] of the server requests are made to.
+///
+/// Without the `std` feature, this is always [`CompatProfile::Gd22`].
+pub fn compat_profile() -> CompatProfile {
+    #[cfg(feature = "std")]
+    return *GD_SERVER_COMPAT_PROFILE.get_or_init(CompatProfile::default);
+
+    #[cfg(not(feature = "std"))]
+    CompatProfile::Gd22
+}
+
+/// The full URL of the given endpoint, taking into account [`endpoint_base_url`] and
+/// [`compat_profile`]
+pub(crate) fn endpoint_url(endpoint: &str) -> String {
+    format!("{}{}", endpoint_base_url(), compat_profile().endpoint(endpoint))
 }
 
 pub const BOOMLINGS_ENDPOINTS_BASE: &str = "https://silverragdps.mathieuar.fr/";
@@ -123,11 +199,522 @@ impl Default for BaseRequest<'static> {
     }
 }
 
-pub(crate) fn to_string<S: Serialize>(request: S) -> String {
+/// A request to a specific endpoint of the Boomlings API
+pub trait Request<'a>: Serialize {
+    /// The endpoint this request is made to, relative to the server's base URL
+    const ENDPOINT: &'static str;
+
+    /// The kind of secret the endpoint requires
+    const SECRET: SecretKind;
+
+    fn base(&self) -> &BaseRequest<'a>;
+
+    fn base_mut(&mut self) -> &mut BaseRequest<'a>;
+
+    /// The key-value pairs this request consists of, in declaration order
+    ///
+    /// This is the structured form of the string produced by the [`Display`](core::fmt::Display)
+    /// implementations of the requests, for use with HTTP clients (or signing layers) that want to
+    /// build the form body themselves. Values are exactly as they would appear in that string.
+    fn to_form_pairs(&self) -> Result<Vec<(&'static str, String)>, SerError> {
+        let (output, fields) = serialize_with_spans(self)?;
+
+        Ok(fields.into_iter().map(|(key, span)| (key, output[span].into())).collect())
+    }
+}
+
+/// The different categories of secrets the Boomlings API uses to identify valid clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SecretKind {
+    /// The secret used by most endpoints, `"Wmfd2893gb7"` on the official servers
+    Common,
+
+    /// The secret used by endpoints managing accounts (e.g. their settings), `"Wmfv3899gc9"` on
+    /// the official servers
+    Account,
+
+    /// The secret used by moderator actions, `"Wmfp3879gc3"` on the official servers
+    Mod,
+
+    /// The secret used for deleting levels, `"Wmfv2898gc9"` on the official servers
+    Level,
+}
+
+/// The values of each [`SecretKind`] for some server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Secrets<'a> {
+    pub common: &'a str,
+    pub account: &'a str,
+    pub moderator: &'a str,
+    pub level: &'a str,
+}
+
+impl<'a> Secrets<'a> {
+    /// The secrets used by the official Boomlings servers
+    pub const BOOMLINGS: Secrets<'static> = Secrets {
+        common: "Wmfd2893gb7",
+        account: "Wmfv3899gc9",
+        moderator: "Wmfp3879gc3",
+        level: "Wmfv2898gc9",
+    };
+
+    /// The secret of the given kind
+    pub const fn get(&self, kind: SecretKind) -> &'a str {
+        match kind {
+            SecretKind::Common => self.common,
+            SecretKind::Account => self.account,
+            SecretKind::Mod => self.moderator,
+            SecretKind::Level => self.level,
+        }
+    }
+
+    /// The kind of the given secret, or [`None`] if it is not one of these secrets
+    pub fn kind_of(&self, secret: &str) -> Option<SecretKind> {
+        [SecretKind::Common, SecretKind::Account, SecretKind::Mod, SecretKind::Level]
+            .iter()
+            .copied()
+            .find(|&kind| self.get(kind) == secret)
+    }
+}
+
+impl Default for Secrets<'static> {
+    fn default() -> Self {
+        Secrets::BOOMLINGS
+    }
+}
+
+/// Error returned by [`ServerConfig::validate_request`] (and, wrapped in a [`BuildRequestError`],
+/// by [`ServerConfig::build_request`]) if a request does not have the secret its endpoint requires attached
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SecretError {
+    /// A secret of the wrong kind was attached
+    #[error("{endpoint} requires the {expected:?} secret, but the {found:?} secret was attached")]
+    WrongKind {
+        endpoint: &'static str,
+        expected: SecretKind,
+        found: SecretKind,
+    },
+
+    /// A secret not known to the server was attached
+    #[error("{endpoint} requires the {expected:?} secret, but an unknown secret was attached")]
+    Unknown { endpoint: &'static str, expected: SecretKind },
+}
+
+/// Error returned by [`ServerConfig::build_request`]
+#[derive(Debug, Error)]
+pub enum BuildRequestError {
+    /// The request does not have the secret its endpoint requires attached
+    #[error("{0}")]
+    Secret(#[from] SecretError),
+
+    /// The request could not be serialized
+    #[error("{0}")]
+    Serialization(#[from] SerError),
+}
+
+/// The order in which the key-value pairs of a request are emitted, see the [module level
+/// documentation](self#field-order)
+///
+/// Fields whose keys appear in `priority` are emitted first, in the given order. All remaining
+/// fields follow in declaration order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FieldOrder<'a> {
+    /// The keys to emit before all others
+    pub priority: &'a [&'a str],
+}
+
+impl<'a> FieldOrder<'a> {
+    /// Emit all fields in declaration order
+    pub const DECLARATION: FieldOrder<'static> = FieldOrder { priority: &[] };
+
+    pub const fn new(priority: &'a [&'a str]) -> Self {
+        FieldOrder { priority }
+    }
+
+    /// Serializes the given request, emitting its fields in this order
+    pub fn serialize<S: Serialize>(&self, request: S) -> Result<String, SerError> {
+        let (output, mut fields) = serialize_with_spans(&request)?;
+
+        if self.priority.is_empty() {
+            return Ok(output);
+        }
+
+        let mut ordered = Vec::with_capacity(fields.len());
+
+        for key in self.priority {
+            if let Some(idx) = fields.iter().position(|(k, _)| k == key) {
+                ordered.push(fields.remove(idx));
+            }
+        }
+
+        ordered.extend(fields);
+
+        let mut reordered = String::with_capacity(output.len());
+
+        for (key, span) in ordered {
+            if !reordered.is_empty() {
+                reordered.push('&');
+            }
+
+            reordered.push_str(key);
+            reordered.push('=');
+            reordered.push_str(&output[span]);
+        }
+
+        Ok(reordered)
+    }
+}
+
+/// Serializes the given request in declaration order, returning the output together with the keys
+/// written and the byte ranges of their values within the output, in the order they were written
+fn serialize_with_spans<S: Serialize + ?Sized>(request: &S) -> Result<(String, Vec<(&'static str, Range<usize>)>), SerError> {
     let mut output = Vec::new();
     let mut serializer = RequestSerializer::new(&mut output);
 
-    request.serialize(&mut serializer).unwrap();
+    request.serialize(&mut serializer)?;
+
+    let fields = serializer.fields().to_vec();
 
-    String::from_utf8(output).unwrap()
+    Ok((String::from_utf8(output)?, fields))
+}
+
+/// The version of the game a server implements, for compatibility with private servers running
+/// older versions
+///
+/// Responses of older servers lack the indices introduced in later versions (such as the coin
+/// and epic indices of levels, introduced in 2.0 and 2.1 respectively). If the profile a response is
+/// parsed with predates an index, it is treated as optional during deserialization and takes on its
+/// default value if missing. Otherwise, a missing index is an error.
+///
+/// The response parsers taking [`Quirks`] use [`Quirks::compat`] (see [`ServerConfig::quirks`]).
+/// Everything else, including [`GJFormat::from_gj_str`](crate::GJFormat::from_gj_str), uses the
+/// global [`compat_profile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum CompatProfile {
+    /// Update 1.9
+    Gd19,
+
+    /// Update 2.0
+    Gd20,
+
+    /// Update 2.1
+    Gd21,
+
+    /// Update 2.2, which is what the official servers run
+    #[default]
+    Gd22,
+}
+
+impl CompatProfile {
+    /// Maps the given endpoint (one of the `*_ENDPOINT` constants in the submodules of this
+    /// module) to the name the corresponding endpoint has on servers of this version.
+    ///
+    /// Endpoints without a version specific name are returned unchanged.
+    pub fn endpoint<'a>(&self, endpoint: &'a str) -> &'a str {
+        match (self, endpoint) {
+            (CompatProfile::Gd19, level::SEARCH_LEVEL_ENDPOINT) => "getGJLevels19.php",
+            (CompatProfile::Gd19, level::DOWNLOAD_LEVEL_ENDPOINT) => "downloadGJLevel19.php",
+            (CompatProfile::Gd19, comment::LEVEL_COMMENTS_ENDPOINT) => "getGJComments19.php",
+            (CompatProfile::Gd19, user::SEARCH_USER_ENDPOINT) => "getGJUsers19.php",
+            (CompatProfile::Gd20, level::SEARCH_LEVEL_ENDPOINT) => "getGJLevels20.php",
+            (CompatProfile::Gd20, level::DOWNLOAD_LEVEL_ENDPOINT) => "downloadGJLevel20.php",
+            (CompatProfile::Gd20, comment::LEVEL_COMMENTS_ENDPOINT) => "getGJComments20.php",
+            _ => endpoint,
+        }
+    }
+}
+
+impl FromStr for CompatProfile {
+    type Err = ();
+
+    /// Parses a game version of the form `"1.9"`, `"2.0"`, `"2.1"` or `"2.2"`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1.9" => Ok(CompatProfile::Gd19),
+            "2.0" => Ok(CompatProfile::Gd20),
+            "2.1" => Ok(CompatProfile::Gd21),
+            "2.2" => Ok(CompatProfile::Gd22),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Configuration describing a Geometry Dash server (either the official Boomlings servers, or some
+/// GDPS) requests are made to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ServerConfig<'a> {
+    /// The base URL all endpoint URLs are relative to
+    pub base_url: &'a str,
+
+    /// The order in which request fields are emitted
+    pub field_order: FieldOrder<'a>,
+
+    /// The version of the game this server implements
+    pub compat: CompatProfile,
+
+    /// The secrets this server expects
+    pub secrets: Secrets<'a>,
+}
+
+impl<'a> ServerConfig<'a> {
+    pub const fn new(base_url: &'a str) -> Self {
+        ServerConfig {
+            base_url,
+            field_order: FieldOrder::DECLARATION,
+            compat: CompatProfile::Gd22,
+            secrets: Secrets::BOOMLINGS,
+        }
+    }
+
+    const_setter! {
+        /// Sets the order in which request fields are emitted
+        ///
+        /// Allows builder-style creation of configurations
+        field_order: FieldOrder<'a>
+    }
+
+    const_setter! {
+        /// Sets the version of the game this server implements
+        ///
+        /// Allows builder-style creation of configurations
+        compat: CompatProfile
+    }
+
+    const_setter! {
+        /// Sets the secrets this server expects
+        ///
+        /// Allows builder-style creation of configurations
+        secrets: Secrets<'a>
+    }
+
+    /// The full URL of the given endpoint on this server, mapped according to this server's
+    /// [`CompatProfile`]
+    pub fn endpoint_url(&self, endpoint: &str) -> String {
+        format!("{}{}", self.base_url, self.compat.endpoint(endpoint))
+    }
+
+    /// The [`Quirks`] to parse the responses of this server with, that is the default ones with
+    /// this server's [`CompatProfile`]
+    pub fn quirks(&self) -> Quirks {
+        Quirks {
+            compat: self.compat,
+            ..Quirks::default()
+        }
+    }
+
+    /// Serializes the given request the way this server expects it
+    pub fn serialize_request<S: Serialize>(&self, request: S) -> Result<String, SerError> {
+        self.field_order.serialize(request)
+    }
+
+    /// A [`BaseRequest`] mimicking a Geometry Dash 2.2 client, with the secret of the given kind
+    /// this server expects
+    pub const fn base_request(&self, kind: SecretKind) -> BaseRequest<'a> {
+        BaseRequest {
+            secret: self.secrets.get(kind),
+            ..GD_22
+        }
+    }
+
+    /// Replaces the secret attached to the given request with the one its endpoint requires on this
+    /// server
+    pub fn with_secret<R: Request<'a>>(&self, mut request: R) -> R {
+        request.base_mut().secret = self.secrets.get(R::SECRET);
+        request
+    }
+
+    /// Checks that the given request has the secret its endpoint requires on this server attached
+    pub fn validate_request<'b, R: Request<'b>>(&self, request: &R) -> Result<(), SecretError> {
+        match self.secrets.kind_of(request.base().secret) {
+            Some(kind) if kind == R::SECRET => Ok(()),
+            Some(found) => Err(SecretError::WrongKind {
+                endpoint: R::ENDPOINT,
+                expected: R::SECRET,
+                found,
+            }),
+            None => Err(SecretError::Unknown {
+                endpoint: R::ENDPOINT,
+                expected: R::SECRET,
+            }),
+        }
+    }
+
+    /// Validates the given request via [`ServerConfig::validate_request`], and serializes it the
+    /// way this server expects it
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(endpoint = R::ENDPOINT)))]
+    pub fn build_request<'b, R: Request<'b>>(&self, request: &R) -> Result<String, BuildRequestError> {
+        self.validate_request(request)?;
+
+        Ok(self.serialize_request(request)?)
+    }
+}
+
+impl Default for ServerConfig<'static> {
+    fn default() -> Self {
+        ServerConfig::new(BOOMLINGS_ENDPOINTS_BASE)
+    }
+}
+
+/// Authentication data included in requests that act on behalf of some account
+///
+/// ## GD Internals:
+/// The Geometry Dash client authenticates requests by including the account ID of the currently
+/// logged in account, as well as a hash of that account's password (called `gjp2`), with every
+/// request that requires a login.
+#[derive(Debug, Clone, Hash, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthenticatedUser<'a> {
+    /// The account ID of the account making the request
+    ///
+    /// ## GD Internals:
+    /// This field is called `accountID` in the boomlings API
+    #[serde(rename = "accountID")]
+    pub account_id: u64,
+
+    /// The hashed password of the account making the request
+    ///
+    /// ## GD Internals:
+    /// This field is called `gjp2` in the boomlings API. It is the hex-encoded SHA-1 hash of the
+    /// account's password concatenated with the salt `"mI29fmAnxgTs"`, see [`crypto::gjp2`](crate::crypto::gjp2).
+    #[serde(rename = "gjp2")]
+    pub password_hash: &'a str,
+}
+
+impl<'a> AuthenticatedUser<'a> {
+    pub const fn new(account_id: u64, password_hash: &'a str) -> Self {
+        AuthenticatedUser { account_id, password_hash }
+    }
+}
+
+pub(crate) fn to_string<S: Serialize>(request: S) -> Result<String, SerError> {
+    FieldOrder::DECLARATION.serialize(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        model::level::LevelLength,
+        request::{
+            level::{LevelRequest, LevelsRequest},
+            user::{UserRequest, UserSearchRequest},
+            CompatProfile, FieldOrder, Request, SecretError, SecretKind, Secrets, ServerConfig, GD_22,
+        },
+    };
+
+    #[test]
+    fn serialize_user_requests() {
+        assert_eq!(
+            super::to_string(UserRequest::new(1710032)).unwrap(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&targetAccountID=1710032"
+        );
+        assert_eq!(
+            super::to_string(UserSearchRequest::with_base(GD_22, "stadust")).unwrap(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&total=0&page=0&str=stadust"
+        );
+    }
+
+    #[test]
+    fn compat_endpoints() {
+        let config = ServerConfig::new("https://example.com/").compat(CompatProfile::Gd19);
+
+        assert_eq!(
+            config.endpoint_url(super::level::SEARCH_LEVEL_ENDPOINT),
+            "https://example.com/getGJLevels19.php"
+        );
+        assert_eq!(
+            config.endpoint_url(super::user::GET_USER_ENDPOINT),
+            "https://example.com/getGJUserInfo20.php"
+        );
+        assert_eq!(
+            CompatProfile::Gd20.endpoint(super::level::DOWNLOAD_LEVEL_ENDPOINT),
+            "downloadGJLevel20.php"
+        );
+        assert_eq!(
+            CompatProfile::Gd22.endpoint(super::level::SEARCH_LEVEL_ENDPOINT),
+            "getGJLevels21.php"
+        );
+        assert_eq!("1.9".parse(), Ok(CompatProfile::Gd19));
+    }
+
+    #[test]
+    fn validate_secrets() {
+        let config = ServerConfig::default();
+
+        assert_eq!(
+            config.build_request(&UserRequest::new(1710032)).unwrap(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&targetAccountID=1710032"
+        );
+        assert_eq!(
+            config.validate_request(&UserRequest::with_base(config.base_request(SecretKind::Mod), 1710032)),
+            Err(SecretError::WrongKind {
+                endpoint: "getGJUserInfo20.php",
+                expected: SecretKind::Common,
+                found: SecretKind::Mod
+            })
+        );
+
+        let gdps = ServerConfig::new("https://example.com/").secrets(Secrets {
+            common: "abc",
+            ..Secrets::BOOMLINGS
+        });
+
+        assert!(matches!(
+            gdps.validate_request(&UserRequest::new(1710032)),
+            Err(SecretError::Unknown { .. })
+        ));
+        assert_eq!(gdps.with_secret(UserRequest::new(1710032)).base.secret, "abc");
+    }
+
+    #[test]
+    fn custom_field_order() {
+        let config = ServerConfig::default().field_order(FieldOrder::new(&["levelID", "secret", "doesNotExist"]));
+
+        assert_eq!(
+            config.serialize_request(LevelRequest::new(10565740)).unwrap(),
+            "levelID=10565740&secret=Wmfd2893gb7&gameVersion=22&binaryVersion=38&inc=1&extra=0"
+        );
+    }
+
+    #[test]
+    fn custom_field_order_with_unescaped_values() {
+        // The search string contains what looks like the next key-value pair
+        let request = LevelsRequest::new().search("a&len=5").with_length(LevelLength::Tiny);
+        let config = ServerConfig::default().field_order(FieldOrder::new(&["len", "str"]));
+
+        assert_eq!(
+            config.serialize_request(&request).unwrap(),
+            "len=0&str=a&len=5&gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&type=0&diff=-&page=0&total=0&featured=0&\
+             original=0&twoPlayer=0&coins=0&epic=0&star=0&onlyCompleted=0&uncompleted=0"
+        );
+        assert_eq!(
+            ServerConfig::default().serialize_request(&request).unwrap(),
+            super::to_string(&request).unwrap()
+        );
+    }
+
+    #[test]
+    fn form_pairs() {
+        let pairs = LevelsRequest::new()
+            .search("a&len=5")
+            .with_length(LevelLength::Tiny)
+            .to_form_pairs()
+            .unwrap();
+
+        assert_eq!(pairs[3], ("type", "0".into()));
+        assert_eq!(pairs[4], ("str", "a&len=5".into()));
+        assert_eq!(pairs[5], ("len", "0".into()));
+        assert_eq!(pairs[6], ("diff", "-".into()));
+        assert_eq!(pairs.last(), Some(&("uncompleted", "0".into())));
+
+        assert_eq!(
+            UserSearchRequest::with_base(GD_22, "stadust").to_form_pairs().unwrap(),
+            vec![
+                ("gameVersion", "22".into()),
+                ("binaryVersion", "38".into()),
+                ("secret", "Wmfd2893gb7".into()),
+                ("total", "0".into()),
+                ("page", "0".into()),
+                ("str", "stadust".into())
+            ]
+        );
+    }
 }
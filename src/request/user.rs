@@ -1,15 +1,30 @@
-//! Module containing request definitions for retrieving users
+//! Module containing request definitions for retrieving and updating users
 
 use crate::{
-    model::creator::Creator,
-    request::{endpoint_base_url, BaseRequest, GD_22},
+    crypto::{chk, USER_SCORE_SALT, USER_SCORE_XOR_KEY},
+    model::{
+        creator::Creator,
+        user::{
+            profile::{CommentHistoryPrivacy, FriendRequestPrivacy, MessagePrivacy, Profile},
+            IconType,
+        },
+    },
+    request::{endpoint_url, AuthenticatedUser, BaseRequest, Secrets, GD_22},
 };
-use serde::Serialize;
-use std::borrow::Cow;
-use std::fmt::Display;
+use alloc::{
+    borrow::Cow,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Display;
+use serde::{ser::SerializeStruct, Serialize, Serializer};
 
 pub const GET_USER_ENDPOINT: &str = "getGJUserInfo20.php";
 pub const SEARCH_USER_ENDPOINT: &str = "getGJUsers20.php";
+pub const REQUEST_MOD_ACCESS_ENDPOINT: &str = "requestUserAccess.php";
+pub const UPDATE_USER_SCORE_ENDPOINT: &str = "updateGJUserScore22.php";
+pub const UPDATE_ACCOUNT_SETTINGS_ENDPOINT: &str = "updateGJAccSettings20.php";
 
 /// Struct modelled after a request to `getGJUserInfo20.php`.
 ///
@@ -28,16 +43,17 @@ pub struct UserRequest<'a> {
     pub user: u64,
 }
 
-impl UserRequest<'_> {
+impl<'a> UserRequest<'a> {
     pub const fn new(user_id: u64) -> UserRequest<'static> {
-        UserRequest {
-            base: GD_22,
-            user: user_id,
-        }
+        UserRequest::with_base(GD_22, user_id)
+    }
+
+    pub const fn with_base(base: BaseRequest<'a>, user_id: u64) -> Self {
+        UserRequest { base, user: user_id }
     }
 
     pub fn to_url(&self) -> String {
-        format!("{}{}", endpoint_base_url(), GET_USER_ENDPOINT)
+        endpoint_url(GET_USER_ENDPOINT)
     }
 }
 
@@ -53,9 +69,11 @@ impl From<Creator<'_>> for UserRequest<'_> {
     }
 }
 
+request!(UserRequest, GET_USER_ENDPOINT, Common);
+
 impl Display for UserRequest<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", super::to_string(self))
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self).map_err(|_| core::fmt::Error)?)
     }
 }
 
@@ -99,8 +117,19 @@ impl<'a> UserSearchRequest<'a> {
         }
     }
 
+    /// Constructs a new `UserSearchRequest` for the given name. Unlike [`UserSearchRequest::new`],
+    /// this can be used in const contexts
+    pub const fn with_base(base: BaseRequest<'a>, search_string: &'a str) -> Self {
+        UserSearchRequest {
+            base,
+            total: 0,
+            page: 0,
+            search_string: Cow::Borrowed(search_string),
+        }
+    }
+
     pub fn to_url(&self) -> String {
-        format!("{}{}", endpoint_base_url(), SEARCH_USER_ENDPOINT)
+        endpoint_url(SEARCH_USER_ENDPOINT)
     }
 }
 
@@ -116,8 +145,506 @@ impl<'a: 'b, 'b> From<&'b Creator<'a>> for UserSearchRequest<'b> {
     }
 }
 
+request!(UserSearchRequest, SEARCH_USER_ENDPOINT, Common);
+
 impl Display for UserSearchRequest<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", super::to_string(self))
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self).map_err(|_| core::fmt::Error)?)
+    }
+}
+
+/// Struct modelled after a request to `requestUserAccess.php`.
+///
+/// In the Geometry Dash API, this endpoint is used by the "Req" button in the game's settings to
+/// check whether the authenticated account has moderator privileges. Use
+/// [`parse_request_user_access_response`](crate::response::parse_request_user_access_response) to
+/// turn the server's response into a [`ModLevel`](crate::model::user::ModLevel).
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash)]
+pub struct RequestModAccessRequest<'a> {
+    /// The base request data
+    pub base: BaseRequest<'a>,
+
+    /// The account whose moderator status should be checked
+    pub authenticated_user: AuthenticatedUser<'a>,
+}
+
+impl<'a> RequestModAccessRequest<'a> {
+    pub const fn new(authenticated_user: AuthenticatedUser<'a>) -> Self {
+        Self::with_base(GD_22, authenticated_user)
+    }
+
+    pub const fn with_base(base: BaseRequest<'a>, authenticated_user: AuthenticatedUser<'a>) -> Self {
+        RequestModAccessRequest { base, authenticated_user }
+    }
+
+    pub fn to_url(&self) -> String {
+        endpoint_url(REQUEST_MOD_ACCESS_ENDPOINT)
+    }
+}
+
+impl<'a> From<AuthenticatedUser<'a>> for RequestModAccessRequest<'a> {
+    fn from(authenticated_user: AuthenticatedUser<'a>) -> Self {
+        RequestModAccessRequest::new(authenticated_user)
+    }
+}
+
+request!(RequestModAccessRequest, REQUEST_MOD_ACCESS_ENDPOINT, Common);
+
+impl Display for RequestModAccessRequest<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self).map_err(|_| core::fmt::Error)?)
+    }
+}
+
+/// The statistics and icon selection of a player, as submitted to the servers by an
+/// [`UpdateUserScoreRequest`]
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash)]
+pub struct UserStats {
+    pub stars: u32,
+    pub moons: u32,
+    pub demons: u16,
+    pub diamonds: u16,
+
+    /// ## GD Internals:
+    /// This field is called `coins` in the boomlings API
+    #[serde(rename = "coins")]
+    pub secret_coins: u8,
+
+    /// ## GD Internals:
+    /// This field is called `userCoins` in the boomlings API
+    #[serde(rename = "userCoins")]
+    pub user_coins: u16,
+
+    /// The game mode whose icon is displayed next to the player's name
+    ///
+    /// ## GD Internals:
+    /// This field is called `iconType` in the boomlings API
+    #[serde(rename = "iconType", serialize_with = "serialize_as_u8")]
+    pub icon_type: IconType,
+
+    /// The index of the icon displayed next to the player's name
+    ///
+    /// ## GD Internals:
+    /// This field is called `icon` in the boomlings API
+    #[serde(rename = "icon")]
+    pub icon_index: u16,
+
+    /// ## GD Internals:
+    /// This field is called `color1` in the boomlings API
+    #[serde(rename = "color1")]
+    pub primary_color: u8,
+
+    /// ## GD Internals:
+    /// This field is called `color2` in the boomlings API
+    #[serde(rename = "color2")]
+    pub secondary_color: u8,
+
+    /// ## GD Internals:
+    /// This field is called `accIcon` in the boomlings API
+    #[serde(rename = "accIcon")]
+    pub cube_index: u16,
+
+    /// ## GD Internals:
+    /// This field is called `accShip` in the boomlings API
+    #[serde(rename = "accShip")]
+    pub ship_index: u8,
+
+    /// ## GD Internals:
+    /// This field is called `accBall` in the boomlings API
+    #[serde(rename = "accBall")]
+    pub ball_index: u8,
+
+    /// ## GD Internals:
+    /// This field is called `accBird` in the boomlings API
+    #[serde(rename = "accBird")]
+    pub ufo_index: u8,
+
+    /// ## GD Internals:
+    /// This field is called `accDart` in the boomlings API
+    #[serde(rename = "accDart")]
+    pub wave_index: u8,
+
+    /// ## GD Internals:
+    /// This field is called `accRobot` in the boomlings API
+    #[serde(rename = "accRobot")]
+    pub robot_index: u8,
+
+    /// ## GD Internals:
+    /// This field is called `accGlow` in the boomlings API
+    #[serde(rename = "accGlow")]
+    pub has_glow: bool,
+
+    /// ## GD Internals:
+    /// This field is called `accSpider` in the boomlings API
+    #[serde(rename = "accSpider")]
+    pub spider_index: u8,
+
+    /// ## GD Internals:
+    /// This field is called `accExplosion` in the boomlings API
+    #[serde(rename = "accExplosion")]
+    pub death_effect_index: u8,
+
+    /// ## GD Internals:
+    /// This field is called `accSwing` in the boomlings API
+    #[serde(rename = "accSwing")]
+    pub swing_index: u8,
+
+    /// ## GD Internals:
+    /// This field is called `accJetpack` in the boomlings API
+    #[serde(rename = "accJetpack")]
+    pub jetpack_index: u8,
+}
+
+impl From<&Profile<'_>> for UserStats {
+    /// Takes the statistics and icons of the given [`Profile`], displaying its cube next to its name
+    fn from(profile: &Profile<'_>) -> Self {
+        UserStats {
+            stars: profile.stars,
+            moons: profile.moons,
+            demons: profile.demons,
+            diamonds: profile.diamonds,
+            secret_coins: profile.secret_coins,
+            user_coins: profile.user_coins,
+            icon_type: IconType::Cube,
+            icon_index: profile.cube_index,
+            primary_color: profile.primary_color.into(),
+            secondary_color: profile.secondary_color.into(),
+            cube_index: profile.cube_index,
+            ship_index: profile.ship_index,
+            ball_index: profile.ball_index,
+            ufo_index: profile.ufo_index,
+            wave_index: profile.wave_index,
+            robot_index: profile.robot_index,
+            has_glow: profile.has_glow,
+            spider_index: profile.spider_index,
+            death_effect_index: profile.death_effect_index,
+            swing_index: profile.swing_index,
+            jetpack_index: profile.jetpack_index,
+        }
+    }
+}
+
+/// Struct modelled after a request to `updateGJUserScore22.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to submit the statistics and icons of the
+/// authenticated account, which are then displayed on its profile and on the leaderboards. Use
+/// [`parse_update_gj_user_score_response`](crate::response::parse_update_gj_user_score_response)
+/// to interpret the server's response.
+///
+/// ## GD Internals:
+/// The request contains a `seed2` checksum over the submitted statistics (see
+/// [`UpdateUserScoreRequest::seed2`]), which is computed during serialization.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UpdateUserScoreRequest<'a> {
+    /// The base request data
+    pub base: BaseRequest<'a>,
+
+    /// The account whose statistics are submitted
+    pub authenticated_user: AuthenticatedUser<'a>,
+
+    /// The name of the account
+    ///
+    /// ## GD Internals:
+    /// This field is called `userName` in the boomlings API
+    pub user_name: Cow<'a, str>,
+
+    /// The statistics to submit
+    pub stats: UserStats,
+
+    /// A random string the game includes with the request
+    ///
+    /// ## GD Internals:
+    /// This field is called `seed` in the boomlings API. The servers do not validate it.
+    pub seed: Cow<'a, str>,
+}
+
+impl<'a> UpdateUserScoreRequest<'a> {
+    pub fn new(authenticated_user: AuthenticatedUser<'a>, user_name: impl Into<Cow<'a, str>>, stats: UserStats) -> Self {
+        Self::with_base(GD_22, authenticated_user, user_name, stats)
+    }
+
+    pub fn with_base(
+        base: BaseRequest<'a>, authenticated_user: AuthenticatedUser<'a>, user_name: impl Into<Cow<'a, str>>, stats: UserStats,
+    ) -> Self {
+        UpdateUserScoreRequest {
+            base,
+            authenticated_user,
+            user_name: user_name.into(),
+            stats,
+            seed: Cow::Borrowed("dashrsseed"),
+        }
+    }
+
+    /// Sets the value of the `seed` field
+    ///
+    /// Allows builder-style creation of requests
+    pub fn seed(mut self, seed: impl Into<Cow<'a, str>>) -> Self {
+        self.seed = seed.into();
+        self
+    }
+
+    /// The checksum the servers verify the submitted statistics against
+    ///
+    /// ## GD Internals:
+    /// The account ID, user coins, demons, stars, secret coins, icon type, icon, diamonds and the
+    /// indices of the cube, ship, ball, ufo, wave, robot, glow, spider and death effect are
+    /// concatenated and passed to [`chk`] with [`USER_SCORE_SALT`] and [`USER_SCORE_XOR_KEY`].
+    pub fn seed2(&self) -> String {
+        let stats = &self.stats;
+        let values = [
+            self.authenticated_user.account_id.to_string(),
+            stats.user_coins.to_string(),
+            stats.demons.to_string(),
+            stats.stars.to_string(),
+            stats.secret_coins.to_string(),
+            u8::from(stats.icon_type).to_string(),
+            stats.icon_index.to_string(),
+            stats.diamonds.to_string(),
+            stats.cube_index.to_string(),
+            stats.ship_index.to_string(),
+            stats.ball_index.to_string(),
+            stats.ufo_index.to_string(),
+            stats.wave_index.to_string(),
+            stats.robot_index.to_string(),
+            u8::from(stats.has_glow).to_string(),
+            stats.spider_index.to_string(),
+            stats.death_effect_index.to_string(),
+        ];
+        let values: Vec<&str> = values.iter().map(String::as_str).collect();
+
+        chk(&values, USER_SCORE_SALT, USER_SCORE_XOR_KEY)
+    }
+
+    pub fn to_url(&self) -> String {
+        endpoint_url(UPDATE_USER_SCORE_ENDPOINT)
+    }
+}
+
+impl Serialize for UpdateUserScoreRequest<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("UpdateUserScoreRequest", 6)?;
+
+        state.serialize_field("base", &self.base)?;
+        state.serialize_field("authenticated_user", &self.authenticated_user)?;
+        state.serialize_field("userName", &self.user_name)?;
+        state.serialize_field("stats", &self.stats)?;
+        state.serialize_field("seed", &self.seed)?;
+        state.serialize_field("seed2", &self.seed2())?;
+
+        state.end()
+    }
+}
+
+request!(UpdateUserScoreRequest, UPDATE_USER_SCORE_ENDPOINT, Common);
+
+impl Display for UpdateUserScoreRequest<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self).map_err(|_| core::fmt::Error)?)
+    }
+}
+
+/// Struct modelled after a request to `updateGJAccSettings20.php`.
+///
+/// In the Geometry Dash API, this endpoint is used to change the privacy settings and social media
+/// handles of the authenticated account. Use
+/// [`parse_update_gj_acc_settings_response`](crate::response::parse_update_gj_acc_settings_response)
+/// to interpret the server's response.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, Hash)]
+pub struct AccountSettingsRequest<'a> {
+    /// The base request data
+    pub base: BaseRequest<'a>,
+
+    /// The account whose settings should be changed
+    pub authenticated_user: AuthenticatedUser<'a>,
+
+    /// Who can send messages to the account
+    ///
+    /// ## GD Internals:
+    /// This field is called `mS` in the boomlings API
+    #[serde(rename = "mS", serialize_with = "serialize_as_u8")]
+    pub messages: MessagePrivacy,
+
+    /// Who can send friend requests to the account
+    ///
+    /// ## GD Internals:
+    /// This field is called `frS` in the boomlings API
+    #[serde(rename = "frS", serialize_with = "serialize_as_u8")]
+    pub friend_requests: FriendRequestPrivacy,
+
+    /// Who can view the comment history of the account
+    ///
+    /// ## GD Internals:
+    /// This field is called `cS` in the boomlings API
+    #[serde(rename = "cS", serialize_with = "serialize_as_u8")]
+    pub comment_history: CommentHistoryPrivacy,
+
+    /// The YouTube channel ID of the account, or the empty string
+    ///
+    /// ## GD Internals:
+    /// This field is called `yt` in the boomlings API
+    #[serde(rename = "yt")]
+    pub youtube: Cow<'a, str>,
+
+    /// The Twitter handle of the account, or the empty string
+    pub twitter: Cow<'a, str>,
+
+    /// The Twitch handle of the account, or the empty string
+    pub twitch: Cow<'a, str>,
+}
+
+impl<'a> AccountSettingsRequest<'a> {
+    const_setter! {
+        /// Sets who can send messages to the account
+        ///
+        /// Allows builder-style creation of requests
+        messages: MessagePrivacy
+    }
+
+    const_setter! {
+        /// Sets who can send friend requests to the account
+        ///
+        /// Allows builder-style creation of requests
+        friend_requests: FriendRequestPrivacy
+    }
+
+    const_setter! {
+        /// Sets who can view the comment history of the account
+        ///
+        /// Allows builder-style creation of requests
+        comment_history: CommentHistoryPrivacy
+    }
+
+    /// Constructs a new `AccountSettingsRequest` allowing everyone to send messages and friend
+    /// requests and to view the comment history, and removing all social media handles.
+    ///
+    /// Uses a [`BaseRequest`] with the secret the Boomlings servers expect for account management,
+    /// which differs from the one used by most other endpoints.
+    pub const fn new(authenticated_user: AuthenticatedUser<'a>) -> Self {
+        Self::with_base(
+            BaseRequest {
+                secret: Secrets::BOOMLINGS.account,
+                ..GD_22
+            },
+            authenticated_user,
+        )
+    }
+
+    pub const fn with_base(base: BaseRequest<'a>, authenticated_user: AuthenticatedUser<'a>) -> Self {
+        AccountSettingsRequest {
+            base,
+            authenticated_user,
+            messages: MessagePrivacy::Everyone,
+            friend_requests: FriendRequestPrivacy::Everyone,
+            comment_history: CommentHistoryPrivacy::Everyone,
+            youtube: Cow::Borrowed(""),
+            twitter: Cow::Borrowed(""),
+            twitch: Cow::Borrowed(""),
+        }
+    }
+
+    /// Sets the YouTube channel ID of the account
+    ///
+    /// Allows builder-style creation of requests
+    pub fn youtube(mut self, youtube: impl Into<Cow<'a, str>>) -> Self {
+        self.youtube = youtube.into();
+        self
+    }
+
+    /// Sets the Twitter handle of the account
+    ///
+    /// Allows builder-style creation of requests
+    pub fn twitter(mut self, twitter: impl Into<Cow<'a, str>>) -> Self {
+        self.twitter = twitter.into();
+        self
+    }
+
+    /// Sets the Twitch handle of the account
+    ///
+    /// Allows builder-style creation of requests
+    pub fn twitch(mut self, twitch: impl Into<Cow<'a, str>>) -> Self {
+        self.twitch = twitch.into();
+        self
+    }
+
+    pub fn to_url(&self) -> String {
+        endpoint_url(UPDATE_ACCOUNT_SETTINGS_ENDPOINT)
+    }
+}
+
+request!(AccountSettingsRequest, UPDATE_ACCOUNT_SETTINGS_ENDPOINT, Account);
+
+impl Display for AccountSettingsRequest<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&super::to_string(self).map_err(|_| core::fmt::Error)?)
+    }
+}
+
+fn serialize_as_u8<T: Copy + Into<u8>, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u8((*value).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        model::user::{
+            profile::{CommentHistoryPrivacy, MessagePrivacy},
+            IconType,
+        },
+        request::{
+            user::{AccountSettingsRequest, UpdateUserScoreRequest, UserStats},
+            AuthenticatedUser,
+        },
+    };
+
+    const STATS: UserStats = UserStats {
+        stars: 5000,
+        moons: 20,
+        demons: 50,
+        diamonds: 12000,
+        secret_coins: 150,
+        user_coins: 100,
+        icon_type: IconType::Cube,
+        icon_index: 37,
+        primary_color: 3,
+        secondary_color: 12,
+        cube_index: 37,
+        ship_index: 12,
+        ball_index: 4,
+        ufo_index: 8,
+        wave_index: 9,
+        robot_index: 3,
+        has_glow: true,
+        spider_index: 5,
+        death_effect_index: 2,
+        swing_index: 1,
+        jetpack_index: 1,
+    };
+
+    #[test]
+    fn serialize_update_user_score() {
+        let request = UpdateUserScoreRequest::new(AuthenticatedUser::new(1710032, "hash"), "stadust", STATS);
+
+        assert_eq!(request.seed2(), "CgJXDwleBFYPAA0FUVZTW1EGBwYIVlFSCVwAUVYHXQ1UVVAJAQtTAQ==");
+        assert_eq!(
+            super::super::to_string(request).unwrap(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&accountID=1710032&gjp2=hash&userName=stadust&stars=5000&moons=20&\
+             demons=50&diamonds=12000&coins=150&userCoins=100&iconType=0&icon=37&color1=3&color2=12&accIcon=37&accShip=12&accBall=4&\
+             accBird=8&accDart=9&accRobot=3&accGlow=1&accSpider=5&accExplosion=2&accSwing=1&accJetpack=1&seed=dashrsseed&\
+             seed2=CgJXDwleBFYPAA0FUVZTW1EGBwYIVlFSCVwAUVYHXQ1UVVAJAQtTAQ=="
+        );
+    }
+
+    #[test]
+    fn serialize_account_settings() {
+        let request = AccountSettingsRequest::new(AuthenticatedUser::new(1710032, "hash"))
+            .messages(MessagePrivacy::FriendsOnly)
+            .comment_history(CommentHistoryPrivacy::Nobody)
+            .twitter("stadust1971");
+
+        assert_eq!(
+            super::super::to_string(request).unwrap(),
+            "gameVersion=22&binaryVersion=38&secret=Wmfv3899gc9&accountID=1710032&gjp2=hash&mS=1&frS=0&cS=2&yt=&twitter=stadust1971&\
+             twitch="
+        );
     }
 }
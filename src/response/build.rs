@@ -0,0 +1,75 @@
+//! Module containing functions for assembling complete server responses from dash-rs models, the
+//! inverse of the parsing functions in the [parent module](super)
+//!
+//! These are primarily intended for GDPS implementations and test servers, which need to produce
+//! responses that the game client accepts, including all sections and checksums.
+
+use alloc::{format, string::String, vec::Vec};
+
+use crate::{
+    crypto::level_search_hash,
+    model::{creator::Creator, level::Level, song::NewgroundsSong, user::profile::Profile},
+    response::{level_checksums, raw_level_field, Paged},
+    serde::GJFormat,
+    SerError,
+};
+
+/// Assembles the response to a `getGJLevels` request for the given page of levels
+///
+/// The given creators and songs are written into their respective sections as-is, meaning the
+/// caller is responsible for including the creators and custom songs of all levels on the page.
+///
+/// ## GD Internals:
+/// The response consists of five sections separated by `#`: The levels separated by `|`, the
+/// creators separated by `|`, the songs separated by `~:~`, the pagination information in the form
+/// `total:offset:page_size`, and finally a checksum over the levels (see
+/// [`level_search_hash`]).
+pub fn get_gj_levels_response(
+    levels: &Paged<Level<'_, ()>>, creators: &[Creator<'_>], songs: &[NewgroundsSong<'_>],
+) -> Result<String, SerError> {
+    let levels_section = join(&levels.items, "|")?;
+    let hash = level_search_hash(levels_section.split('|').filter(|level| !level.is_empty()).map(|level| {
+        (
+            raw_level_field(level, "1"),
+            raw_level_field(level, "18"),
+            raw_level_field(level, "38"),
+        )
+    }));
+
+    Ok(format!(
+        "{}#{}#{}#{}:{}:{}#{}",
+        levels_section,
+        join(creators, "|")?,
+        join(songs, "~:~")?,
+        levels.total,
+        levels.offset,
+        levels.page_size,
+        hash
+    ))
+}
+
+/// Assembles the response to a `downloadGJLevel` request for the given level
+///
+/// ## GD Internals:
+/// The response consists of the level, followed by the two checksums computed by
+/// [`level_data_hash`](crate::crypto::level_data_hash) and
+/// [`level_info_hash`](crate::crypto::level_info_hash), all separated by `#`.
+pub fn download_gj_level_response(level: &Level<'_>) -> Result<String, SerError> {
+    let level = level.to_robtop_string()?;
+    let (data_hash, info_hash) = level_checksums(&level).map_err(serde::ser::Error::custom)?;
+
+    Ok(format!("{}#{}#{}", level, data_hash, info_hash))
+}
+
+/// Assembles the response to a `getGJUserInfo` request for the given profile
+pub fn get_gj_user_info_response(profile: &Profile<'_>) -> Result<String, SerError> {
+    profile.to_robtop_string()
+}
+
+fn join<'de, T: GJFormat<'de>>(objects: &[T], separator: &str) -> Result<String, SerError> {
+    Ok(objects
+        .iter()
+        .map(GJFormat::to_robtop_string)
+        .collect::<Result<Vec<_>, _>>()?
+        .join(separator))
+}
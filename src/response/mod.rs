@@ -0,0 +1,913 @@
+//! Most likely temporary location of helper functions regarding the parsing of complete server
+//! responses.
+
+use alloc::{string::String, vec::Vec};
+use core::str::{FromStr, Split};
+
+use thiserror::Error;
+
+use crate::{
+    crypto::{level_data_hash, level_info_hash, xor_decode, CHALLENGES_XOR_KEY, LEVEL_PASSWORD_XOR_KEY, REWARDS_XOR_KEY},
+    model::{
+        comment::{
+            level::{CommentUser, LevelComment},
+            profile::ProfileComment,
+        },
+        creator::Creator,
+        level::{from_gj_str_with_compat, Level, LevelData, ListedLevel, ListedLevelWithData},
+        reward::{Chest, ChestContent, Cooldown, Quest, QuestKind, Quests, Rewards},
+        song::NewgroundsSong,
+        user::{profile::Profile, searched::SearchedUser, ModLevel},
+    },
+    request::{
+        comment::{COMMENT_HISTORY_ENDPOINT, LEVEL_COMMENTS_ENDPOINT, PROFILE_COMMENT_ENDPOINT},
+        compat_profile,
+        level::{DOWNLOAD_LEVEL_ENDPOINT, SEARCH_LEVEL_ENDPOINT},
+        user::{GET_USER_ENDPOINT, SEARCH_USER_ENDPOINT},
+        CompatProfile,
+    },
+    serde::{limits::Limits, GJFormat},
+    DeError,
+};
+
+pub mod build;
+
+/// The default value of [`Quirks::fragment_capture_limit`]
+pub const DEFAULT_FRAGMENT_CAPTURE_LIMIT: usize = 256;
+
+// Since NoneError is not stabilized, we cannot do `impl From<NoneError> for ResponseError<'_>`, so
+// this is the next best thing
+macro_rules! section {
+    ($iter:expr) => {
+        match $iter.next() {
+            Some(section) => section,
+            None => return Err(ResponseError::UnexpectedFormat),
+        }
+    };
+}
+
+#[derive(Debug, Error)]
+pub enum ResponseError<'a> {
+    /// A deserializer error occured while processing some object contained in the response
+    #[error("{0}")]
+    De(DeError<'a>), // cannot use #[from] here due to non-'static lifetime
+
+    /// A deserializer error occured while processing the given fragment of a response from the given
+    /// endpoint.
+    ///
+    /// The fragment is truncated to at most [`Quirks::fragment_capture_limit`] bytes.
+    #[error("{error} (endpoint: {endpoint}, key: {key:?}, fragment: {fragment:?})")]
+    MalformedFragment {
+        /// The endpoint whose response was being parsed
+        endpoint: &'static str,
+
+        /// The ID of the object that failed to parse (e.g. the level ID for levels), if it could be
+        /// found in the fragment. Together with the endpoint, this allows requesting the object
+        /// again to reproduce the failure.
+        key: Option<&'a str>,
+
+        /// The (possibly truncated) fragment of the response that failed to parse
+        fragment: &'a str,
+
+        /// The error that occurred
+        error: DeError<'a>,
+    },
+
+    /// The response was of the form `"-1"`, which is RobTop's version of `HTTP 404 NOT FOUND`
+    #[error("not found")]
+    NotFound,
+
+    /// The response was not worked in the expected way (too few sections, etc.)
+    #[error("unexpected format")]
+    UnexpectedFormat,
+
+    #[error("you have been IP banned by Cloudflare")]
+    IpBanned,
+
+    /// A checksum included in the response did not match the data it was computed from, meaning
+    /// the response was corrupted or tampered with
+    #[error("checksum mismatch (expected: {expected}, computed: {computed})")]
+    ChecksumMismatch {
+        /// The checksum included in the response
+        expected: &'a str,
+
+        /// The checksum computed from the response data
+        computed: String,
+    },
+}
+
+/// A single page of objects returned by some paginating endpoint, together with the pagination
+/// information the server provided
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paged<T> {
+    /// The objects on this page
+    pub items: Vec<T>,
+
+    /// The total amount of objects available across all pages
+    pub total: u32,
+
+    /// The index of the first object on this page among all available objects
+    pub offset: u32,
+
+    /// The maximal amount of objects per page
+    pub page_size: u32,
+}
+
+impl<T> Paged<T> {
+    /// The total amount of pages available
+    pub fn page_count(&self) -> u32 {
+        if self.page_size == 0 {
+            return 0;
+        }
+
+        self.total.div_ceil(self.page_size)
+    }
+
+    /// The (0-based) index of this page
+    pub fn page(&self) -> u32 {
+        if self.page_size == 0 {
+            return 0;
+        }
+
+        self.offset / self.page_size
+    }
+
+    /// Whether there are more pages available after this one
+    pub fn has_next_page(&self) -> bool {
+        self.offset.saturating_add(self.items.len() as u32) < self.total
+    }
+}
+
+/// The objects contained in some list in a response, parsed in lossy mode
+///
+/// In lossy mode, objects that fail to parse are skipped instead of failing the entire response,
+/// with the errors they caused collected separately. This is useful for GDPSes that return
+/// malformed data for only some of the objects in a response.
+#[derive(Debug)]
+pub struct Lossy<'a, T> {
+    /// The objects that were parsed successfully, in the order they appeared in the response
+    pub items: Vec<T>,
+
+    /// The errors caused by the objects that failed to parse
+    pub errors: Vec<ResponseError<'a>>,
+}
+
+impl<'a, T> Lossy<'a, T> {
+    fn collect(results: impl Iterator<Item = Result<T, ResponseError<'a>>>) -> Self {
+        let mut lossy = Lossy {
+            items: Vec::new(),
+            errors: Vec::new(),
+        };
+
+        for result in results {
+            match result {
+                Ok(item) => lossy.items.push(item),
+                Err(error) => lossy.errors.push(error),
+            }
+        }
+
+        lossy
+    }
+
+    /// Whether all objects were parsed successfully
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Parses the `total:offset:page_size` trailer paginating endpoints append to their responses
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+fn parse_pagination<T>(items: Vec<T>, trailer: &str) -> Result<Paged<T>, ResponseError<'static>> {
+    let mut parts = trailer.split(':').map(|part| part.parse::<u32>());
+
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(total)), Some(Ok(offset)), Some(Ok(page_size))) => Ok(Paged {
+            items,
+            total,
+            offset,
+            page_size,
+        }),
+        _ => Err(ResponseError::UnexpectedFormat),
+    }
+}
+
+impl<'a> From<DeError<'a>> for ResponseError<'a> {
+    fn from(err: DeError<'a>) -> Self {
+        ResponseError::De(err)
+    }
+}
+
+/// Logs the failure to parse the given fragment of a response and, if enabled, attaches the fragment
+/// (truncated to `limit` bytes) to the returned error
+fn malformed_fragment<'a>(
+    endpoint: &'static str, key: Option<&'a str>, fragment: &'a str, error: DeError<'a>, limit: usize,
+) -> ResponseError<'a> {
+    let index = match &error {
+        DeError::Custom { index, .. } => *index,
+        _ => None,
+    };
+
+    // Truncate before logging, so that neither `log` nor `tracing` ever receive more than `limit`
+    // bytes of the fragment
+    let mut end = limit.min(fragment.len());
+
+    while !fragment.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    let fragment = &fragment[..end];
+
+    log::warn!(
+        "Failed to parse fragment of {} response (key {:?}) at index {:?}: {}. Fragment was {:?}",
+        endpoint,
+        key,
+        index,
+        error,
+        fragment
+    );
+
+    #[cfg(feature = "tracing")]
+    tracing::warn!(endpoint, key, index, fragment, %error, "failed to parse response fragment");
+
+    if limit == 0 {
+        return ResponseError::De(error);
+    }
+
+    ResponseError::MalformedFragment {
+        endpoint,
+        key,
+        fragment,
+        error,
+    }
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_get_gj_levels_response(response: &str) -> Result<Vec<ListedLevel>, ResponseError> {
+    check_response_errors(response)?;
+
+    parse_listed_levels(&mut response.split('#'), Quirks::default())?.collect()
+}
+
+/// Like [`parse_get_gj_levels_response`], but in [`Lossy`] mode
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_get_gj_levels_response_lossy(response: &str) -> Result<Lossy<ListedLevel>, ResponseError> {
+    check_response_errors(response)?;
+
+    Ok(Lossy::collect(parse_listed_levels(&mut response.split('#'), Quirks::default())?))
+}
+
+/// Like [`parse_get_gj_levels_response`], but additionally parses the pagination information
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_get_gj_levels_response_paged(response: &str) -> Result<Paged<ListedLevel>, ResponseError> {
+    check_response_errors(response)?;
+
+    let mut sections = response.split('#');
+
+    let levels = parse_listed_levels(&mut sections, Quirks::default())?.collect::<Result<_, _>>()?;
+
+    parse_pagination(levels, section!(sections))
+}
+
+/// Quirks of private servers ("GDPS") that the response parsers can optionally handle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Quirks {
+    /// Some GDPS forks return the full level data (the `levelString` at index `4`, together with the
+    /// password and upload/update times) inline in search results. If set, such levels are parsed
+    /// into a [`Level`] with [`LevelData`] instead of dropping the data.
+    pub inline_level_data: bool,
+
+    /// The maximum length (in bytes) of the raw response fragment attached to a
+    /// [`ResponseError::MalformedFragment`] (and logged) when parsing some object contained in a
+    /// response fails. Parsers not taking [`Quirks`] use [`DEFAULT_FRAGMENT_CAPTURE_LIMIT`].
+    ///
+    /// Setting this to `0` disables capturing of fragments, in which case [`ResponseError::De`] is
+    /// returned instead. Since fragments are simply sub-slices of the response, capturing them does
+    /// not cause any allocations.
+    pub fragment_capture_limit: usize,
+
+    /// The [`Limits`] to enforce while parsing the response
+    ///
+    /// Lazily processed fields (such as level data) are not processed during parsing, pass these
+    /// limits to [`Thunk::process_with_limits`](crate::Thunk::process_with_limits) when processing them.
+    pub limits: Limits,
+
+    /// The version of the game the server implements, which determines whether the indices
+    /// introduced in later versions are allowed to be missing from levels.
+    ///
+    /// Defaults to the global [`compat_profile`]. When talking to a specific server, use
+    /// [`ServerConfig::quirks`](crate::request::ServerConfig::quirks) instead.
+    pub compat: CompatProfile,
+}
+
+impl Default for Quirks {
+    fn default() -> Self {
+        Quirks {
+            inline_level_data: false,
+            fragment_capture_limit: DEFAULT_FRAGMENT_CAPTURE_LIMIT,
+            limits: Limits::DEFAULT,
+            compat: compat_profile(),
+        }
+    }
+}
+
+/// Like [`parse_get_gj_levels_response`], but handles the given [`Quirks`]
+///
+/// If [`Quirks::inline_level_data`] is set, the level data of each level is populated if the server
+/// included it in the response. Otherwise it is always [`None`].
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_get_gj_levels_response_with_quirks(response: &str, quirks: Quirks) -> Result<Vec<ListedLevelWithData>, ResponseError> {
+    check_response_errors(response)?;
+
+    let mut sections = response.split('#');
+
+    let levels = section!(sections);
+    let (creators, songs) = parse_creators_and_songs(&mut sections, quirks)?;
+
+    levels
+        .split('|')
+        .map(|fragment| {
+            if quirks.inline_level_data {
+                if let Ok(level) = from_gj_str_with_compat::<Level<LevelData>>(fragment, &quirks.limits, quirks.compat) {
+                    return Ok(attach_creator_and_song(level.map_data(Some), &creators, &songs));
+                }
+            }
+
+            let level: Level<()> = from_gj_str_with_compat(fragment, &quirks.limits, quirks.compat).map_err(|err| {
+                malformed_fragment(
+                    SEARCH_LEVEL_ENDPOINT,
+                    level_key(fragment),
+                    fragment,
+                    err,
+                    quirks.fragment_capture_limit,
+                )
+            })?;
+
+            Ok(attach_creator_and_song(level.with_data(None), &creators, &songs))
+        })
+        .collect::<Result<_, _>>()
+}
+
+fn attach_creator_and_song<'a, Data>(
+    level: Level<'a, Data, Option<u64>, u64>, creators: &[Creator<'a>], songs: &[NewgroundsSong<'a>],
+) -> Level<'a, Data, Option<NewgroundsSong<'a>>, Option<Creator<'a>>> {
+    // Note: Cloning is cheap because none of the Thunks is evaluated, so we only have references lying
+    // around.
+    let creator = creators.iter().find(|creator| creator.user_id == level.creator).cloned();
+    let song = level
+        .custom_song
+        .and_then(|song_id| songs.iter().find(|song| song.song_id == song_id))
+        .cloned();
+
+    level.with_creator(creator).with_custom_song(song)
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+fn parse_creators_and_songs<'a>(
+    sections: &mut Split<'a, char>, quirks: Quirks,
+) -> Result<(Vec<Creator<'a>>, Vec<NewgroundsSong<'a>>), ResponseError<'a>> {
+    let creators = section!(sections)
+        .split('|')
+        .filter(|s| !s.is_empty()) // It can happen that segments are completely empty. In this case, split returns an iterator that yields `Some("")`, which would cause an error since the empty string is not parsable
+        .map(|fragment| {
+            // Creators are not map-like, their first field is the user ID
+            let key = fragment.split(':').next();
+
+            Creator::from_gj_str_with_limits(fragment, &quirks.limits)
+                .map_err(|err| malformed_fragment(SEARCH_LEVEL_ENDPOINT, key, fragment, err, quirks.fragment_capture_limit))
+        })
+        .collect::<Result<Vec<Creator>, _>>()?;
+    let songs = section!(sections)
+        .split("~:~")
+        .filter(|s| !s.is_empty())
+        .map(|fragment| {
+            let key = lookup_raw_field(fragment, "~|~", "1");
+
+            NewgroundsSong::from_gj_str_with_limits(fragment, &quirks.limits)
+                .map_err(|err| malformed_fragment(SEARCH_LEVEL_ENDPOINT, key, fragment, err, quirks.fragment_capture_limit))
+        })
+        .collect::<Result<Vec<NewgroundsSong>, _>>()?;
+
+    Ok((creators, songs))
+}
+
+/// Parses the levels of a level search response (together with the creators and songs they
+/// reference). The levels themselves are parsed lazily, each in its own trace span.
+fn parse_listed_levels<'a>(
+    sections: &mut Split<'a, char>, quirks: Quirks,
+) -> Result<impl Iterator<Item = Result<ListedLevel<'a>, ResponseError<'a>>>, ResponseError<'a>> {
+    let levels = section!(sections);
+    let (creators, songs) = parse_creators_and_songs(sections, quirks)?;
+
+    Ok(levels.split('|').map(move |fragment| {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("parse_listed_level", key = level_key(fragment)).entered();
+
+        let level: Level<()> = from_gj_str_with_compat(fragment, &quirks.limits, quirks.compat).map_err(|err| {
+            malformed_fragment(
+                SEARCH_LEVEL_ENDPOINT,
+                level_key(fragment),
+                fragment,
+                err,
+                quirks.fragment_capture_limit,
+            )
+        })?;
+
+        Ok(attach_creator_and_song(level, &creators, &songs))
+    }))
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_download_gj_level_response(response: &str) -> Result<Level, ResponseError> {
+    parse_download_gj_level_response_with_quirks(response, Quirks::default())
+}
+
+/// Like [`parse_download_gj_level_response`], but handles the given [`Quirks`]
+///
+/// [`Quirks::inline_level_data`] has no effect here, as downloaded levels always contain their data.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_download_gj_level_response_with_quirks(response: &str, quirks: Quirks) -> Result<Level, ResponseError> {
+    check_response_errors(response)?;
+
+    let mut sections = response.split('#');
+    let section = section!(sections);
+
+    from_gj_str_with_compat(section, &quirks.limits, quirks.compat).map_err(|err| {
+        malformed_fragment(
+            DOWNLOAD_LEVEL_ENDPOINT,
+            level_key(section),
+            section,
+            err,
+            quirks.fragment_capture_limit,
+        )
+    })
+}
+
+/// Like [`parse_download_gj_level_response`], but additionally verifies the checksums the servers
+/// append to the level, see [`verify_level_checksums`]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_download_gj_level_response_checked(response: &str) -> Result<Level, ResponseError> {
+    check_response_errors(response)?;
+
+    let mut sections = response.split('#');
+    let section = section!(sections);
+
+    verify_level_checksums(section, section!(sections), section!(sections))?;
+
+    Level::from_gj_str(section).map_err(|err| {
+        malformed_fragment(
+            DOWNLOAD_LEVEL_ENDPOINT,
+            level_key(section),
+            section,
+            err,
+            DEFAULT_FRAGMENT_CAPTURE_LIMIT,
+        )
+    })
+}
+
+/// Verifies the checksums the servers append to the response of a level download against the
+/// (raw) level they were computed from, returning [`ResponseError::ChecksumMismatch`] if either
+/// does not match.
+///
+/// See [`level_data_hash`] and [`level_info_hash`] for how the checksums are computed.
+pub fn verify_level_checksums<'a>(level: &'a str, data_hash: &'a str, info_hash: &'a str) -> Result<(), ResponseError<'a>> {
+    let (computed_data_hash, computed_info_hash) = level_checksums(level)?;
+
+    if computed_data_hash != data_hash {
+        return Err(ResponseError::ChecksumMismatch {
+            expected: data_hash,
+            computed: computed_data_hash,
+        });
+    }
+
+    if computed_info_hash != info_hash {
+        return Err(ResponseError::ChecksumMismatch {
+            expected: info_hash,
+            computed: computed_info_hash,
+        });
+    }
+
+    Ok(())
+}
+
+/// Computes the checksums the servers append to the response of a level download from the (raw)
+/// level
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+fn level_checksums(level: &str) -> Result<(String, String), ResponseError<'static>> {
+    let field = |index| raw_level_field(level, index);
+
+    let password = match field("27") {
+        "" | "0" => String::from("0"),
+        encoded => match xor_decode(encoded, LEVEL_PASSWORD_XOR_KEY)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+        {
+            Some(password) => password,
+            None => return Err(ResponseError::UnexpectedFormat),
+        },
+    };
+    let demon = if field("17") == "1" { "1" } else { "0" };
+    let daily_id = match field("41") {
+        "" => "0",
+        id => id,
+    };
+
+    let info_hash = level_info_hash(&[
+        field("6"),
+        field("18"),
+        demon,
+        field("1"),
+        field("38"),
+        field("19"),
+        &password,
+        daily_id,
+    ]);
+
+    Ok((level_data_hash(field("4")), info_hash))
+}
+
+/// The raw value of the given index in the given level, or the empty string if it is not present
+fn raw_level_field<'a>(level: &'a str, index: &str) -> &'a str {
+    lookup_raw_field(level, ":", index).unwrap_or_default()
+}
+
+/// The raw value of the given index in the given map-like fragment, or [`None`] if it is not present
+fn lookup_raw_field<'a>(fragment: &'a str, delimiter: &str, index: &str) -> Option<&'a str> {
+    let mut fields = fragment.split(delimiter);
+
+    while let (Some(key), Some(value)) = (fields.next(), fields.next()) {
+        if key == index {
+            return Some(value);
+        }
+    }
+
+    None
+}
+
+/// The level ID contained in the given raw level, see [`ResponseError::MalformedFragment::key`]
+fn level_key(level: &str) -> Option<&str> {
+    lookup_raw_field(level, ":", "1")
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_get_gj_user_info_response(response: &str) -> Result<Profile, ResponseError> {
+    check_response_errors(response)?;
+
+    Profile::from_gj_str(response).map_err(|err| {
+        malformed_fragment(
+            GET_USER_ENDPOINT,
+            lookup_raw_field(response, ":", "2"),
+            response,
+            err,
+            DEFAULT_FRAGMENT_CAPTURE_LIMIT,
+        )
+    })
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_get_gj_users_response(response: &str) -> Result<SearchedUser, ResponseError> {
+    check_response_errors(response)?;
+
+    let mut sections = response.split('#');
+
+    // In the past this used to be a paginating endpoint which performed an infix search on the user
+    // name. Now, it performs a full match, and since account names are unique, this endpoint returns at
+    // most one object anymore.
+    let section = section!(sections);
+
+    SearchedUser::from_gj_str(section).map_err(|err| {
+        malformed_fragment(
+            SEARCH_USER_ENDPOINT,
+            lookup_raw_field(section, ":", "2"),
+            section,
+            err,
+            DEFAULT_FRAGMENT_CAPTURE_LIMIT,
+        )
+    })
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_get_gj_comments_response(response: &str) -> Result<Vec<LevelComment>, ResponseError> {
+    check_response_errors(response)?;
+
+    let mut sections = response.split('#');
+
+    parse_level_comments(section!(sections), LEVEL_COMMENTS_ENDPOINT).collect()
+}
+
+/// Like [`parse_get_gj_comments_response`], but in [`Lossy`] mode
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_get_gj_comments_response_lossy(response: &str) -> Result<Lossy<LevelComment>, ResponseError> {
+    check_response_errors(response)?;
+
+    let mut sections = response.split('#');
+
+    Ok(Lossy::collect(parse_level_comments(section!(sections), LEVEL_COMMENTS_ENDPOINT)))
+}
+
+/// Like [`parse_get_gj_comments_response`], but additionally parses the pagination information
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_get_gj_comments_response_paged(response: &str) -> Result<Paged<LevelComment>, ResponseError> {
+    check_response_errors(response)?;
+
+    let mut sections = response.split('#');
+
+    let comments = parse_level_comments(section!(sections), LEVEL_COMMENTS_ENDPOINT).collect::<Result<_, _>>()?;
+
+    parse_pagination(comments, section!(sections))
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_get_gj_comment_history_response(response: &str) -> Result<Paged<LevelComment>, ResponseError> {
+    check_response_errors(response)?;
+
+    let mut sections = response.split('#');
+
+    let comments = parse_level_comments(section!(sections), COMMENT_HISTORY_ENDPOINT).collect::<Result<_, _>>()?;
+
+    parse_pagination(comments, section!(sections))
+}
+
+fn parse_level_comments<'a>(section: &'a str, endpoint: &'static str) -> impl Iterator<Item = Result<LevelComment<'a>, ResponseError<'a>>> {
+    // The format here is very weird. We have a '|' separated list of (comment, user) pairs, and said
+    // pair is separated by a ':'
+
+    section.split('|').map(move |fragment| {
+        let mut parts = fragment.split(':');
+
+        if let (Some(raw_comment), Some(raw_user)) = (parts.next(), parts.next()) {
+            // The user does not contain any ID, so failures are attributed to the comment
+            let key = lookup_raw_field(raw_comment, "~", "6");
+
+            #[cfg(feature = "tracing")]
+            let _span = tracing::trace_span!("parse_level_comment", endpoint, key).entered();
+
+            let mut comment = LevelComment::from_gj_str(raw_comment)
+                .map_err(|err| malformed_fragment(endpoint, key, raw_comment, err, DEFAULT_FRAGMENT_CAPTURE_LIMIT))?;
+
+            comment.user = if raw_user == "1~~9~~10~~11~~14~~15~~16~" {
+                None
+            } else {
+                Some(
+                    CommentUser::from_gj_str(raw_user)
+                        .map_err(|err| malformed_fragment(endpoint, key, raw_user, err, DEFAULT_FRAGMENT_CAPTURE_LIMIT))?,
+                )
+            };
+
+            Ok(comment)
+        } else {
+            Err(ResponseError::UnexpectedFormat)
+        }
+    })
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_get_gj_acccount_comments_response(response: &str) -> Result<Vec<ProfileComment>, ResponseError> {
+    check_response_errors(response)?;
+
+    let mut sections = response.split('#');
+
+    parse_profile_comments(section!(sections)).collect()
+}
+
+/// Like [`parse_get_gj_acccount_comments_response`], but in [`Lossy`] mode
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_get_gj_acccount_comments_response_lossy(response: &str) -> Result<Lossy<ProfileComment>, ResponseError> {
+    check_response_errors(response)?;
+
+    let mut sections = response.split('#');
+
+    Ok(Lossy::collect(parse_profile_comments(section!(sections))))
+}
+
+/// Like [`parse_get_gj_acccount_comments_response`], but additionally parses the pagination
+/// information
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_get_gj_acccount_comments_response_paged(response: &str) -> Result<Paged<ProfileComment>, ResponseError> {
+    check_response_errors(response)?;
+
+    let mut sections = response.split('#');
+
+    let comments = parse_profile_comments(section!(sections)).collect::<Result<_, _>>()?;
+
+    parse_pagination(comments, section!(sections))
+}
+
+fn parse_profile_comments(section: &str) -> impl Iterator<Item = Result<ProfileComment, ResponseError>> {
+    section.split('|').map(|fragment| {
+        let key = lookup_raw_field(fragment, "~", "6");
+
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("parse_profile_comment", key).entered();
+
+        ProfileComment::from_gj_str(fragment)
+            .map_err(|err| malformed_fragment(PROFILE_COMMENT_ENDPOINT, key, fragment, err, DEFAULT_FRAGMENT_CAPTURE_LIMIT))
+    })
+}
+
+/// The outcome of a request performing some action on the server, such as deleting content or
+/// updating a level's description
+///
+/// ## GD Internals:
+/// The servers respond to all such requests in the same way: `1` if the action was performed, and
+/// a negative error code otherwise. Any other response is rejected as
+/// [`ResponseError::UnexpectedFormat`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ActionOutcome {
+    /// The action was performed
+    ///
+    /// ## GD Internals:
+    /// This variant is represented by the value `1`
+    Done,
+
+    /// The server refused to perform the action, for example because the content does not exist,
+    /// is not owned by the authenticated account, or the provided credentials were wrong
+    ///
+    /// ## GD Internals:
+    /// The official servers use `-1` for all failures, however some GDPS implementations use other
+    /// negative values to indicate specific failure reasons. The value returned by the server is
+    /// contained in this variant.
+    Rejected(i32),
+}
+
+/// The outcome of a request deleting some content, see [`ActionOutcome`]
+pub type DeletionOutcome = ActionOutcome;
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_delete_gj_acc_comment_response(response: &str) -> Result<DeletionOutcome, ResponseError> {
+    parse_action_response(response)
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_delete_gj_comment_response(response: &str) -> Result<DeletionOutcome, ResponseError> {
+    parse_action_response(response)
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_delete_gj_level_user_response(response: &str) -> Result<DeletionOutcome, ResponseError> {
+    parse_action_response(response)
+}
+
+/// Parses the response to a request submitting the statistics of an account, returning the user ID
+/// of the account
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_update_gj_user_score_response(response: &str) -> Result<u64, ResponseError> {
+    check_response_errors(response)?;
+
+    response.trim().parse().map_err(|_| ResponseError::UnexpectedFormat)
+}
+
+/// Parses the response to a request changing an account's settings
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_update_gj_acc_settings_response(response: &str) -> Result<ActionOutcome, ResponseError> {
+    parse_action_response(response)
+}
+
+/// Parses the response to a request changing a level's description
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_update_gj_desc_response(response: &str) -> Result<ActionOutcome, ResponseError> {
+    parse_action_response(response)
+}
+
+fn parse_action_response(response: &str) -> Result<ActionOutcome, ResponseError> {
+    if response == "error code: 1005" {
+        return Err(ResponseError::IpBanned);
+    }
+
+    match response.trim().parse::<i32>() {
+        Ok(1) => Ok(ActionOutcome::Done),
+        Ok(code) if code < 0 => Ok(ActionOutcome::Rejected(code)),
+        _ => Err(ResponseError::UnexpectedFormat),
+    }
+}
+
+/// Parses the response to a [`RequestModAccessRequest`](crate::request::user::RequestModAccessRequest)
+///
+/// The server responds with `-1` if the authenticated account is not a moderator (or the provided
+/// credentials were wrong), which is mapped to [`ModLevel::None`]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_request_user_access_response(response: &str) -> Result<ModLevel, ResponseError> {
+    if response == "error code: 1005" {
+        return Err(ResponseError::IpBanned);
+    }
+
+    match response.trim().parse::<i8>() {
+        Ok(level) if level <= 0 => Ok(ModLevel::None),
+        Ok(level) => Ok(ModLevel::from(level as u8)),
+        Err(_) => Err(ResponseError::UnexpectedFormat),
+    }
+}
+
+/// Parses the response of the `getGJRewards` endpoint
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_get_gj_rewards_response(response: &str) -> Result<Rewards, ResponseError> {
+    check_response_errors(response)?;
+
+    let payload = decode_reward_payload(response, REWARDS_XOR_KEY)?;
+    let fields: Vec<&str> = payload.split(':').collect();
+
+    let chest = |offset: usize| -> Result<Chest, ResponseError<'static>> {
+        let mut content = [0u32; 4];
+
+        for (slot, value) in content.iter_mut().zip(raw_field(&fields, offset + 1)?.split(',')) {
+            *slot = parse_or_zero(value)?;
+        }
+
+        Ok(Chest {
+            cooldown: Cooldown::new(parse_field(&fields, offset)?),
+            content: ChestContent {
+                orbs: content[0],
+                diamonds: content[1],
+                items: [content[2], content[3]],
+            },
+            times_opened: parse_field(&fields, offset + 2)?,
+        })
+    };
+
+    Ok(Rewards {
+        user_id: parse_field(&fields, 1)?,
+        account_id: parse_field(&fields, 4)?,
+        small_chest: chest(5)?,
+        large_chest: chest(8)?,
+        reward_type: fields.get(11).map(|value| parse_or_zero(value)).transpose()?.unwrap_or_default(),
+    })
+}
+
+/// Parses the response of the `getGJChallenges` endpoint
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(length = response.len())))]
+pub fn parse_get_gj_challenges_response(response: &str) -> Result<Quests, ResponseError> {
+    check_response_errors(response)?;
+
+    let payload = decode_reward_payload(response, CHALLENGES_XOR_KEY)?;
+    let fields: Vec<&str> = payload.split(':').collect();
+
+    let quests = fields
+        .iter()
+        .skip(6)
+        .filter(|quest| !quest.is_empty())
+        .map(|quest| {
+            let parts: Vec<&str> = quest.splitn(5, ',').collect();
+
+            Ok(Quest {
+                quest_id: parse_field(&parts, 0)?,
+                kind: QuestKind::from(parse_field::<u8>(&parts, 1)?),
+                amount: parse_field(&parts, 2)?,
+                reward: parse_field(&parts, 3)?,
+                name: String::from(raw_field(&parts, 4)?),
+            })
+        })
+        .collect::<Result<_, ResponseError>>()?;
+
+    Ok(Quests {
+        user_id: parse_field(&fields, 1)?,
+        account_id: parse_field(&fields, 4)?,
+        next_quest: Cooldown::new(parse_field(&fields, 5)?),
+        quests,
+    })
+}
+
+/// Decodes the payload of a `getGJRewards` or `getGJChallenges` response
+///
+/// ## GD Internals:
+/// These responses are of the form `<5 random characters><payload>|<hash>`, where the payload is
+/// XOR-ed with an endpoint specific key and then urlsafe base64 encoded. The decoded payload is a
+/// `:` separated list of values, the first of which is again a random string.
+fn decode_reward_payload(response: &str, key: &str) -> Result<String, ResponseError<'static>> {
+    let payload = response
+        .split('|')
+        .next()
+        .and_then(|payload| payload.get(5..))
+        .ok_or(ResponseError::UnexpectedFormat)?;
+
+    xor_decode(payload, key)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .ok_or(ResponseError::UnexpectedFormat)
+}
+
+fn raw_field<'a>(fields: &[&'a str], index: usize) -> Result<&'a str, ResponseError<'static>> {
+    fields.get(index).copied().ok_or(ResponseError::UnexpectedFormat)
+}
+
+fn parse_field<T: FromStr>(fields: &[&str], index: usize) -> Result<T, ResponseError<'static>> {
+    raw_field(fields, index)?.parse().map_err(|_| ResponseError::UnexpectedFormat)
+}
+
+/// Parses the given value, treating the empty string as `0`
+fn parse_or_zero<T: FromStr + Default>(value: &str) -> Result<T, ResponseError<'static>> {
+    match value {
+        "" => Ok(T::default()),
+        value => value.parse().map_err(|_| ResponseError::UnexpectedFormat),
+    }
+}
+
+fn check_response_errors(response: &str) -> Result<(), ResponseError> {
+    if response == "-1" {
+        return Err(ResponseError::NotFound);
+    }
+
+    if response == "error code: 1005" {
+        return Err(ResponseError::IpBanned);
+    }
+
+    Ok(())
+}
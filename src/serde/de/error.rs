@@ -1,9 +1,12 @@
 //! Module containing the error type for deserialization errors
 
-use std::fmt::Display;
+use alloc::string::{String, ToString};
+use core::fmt::Display;
 
 use thiserror::Error;
 
+use crate::serde::limits::LimitExceeded;
+
 /// Errors that can occur during deserialization
 #[derive(Debug, Error)]
 pub enum Error<'de> {
@@ -15,7 +18,7 @@ pub enum Error<'de> {
     Eof,
 
     /// Some custom error happened while deserializing the data at the specified index.
-    #[error("{value:?} at index {index:?} caused {message}")]
+    #[error("{value:?} at index {index:?} (offset {offset:?}) caused {message}")]
     Custom {
         /// The error message
         message: String,
@@ -31,11 +34,21 @@ pub enum Error<'de> {
         /// Not available if the error is not related to any value (for instance if the format
         /// itself was malformed).
         value: Option<&'de str>,
+
+        /// The byte offset of the value that caused the error within the input passed to the
+        /// deserializer
+        ///
+        /// Only available if [`Error::Custom::value`] is available.
+        offset: Option<usize>,
     },
 
     /// A given [`Deserializer`](serde::Deserializer) function was not supported
     #[error("unsupported deserializer function: {0}")]
     Unsupported(&'static str),
+
+    /// The input exceeded one of the configured [`limits`](crate::limits)
+    #[error("{0}")]
+    LimitExceeded(#[from] LimitExceeded),
 }
 
 impl serde::de::Error for Error<'_> {
@@ -47,6 +60,7 @@ impl serde::de::Error for Error<'_> {
             message: msg.to_string(),
             index: None,
             value: None,
+            offset: None,
         }
     }
 }
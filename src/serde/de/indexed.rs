@@ -1,12 +1,14 @@
 //! Module containing the deserializer for robtop's indexed data format
 
 use super::error::Error;
+use crate::serde::UnknownIndices;
+use alloc::{borrow::ToOwned, string::ToString, vec::Vec};
+use core::str::Split;
 use serde::{
     de,
     de::{DeserializeSeed, Visitor},
     Deserializer,
 };
-use std::str::Split;
 
 // Special versions of the trace and debug macros used in this module that are statically disabled
 // in release mode. We do not want to explicitly pass "release_max_level_off" feature to log because
@@ -39,13 +41,95 @@ macro_rules! dev_debug {
 /// * **List-like**: There are no keys, identification of
 ///   fields has to occur based on the how many-th field they are. In this case the deserializer
 ///   generates artificial indices (which just count up by 1 for each field) for error messages.
+///
+/// Sequences and maps that appear as a field of some object (such as a list of level IDs) are
+/// stored in a single field, with their elements separated by a secondary delimiter (by default
+/// `","`). See [`IndexedDeserializer::with_sub_delimiter`].
 #[derive(Debug)]
 pub struct IndexedDeserializer<'de> {
     map_like: bool,
     splitter: Split<'de, &'de str>,
     input: &'de str,
+
+    /// The input of the top level deserializer, relative to which the offsets of values in errors
+    /// are computed. Identical to `input` unless this is the deserializer of a nested sequence or
+    /// map
+    root: &'de str,
     end_of_current_token: usize,
     delimiter: &'de str,
+    sub_delimiter: Option<&'static str>,
+
+    /// Value indicating whether deserialization of the top level object has already started, in
+    /// which case any further sequences or maps are nested ones
+    nested: bool,
+
+    /// The key-value pairs of all ignored indices, each together with the last known index that
+    /// preceded it, if capturing them is enabled. See [`IndexedDeserializer::capture_unknown_indices`]
+    unknown: Option<Vec<(Option<&'de str>, &'de str, &'de str)>>,
+
+    /// The last index of the map-like top level object that was not ignored
+    last_known_index: Option<&'de str>,
+
+    /// Lookup table from the numeric keys of the map-like top level struct to the positions of the
+    /// corresponding fields, see [`KeyTable`]
+    keys: Option<KeyTable>,
+
+    /// Value indicating whether the next call to `deserialize_identifier` is for a map key
+    expecting_key: bool,
+}
+
+/// Lookup table mapping the numeric keys of a map-like struct to the positions of the fields they
+/// belong to, in the order serde passes them to [`Deserializer::deserialize_struct`].
+///
+/// serde's generated field identifiers resolve string keys by comparing them against every field
+/// name in turn, which for structs with dozens of fields (such as levels) makes up a significant
+/// part of the deserialization time. Since virtually all keys in RobTop's formats are small
+/// integers, we instead parse each key once and feed the field position straight to the
+/// identifier's `visit_u64`. Keys that are not in the table (because they are unknown, not numeric
+/// or too large) still go through `visit_borrowed_str`.
+///
+/// Note that this relies on the field positions matching the order of `fields`, which serde only
+/// guarantees as long as no field has a `#[serde(alias)]`. Aliases are listed in `fields` alongside
+/// the actual names, so if a key occurs more than once we assume the positions to be off and do not
+/// construct a table at all.
+#[derive(Debug)]
+struct KeyTable([u8; KeyTable::SIZE]);
+
+impl KeyTable {
+    const SIZE: usize = 128;
+
+    fn new(fields: &[&str]) -> Option<Self> {
+        // Position 0 is used to mark keys without a field
+        if fields.len() >= u8::MAX as usize {
+            return None;
+        }
+
+        let mut table = [0; KeyTable::SIZE];
+
+        for (position, field) in fields.iter().enumerate() {
+            if let Some(slot) = field.parse::<usize>().ok().and_then(|key| table.get_mut(key)) {
+                if *slot != 0 {
+                    return None;
+                }
+
+                *slot = position as u8 + 1;
+            }
+        }
+
+        Some(KeyTable(table))
+    }
+
+    fn position_of(&self, key: &str) -> Option<u64> {
+        // Only plain decimal digits, so that e.g. "+1" or "01" are not mistaken for "1"
+        if key.is_empty() || key.len() > 3 || (key.len() > 1 && key.starts_with('0')) || !key.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        match *self.0.get(key.parse::<usize>().ok()?)? {
+            0 => None,
+            position => Some(position as u64 - 1),
+        }
+    }
 }
 
 impl<'de> IndexedDeserializer<'de> {
@@ -63,11 +147,60 @@ impl<'de> IndexedDeserializer<'de> {
             splitter: source.split(delimiter),
             map_like,
             input: source,
+            root: source,
             end_of_current_token: source.as_ptr() as usize,
             delimiter,
+            sub_delimiter: Some(","),
+            nested: false,
+            unknown: None,
+            last_known_index: None,
+            keys: None,
+            expecting_key: false,
         }
     }
 
+    /// Makes this deserializer record the key-value pairs of all indices of a map-like top level
+    /// object that the deserialization target ignored, together with their positions relative to the
+    /// indices it did not ignore. They can be retrieved via
+    /// [`IndexedDeserializer::take_unknown_indices`]
+    pub fn capture_unknown_indices(mut self) -> Self {
+        self.unknown = Some(Vec::new());
+        self
+    }
+
+    /// Returns the key-value pairs recorded since capturing was enabled via
+    /// [`IndexedDeserializer::capture_unknown_indices`]. Empty if capturing is not enabled.
+    pub fn take_unknown_indices(&mut self) -> UnknownIndices<'de> {
+        UnknownIndices::from_captured(self.unknown.as_mut().map(core::mem::take).unwrap_or_default())
+    }
+
+    /// Sets the delimiter separating the elements of sequences and maps nested inside the top level
+    /// object
+    pub fn with_sub_delimiter(mut self, sub_delimiter: &'static str) -> Self {
+        self.sub_delimiter = Some(sub_delimiter);
+        self
+    }
+
+    /// Constructs a deserializer for the elements of a sequence or map that is contained in a single
+    /// field of the top level object
+    fn nested(&mut self) -> Result<IndexedDeserializer<'de>, Error<'de>> {
+        let sub_delimiter = self.sub_delimiter.ok_or(Error::Unsupported("doubly nested sequence or map"))?;
+        let token = self.consume_token().ok_or(Error::Eof)?;
+
+        let mut deserializer = IndexedDeserializer::new(token, sub_delimiter, false);
+        deserializer.root = self.root;
+        deserializer.sub_delimiter = None;
+        deserializer.nested = true;
+
+        // Splitting the empty string yields a single empty token, however an empty field represents
+        // an empty sequence/map
+        if token.is_empty() {
+            let _ = deserializer.consume_token();
+        }
+
+        Ok(deserializer)
+    }
+
     /// Returns the next token in the input string and consumes it.
     ///
     /// If the input string has already been fully consumed, returns [`Error::Eof`]. If the
@@ -82,6 +215,12 @@ impl<'de> IndexedDeserializer<'de> {
         Some(tok)
     }
 
+    /// The byte offset of the given token (which has to be a sub-slice of the input) within the
+    /// input of the top level deserializer
+    fn offset_of(&self, token: &str) -> usize {
+        token.as_ptr() as usize - self.root.as_ptr() as usize
+    }
+
     fn position(&self) -> usize {
         self.end_of_current_token - self.input.as_ptr() as usize
     }
@@ -121,6 +260,7 @@ macro_rules! delegate_to_from_str {
                     message: error.to_string(),
                     index: None,
                     value: Some(token),
+                    offset: Some(self.offset_of(token)),
                 }),
             }
         }
@@ -179,6 +319,7 @@ impl<'de> Deserializer<'de> for &mut IndexedDeserializer<'de> {
                 message: "Expected 0, 1, 2, 10 or the empty string".to_owned(),
                 index: None,
                 value: Some(value),
+                offset: Some(self.offset_of(value)),
             }),
         }
     }
@@ -268,6 +409,17 @@ impl<'de> Deserializer<'de> for &mut IndexedDeserializer<'de> {
     where
         V: Visitor<'de>,
     {
+        if self.nested {
+            dev_trace!("RobtopDeserializer::deserialize_seq called on nested sequence");
+
+            return visitor.visit_seq(SeqAccess {
+                deserializer: &mut self.nested()?,
+                index: 0,
+            });
+        }
+
+        self.nested = true;
+
         visitor.visit_seq(SeqAccess {
             deserializer: self,
             index: 0,
@@ -292,19 +444,40 @@ impl<'de> Deserializer<'de> for &mut IndexedDeserializer<'de> {
     where
         V: Visitor<'de>,
     {
+        if self.nested {
+            dev_trace!("RobtopDeserializer::deserialize_map called on nested map");
+
+            return visitor.visit_map(MapAccess {
+                deserializer: &mut self.nested()?,
+            });
+        }
+
+        self.nested = true;
+
         visitor.visit_map(MapAccess { deserializer: self })
     }
 
     fn deserialize_struct<V>(
-        self, _name: &'static str, _fields: &'static [&'static str], visitor: V,
+        self, _name: &'static str, fields: &'static [&'static str], visitor: V,
     ) -> Result<<V as Visitor<'de>>::Value, Error<'de>>
     where
         V: Visitor<'de>,
     {
+        if self.nested {
+            return Err(Error::Unsupported("nested struct"));
+        }
+
+        self.nested = true;
+
         if self.map_like {
+            self.keys = KeyTable::new(fields);
+
             visitor.visit_map(MapAccess { deserializer: self })
         } else {
-            self.deserialize_seq(visitor)
+            visitor.visit_seq(SeqAccess {
+                deserializer: self,
+                index: 0,
+            })
         }
     }
 
@@ -323,7 +496,16 @@ impl<'de> Deserializer<'de> for &mut IndexedDeserializer<'de> {
     {
         dev_trace!("RobtopDeserializer::deserialize_identifier called");
 
-        self.deserialize_str(visitor)
+        if !core::mem::take(&mut self.expecting_key) {
+            return self.deserialize_str(visitor);
+        }
+
+        let token = self.consume_token().ok_or(Error::Eof)?;
+
+        match self.keys.as_ref().and_then(|keys| keys.position_of(token)) {
+            Some(position) => visitor.visit_u64(position),
+            None => visitor.visit_borrowed_str(token),
+        }
     }
 
     fn deserialize_ignored_any<V>(self, visitor: V) -> Result<<V as Visitor<'de>>::Value, Error<'de>>
@@ -334,11 +516,19 @@ impl<'de> Deserializer<'de> for &mut IndexedDeserializer<'de> {
         // indices. By the time this is called, they key itself will already have been popped in our
         // `MapAccess` implementation. This means we need to skip exactly one item! We'll feed a `None` to
         // the visitor. Because idk what we really wanna do here otherwise
-        let _token = self.consume_token();
+        let token = self.consume_token();
+
+        if self.map_like {
+            let key = self.nth_last(2);
+
+            if let (Some(unknown), Some(key), Some(value)) = (self.unknown.as_mut(), key, token) {
+                unknown.push((self.last_known_index, key, value));
+            }
+        }
 
         dev_debug!(
             "Ignored token {:?}. Preceding token (potentially an unmapped index) was {:?}",
-            _token,
+            token,
             self.nth_last(1)
         );
 
@@ -370,11 +560,18 @@ impl<'a, 'de> de::SeqAccess<'de> for SeqAccess<'a, 'de> {
 
         match seed.deserialize(&mut *self.deserializer) {
             Err(Error::Eof) => Ok(None),
-            Err(Error::Custom { message, value, .. }) => Err(Error::Custom {
-                message,
-                value: value.or_else(|| self.deserializer.nth_last(1)),
-                index: Some(INDICES.get(self.index - 1).unwrap_or(&">=51")),
-            }),
+            Err(Error::Custom {
+                message, value, offset, ..
+            }) => {
+                let value = value.or_else(|| self.deserializer.nth_last(1));
+
+                Err(Error::Custom {
+                    message,
+                    offset: offset.or_else(|| value.map(|value| self.deserializer.offset_of(value))),
+                    value,
+                    index: Some(INDICES.get(self.index - 1).unwrap_or(&">=51")),
+                })
+            },
             Err(err) => Err(err),
             Ok(item) => Ok(Some(item)),
         }
@@ -394,12 +591,19 @@ impl<'a, 'de> de::MapAccess<'de> for MapAccess<'a, 'de> {
     {
         dev_trace!("Processing a map key");
 
-        match seed.deserialize(&mut *self.deserializer) {
+        self.deserializer.expecting_key = true;
+
+        let key = seed.deserialize(&mut *self.deserializer);
+
+        self.deserializer.expecting_key = false;
+
+        match key {
             Err(Error::Eof) => Ok(None),
             Err(Error::Custom { message, .. }) => Err(Error::Custom {
                 message,
                 value: None,
                 index: self.deserializer.nth_last(1),
+                offset: None,
             }),
             Err(err) => Err(err),
             Ok(item) => Ok(Some(item)),
@@ -412,12 +616,30 @@ impl<'a, 'de> de::MapAccess<'de> for MapAccess<'a, 'de> {
     {
         dev_trace!("Processing a map value");
 
-        match seed.deserialize(&mut *self.deserializer) {
-            Err(Error::Custom { message, value, .. }) => Err(Error::Custom {
-                message,
-                value: value.or_else(|| self.deserializer.nth_last(1)),
-                index: self.deserializer.nth_last(2),
-            }),
+        let key = self.deserializer.nth_last(1);
+        let captured = self.deserializer.unknown.as_ref().map(Vec::len);
+
+        let value = seed.deserialize(&mut *self.deserializer);
+
+        // If nothing was captured, the value belonged to a known index, which subsequently captured
+        // unknown indices are positioned relative to
+        if captured.is_some() && self.deserializer.unknown.as_ref().map(Vec::len) == captured {
+            self.deserializer.last_known_index = key;
+        }
+
+        match value {
+            Err(Error::Custom {
+                message, value, offset, ..
+            }) => {
+                let value = value.or_else(|| self.deserializer.nth_last(1));
+
+                Err(Error::Custom {
+                    message,
+                    offset: offset.or_else(|| value.map(|value| self.deserializer.offset_of(value))),
+                    value,
+                    index: self.deserializer.nth_last(2),
+                })
+            },
             r => r,
         }
     }
@@ -425,8 +647,9 @@ impl<'a, 'de> de::MapAccess<'de> for MapAccess<'a, 'de> {
 
 #[cfg(test)]
 mod tests {
+    use super::KeyTable;
     use crate::serde::IndexedDeserializer;
-    use serde::de::Deserialize;
+    use serde::Deserialize;
     use std::collections::HashMap;
 
     const INPUT: &str = "1:hello:2:world";
@@ -455,6 +678,20 @@ mod tests {
         assert_eq!(map.get("2"), Some(&""));
     }
 
+    #[test]
+    fn test_key_table() {
+        let table = KeyTable::new(&["1", "3", "2", "name"]).unwrap();
+
+        assert_eq!(table.position_of("1"), Some(0));
+        assert_eq!(table.position_of("2"), Some(2));
+        assert_eq!(table.position_of("01"), None);
+        assert_eq!(table.position_of("4"), None);
+        assert_eq!(table.position_of("name"), None);
+
+        // Duplicate keys mean aliases are present, see the `KeyTable` docs
+        assert!(KeyTable::new(&["1", "2", "1"]).is_none());
+    }
+
     #[test]
     fn test_deserialize_to_vec() {
         let mut deserializer = IndexedDeserializer::new(INPUT, ":", false);
@@ -463,4 +700,116 @@ mod tests {
 
         assert_eq!(vec, INPUT.split(':').collect::<Vec<_>>())
     }
+
+    #[test]
+    fn test_error_context() {
+        use crate::DeError;
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Level {
+            #[serde(rename = "1")]
+            id: u64,
+            #[serde(rename = "3")]
+            levels: Vec<u64>,
+        }
+
+        let mut deserializer = IndexedDeserializer::new("1:1:3:27732941,nope", ":", true);
+
+        match Level::deserialize(&mut deserializer).unwrap_err() {
+            DeError::Custom { index, value, offset, .. } => {
+                assert_eq!(index, Some("3"));
+                assert_eq!(value, Some("nope"));
+                assert_eq!(offset, Some(15));
+            },
+            err => panic!("expected custom error, got {:?}", err),
+        }
+    }
+
+    #[test]
+    fn test_deserialize_nested_sequence() {
+        #[derive(Deserialize)]
+        struct Gauntlet {
+            #[serde(rename = "1")]
+            id: u8,
+            #[serde(rename = "3")]
+            levels: Vec<u64>,
+            #[serde(rename = "4")]
+            empty: Vec<u64>,
+        }
+
+        let mut deserializer = IndexedDeserializer::new("1:1:3:27732941,28200611,27483789:4:", ":", true);
+
+        let gauntlet = Gauntlet::deserialize(&mut deserializer).unwrap();
+
+        assert_eq!(gauntlet.id, 1);
+        assert_eq!(gauntlet.levels, vec![27732941, 28200611, 27483789]);
+        assert!(gauntlet.empty.is_empty());
+    }
+
+    #[test]
+    fn test_numeric_key_dispatch() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Song<'a> {
+            #[serde(rename = "10")]
+            link: &'a str,
+            #[serde(rename = "2")]
+            name: &'a str,
+            #[serde(rename = "name")]
+            artist: &'a str,
+        }
+
+        let mut deserializer =
+            IndexedDeserializer::new("2:Crystal Tokyo:02:nope:name:Fantomenk:999:nope:10:link", ":", true).capture_unknown_indices();
+
+        let song = Song::deserialize(&mut deserializer).unwrap();
+
+        assert_eq!(
+            song,
+            Song {
+                link: "link",
+                name: "Crystal Tokyo",
+                artist: "Fantomenk"
+            }
+        );
+        assert_eq!(deserializer.take_unknown_indices().len(), 2);
+    }
+
+    #[test]
+    fn test_unknown_indices_are_positioned() {
+        #[derive(Debug, Deserialize)]
+        struct Song<'a> {
+            #[serde(rename = "1")]
+            _id: u64,
+            #[serde(rename = "2")]
+            _name: &'a str,
+        }
+
+        let mut deserializer = IndexedDeserializer::new("0:a:1:1:5:b:6:c:2:name:7:d", ":", true).capture_unknown_indices();
+
+        Song::deserialize(&mut deserializer).unwrap();
+
+        let mut unknown = deserializer.take_unknown_indices();
+        let mut drained = Vec::new();
+
+        for known in [None, Some("1"), Some("2")].iter() {
+            unknown
+                .drain_after::<()>(*known, |index, value| {
+                    drained.push((*known, index.to_owned(), value.to_owned()));
+                    Ok(())
+                })
+                .unwrap();
+        }
+
+        assert!(unknown.is_empty());
+        assert_eq!(
+            drained,
+            vec![
+                (None, "0".to_owned(), "a".to_owned()),
+                (Some("1"), "5".to_owned(), "b".to_owned()),
+                (Some("1"), "6".to_owned(), "c".to_owned()),
+                (Some("2"), "7".to_owned(), "d".to_owned()),
+            ]
+        );
+    }
 }
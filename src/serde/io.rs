@@ -0,0 +1,70 @@
+//! The [`Write`] trait used by dash-rs' serializers
+//!
+//! With the `std` feature, this simply re-exports [`std::io::Write`]. Without it, this module
+//! provides a minimal replacement, which is implemented for [`Vec<u8>`].
+
+#[cfg(feature = "std")]
+pub use std::io::{Error, Write};
+
+#[cfg(not(feature = "std"))]
+pub use self::no_std::{Error, Write};
+
+#[cfg(not(feature = "std"))]
+mod no_std {
+    use alloc::vec::Vec;
+    use core::fmt::{self, Display, Formatter};
+
+    /// Error returned if a [`Write`] implementation fails to write some data
+    #[derive(Debug)]
+    pub struct Error;
+
+    impl Display for Error {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            f.write_str("failed to write data")
+        }
+    }
+
+    impl core::error::Error for Error {}
+
+    /// Replacement for `std::io::Write` when dash-rs is built without the `std` feature
+    pub trait Write {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Error>;
+
+        fn write_all(&mut self, mut buf: &[u8]) -> Result<(), Error> {
+            while !buf.is_empty() {
+                match self.write(buf)? {
+                    0 => return Err(Error),
+                    n => buf = &buf[n..],
+                }
+            }
+
+            Ok(())
+        }
+
+        fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> Result<(), Error> {
+            struct Adapter<'a, W: ?Sized>(&'a mut W);
+
+            impl<W: Write + ?Sized> fmt::Write for Adapter<'_, W> {
+                fn write_str(&mut self, s: &str) -> fmt::Result {
+                    self.0.write_all(s.as_bytes()).map_err(|_| fmt::Error)
+                }
+            }
+
+            fmt::write(&mut Adapter(self), args).map_err(|_| Error)
+        }
+    }
+
+    impl Write for Vec<u8> {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+            self.extend_from_slice(buf);
+
+            Ok(buf.len())
+        }
+    }
+
+    impl<W: Write + ?Sized> Write for &mut W {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+            (**self).write(buf)
+        }
+    }
+}
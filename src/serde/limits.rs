@@ -0,0 +1,99 @@
+//! Module containing the limits dash-rs enforces when processing untrusted input
+//!
+//! Responses of private servers ("GDPS") can be adversarial, for instance containing gigabytes of
+//! base64 or level data that decompresses to a multiple of its size ("zip bombs"). To allow using
+//! dash-rs in public proxies that parse third-party server output, all of these are bounded by the
+//! limits in this module. Exceeding one of them results in a [`LimitExceeded`] error.
+//!
+//! The limits are configured per call via a [`Limits`] value, e.g. via
+//! [`GJFormat::from_gj_str_with_limits`](crate::GJFormat::from_gj_str_with_limits) or
+//! [`Thunk::process_with_limits`](crate::Thunk::process_with_limits), so that a proxy can apply
+//! different limits per upstream server. Functions not taking [`Limits`] use [`Limits::DEFAULT`],
+//! which is generous enough for every level on the official servers.
+//!
+//! Note that there is no limit on recursion: The [`IndexedDeserializer`](crate::IndexedDeserializer)
+//! supports exactly one level of nesting (sequences and maps stored in a single field), and rejects
+//! anything deeper with [`DeError::Unsupported`](crate::DeError::Unsupported).
+
+use thiserror::Error;
+
+/// The limits enforced when processing untrusted input, see the [module level documentation](self)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Limits {
+    /// The maximal length (in bytes) of the input to
+    /// [`GJFormat::from_gj_str_with_limits`](crate::GJFormat::from_gj_str_with_limits)
+    ///
+    /// Defaults to 64 MiB
+    pub max_input_length: usize,
+
+    /// The maximal length (in bytes) of base64 encoded data that dash-rs will decode, such as level
+    /// data, level descriptions or comment contents
+    ///
+    /// Defaults to 64 MiB
+    pub max_base64_length: usize,
+
+    /// The maximal size (in bytes) of decompressed level data. Decompression is aborted as soon as
+    /// this many bytes have been produced.
+    ///
+    /// Defaults to 256 MiB
+    pub max_decompressed_level_size: usize,
+}
+
+impl Limits {
+    /// The limits used by all functions not taking [`Limits`]
+    pub const DEFAULT: Limits = Limits {
+        max_input_length: 64 << 20,
+        max_base64_length: 64 << 20,
+        max_decompressed_level_size: 256 << 20,
+    };
+
+    /// The value of the given limit
+    pub const fn get(&self, limit: Limit) -> usize {
+        match limit {
+            Limit::InputLength => self.max_input_length,
+            Limit::Base64Length => self.max_base64_length,
+            Limit::DecompressedLevelSize => self.max_decompressed_level_size,
+        }
+    }
+
+    /// Returns an error if the given length exceeds the given limit
+    pub(crate) fn check(&self, limit: Limit, length: usize) -> Result<(), LimitExceeded> {
+        let max = self.get(limit);
+
+        if length > max {
+            return Err(LimitExceeded { limit, max });
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits::DEFAULT
+    }
+}
+
+/// The limits that can be exceeded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Limit {
+    /// See [`Limits::max_input_length`]
+    InputLength,
+
+    /// See [`Limits::max_base64_length`]
+    Base64Length,
+
+    /// See [`Limits::max_decompressed_level_size`]
+    DecompressedLevelSize,
+}
+
+/// Error returned when some input exceeds one of the [`Limits`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Error)]
+#[error("input exceeds limit {limit:?} of {max} bytes")]
+pub struct LimitExceeded {
+    /// The limit that was exceeded
+    pub limit: Limit,
+
+    /// The value of the limit at the time it was exceeded
+    pub max: usize,
+}
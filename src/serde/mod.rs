@@ -1,13 +1,25 @@
 mod de;
+pub mod io;
+pub mod limits;
 mod ser;
 mod thunk;
+mod unknown;
 
 pub use de::{error::Error as DeError, indexed::IndexedDeserializer};
 pub use ser::{error::Error as SerError, indexed::IndexedSerializer, request::RequestSerializer};
 use serde::{Deserializer, Serializer};
-pub use thunk::{Base64Decoder, PercentDecoder, ProcessError, Thunk, ThunkProcessor};
+pub use thunk::{Base64Decoder, IntoOwnedThunkProcessor, PercentDecoder, ProcessError, Thunk, ThunkProcessor};
+pub(crate) use thunk::{ROBTOP_BASE64_CONFIG, ROBTOP_SET, ROBTOP_STANDARD_BASE64_CONFIG};
+pub use unknown::UnknownIndices;
 
-use std::{borrow::Cow, io::Write};
+use alloc::{
+    borrow::{Cow, ToOwned},
+    string::String,
+    vec::Vec,
+};
+use core::fmt::{Display, Formatter};
+use io::Write;
+use limits::{Limit, Limits};
 
 /// Trait for objects that can be (de)serialized from some Geometry Dash data format (e.g. an
 /// indexed description).
@@ -26,25 +38,126 @@ use std::{borrow::Cow, io::Write};
 pub trait Dash<'de>: Sized {
     fn dash_deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
     fn dash_serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+
+    /// The indices this object was deserialized from that are not mapped to any of its fields, or
+    /// [`None`] if this type does not preserve unknown indices. See [`UnknownIndices`]
+    fn unknown_indices(&self) -> Option<&UnknownIndices<'de>> {
+        None
+    }
+
+    /// Mutable version of [`Dash::unknown_indices`]
+    fn unknown_indices_mut(&mut self) -> Option<&mut UnknownIndices<'de>> {
+        None
+    }
 }
 
+/// Trait for objects that can be converted from and to the indexed data format the Geometry Dash
+/// servers use in their responses
+///
+/// This is the API GDPS implementations should use for generating responses, via
+/// [`GJFormat::to_robtop_string`] or [`GJFormat::write_gj`].
 pub trait GJFormat<'de>: Dash<'de> {
     const DELIMITER: &'static str;
     const MAP_LIKE: bool;
 
+    /// The delimiter separating the elements of sequences (such as lists of IDs) stored in a single
+    /// field
+    const SUB_DELIMITER: &'static str = ",";
+
     fn from_gj_str(input: &'de str) -> Result<Self, de::error::Error<'de>> {
-        let mut indexed_deserializer = IndexedDeserializer::new(input, Self::DELIMITER, Self::MAP_LIKE);
+        Self::from_gj_str_with_limits(input, &Limits::DEFAULT)
+    }
+
+    /// Like [`GJFormat::from_gj_str`], but enforces the given [`Limits`] instead of
+    /// [`Limits::DEFAULT`]
+    ///
+    /// Note that fields processed lazily (such as [`Thunk`]s) are only subject to the given
+    /// limits if processed via [`Thunk::process_with_limits`].
+    fn from_gj_str_with_limits(input: &'de str, limits: &Limits) -> Result<Self, de::error::Error<'de>> {
+        limits.check(Limit::InputLength, input.len())?;
+
+        let mut indexed_deserializer =
+            IndexedDeserializer::new(input, Self::DELIMITER, Self::MAP_LIKE).with_sub_delimiter(Self::SUB_DELIMITER);
 
         Self::dash_deserialize(&mut indexed_deserializer)
     }
 
+    /// Like [`GJFormat::from_gj_str`], but captures all indices not mapped to any field into the
+    /// [`UnknownIndices`] of the models supporting it (currently
+    /// [`Level`](crate::model::level::Level), [`Profile`](crate::model::user::profile::Profile) and
+    /// [`NewgroundsSong`](crate::model::song::NewgroundsSong)), instead of dropping them.
+    ///
+    /// Serializing the returned object writes the unknown indices back out at their original
+    /// positions.
+    fn from_gj_str_preserving(input: &'de str) -> Result<Self, de::error::Error<'de>> {
+        Limits::DEFAULT.check(Limit::InputLength, input.len())?;
+
+        let mut indexed_deserializer = IndexedDeserializer::new(input, Self::DELIMITER, Self::MAP_LIKE)
+            .with_sub_delimiter(Self::SUB_DELIMITER)
+            .capture_unknown_indices();
+
+        let mut deserialized = Self::dash_deserialize(&mut indexed_deserializer)?;
+
+        if let Some(unknown_indices) = deserialized.unknown_indices_mut() {
+            *unknown_indices = indexed_deserializer.take_unknown_indices();
+        }
+
+        Ok(deserialized)
+    }
+
+    /// Parses an object from the string the Geometry Dash servers would use to represent it.
+    /// Equivalent to [`GJFormat::from_gj_str`]
+    fn from_robtop_str(input: &'de str) -> Result<Self, de::error::Error<'de>> {
+        Self::from_gj_str(input)
+    }
+
+    /// Serializes this object into the string the Geometry Dash servers would use to represent it
+    fn to_robtop_string(&self) -> Result<String, ser::error::Error> {
+        let mut bytes = Vec::new();
+
+        self.write_gj(&mut bytes)?;
+
+        String::from_utf8(bytes).map_err(serde::ser::Error::custom)
+    }
+
+    /// Wraps this object into a value whose [`Display`] implementation outputs
+    /// [`GJFormat::to_robtop_string`]
+    ///
+    /// Must only be used on objects that can be serialized (that is, for which
+    /// [`GJFormat::to_robtop_string`] succeeds). See [`RobtopDisplay`] for what happens otherwise.
+    fn display_robtop(&self) -> RobtopDisplay<'_, Self> {
+        RobtopDisplay(self)
+    }
+
     fn write_gj<W: Write>(&self, writer: W) -> Result<(), ser::error::Error> {
-        let mut indexed_serializer = IndexedSerializer::new(Self::DELIMITER, writer, Self::MAP_LIKE);
+        let mut indexed_serializer =
+            IndexedSerializer::new(Self::DELIMITER, writer, Self::MAP_LIKE).with_sub_delimiter(Self::SUB_DELIMITER);
+
+        if let Some(unknown_indices) = self.unknown_indices().filter(|unknown_indices| !unknown_indices.is_empty()) {
+            indexed_serializer = indexed_serializer.with_unknown_indices(unknown_indices);
+        }
 
         self.dash_serialize(&mut indexed_serializer)
     }
 }
 
+/// Wrapper around some [`GJFormat`] object whose [`Display`] implementation outputs the object in
+/// RobTop's data format, constructed via [`GJFormat::display_robtop`]
+///
+/// [`Display`] cannot report why serialization failed, so if the object cannot be serialized (for
+/// instance because some processed [`Thunk`] fails to convert back into its raw form), formatting
+/// returns a bare [`core::fmt::Error`]. Since the standard library treats such errors as bugs,
+/// [`ToString::to_string`](alloc::string::ToString::to_string) and `format!` then panic. Use
+/// [`GJFormat::to_robtop_string`] for objects that may fail to serialize.
+#[derive(Debug, Clone, Copy)]
+pub struct RobtopDisplay<'a, T: ?Sized>(&'a T);
+
+impl<'de, T: GJFormat<'de>> Display for RobtopDisplay<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.0.to_robtop_string().map_err(|_| core::fmt::Error)?)
+    }
+}
+
 /// Trait describing an intermediate step between the raw Geomtry Dash data format, and the APIs
 /// exposed by dash-rs
 ///
@@ -117,6 +230,22 @@ impl<'b, T: ThunkProcessor> InternalProxy for Thunk<'b, T> {
     }
 }
 
+impl<T: InternalProxy> InternalProxy for Vec<T> {
+    type DeserializeProxy = Vec<T::DeserializeProxy>;
+    type SerializeProxy<'a>
+        = Vec<T::SerializeProxy<'a>>
+    where
+        Self: 'a;
+
+    fn to_serialize_proxy(&self) -> Self::SerializeProxy<'_> {
+        self.iter().map(|t| t.to_serialize_proxy()).collect()
+    }
+
+    fn from_deserialize_proxy(from: Self::DeserializeProxy) -> Self {
+        from.into_iter().map(|f| T::from_deserialize_proxy(f)).collect()
+    }
+}
+
 impl<T: InternalProxy> InternalProxy for Option<T> {
     type DeserializeProxy = Option<T::DeserializeProxy>;
     type SerializeProxy<'a>
@@ -1,4 +1,6 @@
-use std::{fmt::Display, io};
+use crate::serde::io;
+use alloc::string::{FromUtf8Error, String, ToString};
+use core::fmt::Display;
 
 use thiserror::Error;
 
@@ -17,7 +19,7 @@ pub enum Error {
     Io(#[from] io::Error),
 
     #[error("failed utf8 conversion: {0}")]
-    Utf8(#[from] std::string::FromUtf8Error),
+    Utf8(#[from] FromUtf8Error),
 }
 
 impl serde::ser::Error for Error {
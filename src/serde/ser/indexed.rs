@@ -1,10 +1,12 @@
+use crate::serde::io::Write;
 use crate::serde::ser::error::Error;
+use crate::serde::UnknownIndices;
+use core::fmt::Display;
 use itoa::{Buffer, Integer};
 use serde::{
-    ser::{Error as _, Impossible, SerializeStruct},
+    ser::{Error as _, Impossible, SerializeMap, SerializeSeq, SerializeStruct},
     Serialize, Serializer,
 };
-use std::{fmt::Display, io::Write};
 
 #[allow(missing_debug_implementations)]
 pub struct IndexedSerializer<W> {
@@ -20,6 +22,19 @@ pub struct IndexedSerializer<W> {
     /// empty string. In that case, a delimiter needs to be appended, but since the writer would
     /// still be empty, no delimiter would be added.
     is_start: bool,
+
+    /// The delimiter separating the elements of sequences and maps nested inside the top level
+    /// object, or [`None`] if nested sequences and maps are not supported (because we are already
+    /// serializing a nested sequence or map)
+    sub_delimiter: Option<&'static str>,
+
+    /// Value indicating whether serialization of the top level object has already started, in
+    /// which case any further sequences or maps are nested ones
+    nested: bool,
+
+    /// Unknown indices to write in between the fields of a map-like top level struct, see
+    /// [`IndexedSerializer::with_unknown_indices`]
+    unknown: UnknownIndices<'static>,
 }
 
 impl<W> IndexedSerializer<W>
@@ -32,7 +47,65 @@ where
             writer,
             map_like,
             is_start: true,
+            sub_delimiter: Some(","),
+            nested: false,
+            unknown: UnknownIndices::new(),
+        }
+    }
+
+    /// Sets the delimiter separating the elements of sequences and maps nested inside the top level
+    /// object
+    pub fn with_sub_delimiter(mut self, sub_delimiter: &'static str) -> Self {
+        self.sub_delimiter = Some(sub_delimiter);
+        self
+    }
+
+    /// Makes this serializer write the given unknown indices at the positions they were captured at
+    /// (relative to the known indices) when serializing a map-like struct
+    pub(crate) fn with_unknown_indices(mut self, unknown: &UnknownIndices<'_>) -> Self {
+        self.unknown = unknown.clone().into_owned();
+        self
+    }
+
+    /// Writes all unknown indices that followed the given known index (or preceded all known
+    /// indices, if [`None`])
+    fn append_unknown_after(&mut self, known: Option<&str>) -> Result<(), Error> {
+        let mut unknown = core::mem::take(&mut self.unknown);
+        let result = unknown.drain_after(known, |index, value| {
+            self.append(index)?;
+            self.append(value)
+        });
+        self.unknown = unknown;
+
+        result
+    }
+
+    /// Starts serialization of a sequence or map. If it is nested inside the top level object,
+    /// all its elements will be written into a single field.
+    fn collection(&mut self) -> Result<CollectionSerializer<'_, W>, Error> {
+        if !self.nested {
+            self.nested = true;
+
+            return Ok(CollectionSerializer::TopLevel(self));
+        }
+
+        let sub_delimiter = self.sub_delimiter.ok_or(Error::Unsupported("doubly nested sequence or map"))?;
+
+        if self.is_start {
+            self.is_start = false;
+        } else {
+            self.writer.write_all(self.delimiter)?;
         }
+
+        Ok(CollectionSerializer::Nested(IndexedSerializer {
+            delimiter: sub_delimiter.as_bytes(),
+            writer: &mut self.writer,
+            map_like: false,
+            is_start: true,
+            sub_delimiter: None,
+            nested: true,
+            unknown: UnknownIndices::new(),
+        }))
     }
 
     fn append_integer<I: Integer>(&mut self, int: I) -> Result<(), Error> {
@@ -72,11 +145,11 @@ where
     }
 }
 
-impl<W: Write> Serializer for &mut IndexedSerializer<W> {
+impl<'a, W: Write> Serializer for &'a mut IndexedSerializer<W> {
     type Error = Error;
     type Ok = ();
-    type SerializeMap = Impossible<(), Error>;
-    type SerializeSeq = Impossible<(), Error>;
+    type SerializeMap = CollectionSerializer<'a, W>;
+    type SerializeSeq = CollectionSerializer<'a, W>;
     type SerializeStruct = Self;
     type SerializeStructVariant = Impossible<(), Error>;
     type SerializeTuple = Impossible<(), Error>;
@@ -148,10 +221,8 @@ impl<W: Write> Serializer for &mut IndexedSerializer<W> {
 
     // Here we serialize bytes by base64 encoding them, so it's always valid in Geometry Dash's format
     fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
-        use base64::{engine::general_purpose::URL_SAFE, write::EncoderWriter};
-        let mut enc = EncoderWriter::new(&mut self.writer, &URL_SAFE);
-        enc.write_all(v)?;
-        enc.finish()?;
+        use base64::{engine::general_purpose::URL_SAFE, Engine};
+        self.writer.write_all(URL_SAFE.encode(v).as_bytes())?;
         Ok(())
     }
 
@@ -196,7 +267,7 @@ impl<W: Write> Serializer for &mut IndexedSerializer<W> {
     }
 
     fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
-        Err(Error::Unsupported("serialize_seq"))
+        self.collection()
     }
 
     fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
@@ -214,10 +285,20 @@ impl<W: Write> Serializer for &mut IndexedSerializer<W> {
     }
 
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
-        Err(Error::Unsupported("serialize_map"))
+        self.collection()
     }
 
     fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct, Self::Error> {
+        if self.nested {
+            return Err(Error::Unsupported("nested struct"));
+        }
+
+        self.nested = true;
+
+        if self.map_like {
+            self.append_unknown_after(None)?;
+        }
+
         // We don't store the struct name and the amount of fields doesn't matter
         Ok(self)
     }
@@ -247,7 +328,80 @@ impl<W: Write> SerializeStruct for &mut IndexedSerializer<W> {
         if self.map_like {
             self.append(key)?;
         }
-        value.serialize(&mut **self)
+        value.serialize(&mut **self)?;
+
+        if self.map_like {
+            self.append_unknown_after(Some(key))?;
+        }
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        // Unknown indices whose preceding known index was not written (or that were inserted
+        // manually) go to the end
+        let mut unknown = core::mem::take(&mut self.unknown);
+
+        unknown.drain_all(|index, value| {
+            self.append(index)?;
+            self.append(value)
+        })
+    }
+}
+
+/// Serializer for sequences and maps, see [`IndexedSerializer::with_sub_delimiter`]
+#[allow(missing_debug_implementations)]
+pub enum CollectionSerializer<'a, W> {
+    /// The sequence or map is the top level object, and its elements are separated by the main
+    /// delimiter
+    TopLevel(&'a mut IndexedSerializer<W>),
+
+    /// The sequence or map is nested inside the top level object, and its elements are written into
+    /// a single field, separated by the sub-delimiter
+    Nested(IndexedSerializer<&'a mut W>),
+}
+
+impl<'a, W: Write> CollectionSerializer<'a, W> {
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        match self {
+            CollectionSerializer::TopLevel(serializer) => value.serialize(&mut **serializer),
+            CollectionSerializer::Nested(serializer) => value.serialize(serializer),
+        }
+    }
+}
+
+impl<'a, W: Write> SerializeSeq for CollectionSerializer<'a, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, W: Write> SerializeMap for CollectionSerializer<'a, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.element(key)
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.element(value)
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
@@ -257,7 +411,7 @@ impl<W: Write> SerializeStruct for &mut IndexedSerializer<W> {
 
 #[cfg(test)]
 mod tests {
-    use serde::Serializer;
+    use serde::{Serialize, Serializer};
 
     use super::IndexedSerializer;
 
@@ -269,4 +423,24 @@ mod tests {
         serializer.serialize_f64(11.0f64).unwrap();
         assert_eq!("11", std::str::from_utf8(buffer.as_slice()).unwrap());
     }
+
+    #[test]
+    fn serialize_nested_sequence() {
+        #[derive(Serialize)]
+        struct Gauntlet {
+            #[serde(rename = "1")]
+            id: u8,
+            #[serde(rename = "3")]
+            levels: Vec<u64>,
+        }
+
+        let gauntlet = Gauntlet {
+            id: 1,
+            levels: vec![27732941, 28200611, 27483789],
+        };
+
+        let mut buffer = Vec::new();
+        gauntlet.serialize(&mut IndexedSerializer::new(":", &mut buffer, true)).unwrap();
+        assert_eq!("1:1:3:27732941,28200611,27483789", std::str::from_utf8(buffer.as_slice()).unwrap());
+    }
 }
@@ -12,26 +12,70 @@
 //!   them isn't supported. What happens if we include them programmatically is something yet to be
 //!   investigated) TODO GAME SPECIFIC
 
+use crate::serde::io::{self, Write};
 use crate::serde::SerError as Error;
+use alloc::vec::Vec;
+use core::{fmt::Display, ops::Range};
 use itoa::{Buffer, Integer};
 use serde::{
     ser::{Error as _, Impossible, SerializeStruct},
     Serialize, Serializer,
 };
-use std::{fmt::Display, io::Write};
 
 #[allow(missing_debug_implementations)]
 pub struct RequestSerializer<W> {
-    writer: W,
+    writer: Counting<W>,
 
     /// Value indicating whether this serializer has already serialized something. This is used to
     /// check if we need to prepend the delimiter to the next field.
     is_start: bool,
+
+    /// The keys written so far together with the byte ranges of their values, in the order they
+    /// were written. Used by [`FieldOrder`](crate::request::FieldOrder) to reorder the serialized
+    /// key-value pairs, since values are not escaped and thus cannot be found by splitting the
+    /// output.
+    fields: Vec<(&'static str, Range<usize>)>,
+
+    /// Index into `fields` of the field whose value is currently being written
+    open: Option<usize>,
 }
 
 impl<W> RequestSerializer<W> {
     pub fn new(writer: W) -> Self {
-        RequestSerializer { writer, is_start: true }
+        RequestSerializer {
+            writer: Counting { inner: writer, count: 0 },
+            is_start: true,
+            fields: Vec::new(),
+            open: None,
+        }
+    }
+
+    /// The keys written by this serializer so far, in the order they were written, together with
+    /// the byte ranges their values occupy in the output (relative to the first byte written by
+    /// this serializer)
+    pub fn fields(&self) -> &[(&'static str, Range<usize>)] {
+        &self.fields
+    }
+}
+
+/// [`Write`] adapter keeping track of the number of bytes written
+struct Counting<W> {
+    inner: W,
+    count: usize,
+}
+
+impl<W: Write> Write for Counting<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, io::Error> {
+        let written = self.inner.write(buf)?;
+
+        self.count += written;
+
+        Ok(written)
+    }
+
+    #[cfg(feature = "std")]
+    fn flush(&mut self) -> Result<(), io::Error> {
+        self.inner.flush()
     }
 }
 
@@ -171,7 +215,14 @@ impl<W: Write> SerializeStruct for &mut RequestSerializer<W> {
         value.serialize(&mut ValueSerializer {
             key: Some(key),
             serializer: self,
-        })
+        })?;
+
+        // For inlined structs, the spans have already been closed by the nested `serialize_field` calls
+        if let Some(idx) = self.open.take() {
+            self.fields[idx].1.end = self.writer.count;
+        }
+
+        Ok(())
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
@@ -191,7 +242,11 @@ impl<'ser, W: Write> ValueSerializer<'ser, W> {
             self.serializer.writer.write_all(key.as_bytes()).map_err(Error::custom)?;
             self.serializer.writer.write(b"=").map_err(Error::custom)?;
 
+            let start = self.serializer.writer.count;
+
             self.serializer.is_start = false;
+            self.serializer.open = Some(self.serializer.fields.len());
+            self.serializer.fields.push((key, start..start));
         }
         Ok(())
     }
@@ -456,12 +511,21 @@ mod tests {
         let level_request = LevelRequest::default();
         let mut buffer = Vec::new();
 
-        let mut ser = RequestSerializer {
-            writer: &mut buffer,
-            is_start: true,
-        };
+        let mut ser = RequestSerializer::new(&mut buffer);
+
         level_request.serialize(&mut ser).unwrap();
 
+        assert_eq!(
+            ser.fields(),
+            [
+                ("gameVersion", 12..14),
+                ("binaryVersion", 29..31),
+                ("secret", 39..50),
+                ("levelID", 59..60),
+                ("inc", 65..66),
+                ("extra", 73..74)
+            ]
+        );
         assert_eq!(
             "gameVersion=22&binaryVersion=38&secret=Wmfd2893gb7&levelID=0&inc=0&extra=0",
             String::from_utf8(buffer).unwrap()
@@ -1,19 +1,19 @@
+use alloc::{
+    borrow::{Borrow, Cow},
+    string::{FromUtf8Error, String},
+};
 use base64::{
-    alphabet::URL_SAFE,
+    alphabet::{STANDARD, URL_SAFE},
     engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
     DecodeError, DecodeSliceError, Engine,
 };
+use core::{mem, num::ParseIntError, str::Utf8Error};
 use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
-use serde::{ser::Error as _, Deserialize, Serialize, Serializer};
-use std::{
-    borrow::{Borrow, Cow},
-    mem,
-    num::ParseIntError,
-    str::Utf8Error,
-    string::FromUtf8Error,
-};
+use serde::{ser::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
 
+use crate::serde::limits::{Limit, LimitExceeded, Limits};
+
 /// Enum modelling the different errors that can occur during processing of a [`Thunk`]
 ///
 /// ## Why is this a seperate enum
@@ -39,8 +39,9 @@ pub enum ProcessError {
     FromUtf8(#[from] FromUtf8Error),
 
     /// Some base64 decoding error occurred during processing
+    // base64's errors only implement `Error` with its `std` feature
     #[error("{0}")]
-    Base64(#[from] DecodeSliceError),
+    Base64(#[cfg_attr(feature = "std", source)] DecodeSliceError),
 
     /// Some error occurred when parsing a number
     #[error("{0}")]
@@ -53,6 +54,21 @@ pub enum ProcessError {
 
     #[error("Received value that cannot be represented in Geometry Dash data format")]
     Unrepresentable,
+
+    /// A URL (such as the download link of a song) was malformed. Holds a description of what is
+    /// wrong with it.
+    #[error("Malformed URL: {0}")]
+    InvalidUrl(&'static str),
+
+    /// The value exceeded one of the configured [`limits`](crate::limits)
+    #[error("{0}")]
+    LimitExceeded(#[from] LimitExceeded),
+}
+
+impl From<DecodeSliceError> for ProcessError {
+    fn from(value: DecodeSliceError) -> Self {
+        ProcessError::Base64(value)
+    }
 }
 
 impl From<DecodeError> for ProcessError {
@@ -69,14 +85,26 @@ impl From<DecodeError> for ProcessError {
 /// The required further processing should happen in the [`ThunkProcessor`] implementation, which is
 /// invoked by calling [`Thunk::process`]. Think of it as [`Cow`] with extra steps and potential new
 /// allocations instead of cloning.
-#[derive(Debug, Eq, Clone, Deserialize)]
-#[serde(untagged)]
+///
+/// Outside of RobTop's data format, a [`Thunk`] is always (de)serialized as its processed output.
+#[derive(Debug, Eq, Clone)]
 pub enum Thunk<'a, C: ThunkProcessor> {
-    #[serde(skip)]
     Unprocessed(Cow<'a, str>),
     Processed(C::Output<'a>),
 }
 
+impl<'de, 'a, C: ThunkProcessor> Deserialize<'de> for Thunk<'a, C>
+where
+    C::Output<'a>: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        C::Output::deserialize(deserializer).map(Thunk::Processed)
+    }
+}
+
 impl<'a, 'b, P: ThunkProcessor> PartialEq<Thunk<'b, P>> for Thunk<'a, P>
 where
     P::Output<'a>: PartialEq<P::Output<'b>>,
@@ -112,7 +140,7 @@ where
 /// This trait provides the means to translate from and into RobTop's representation for thunked
 /// data, while not being used in the (de)serialization into any other data format.
 pub trait ThunkProcessor {
-    type Error: std::error::Error;
+    type Error: core::error::Error;
     type Output<'a>;
 
     /// Takes some data from the [`Thunk::Unprocessed`] variant and processes it
@@ -121,6 +149,17 @@ pub trait ThunkProcessor {
     /// format.
     fn from_unprocessed(unprocessed: Cow<str>) -> Result<Self::Output<'_>, Self::Error>;
 
+    /// Like [`ThunkProcessor::from_unprocessed`], but enforces the given [`Limits`] instead of
+    /// [`Limits::DEFAULT`]
+    ///
+    /// Only processors decoding potentially large data (such as base64 or compressed level data)
+    /// need to override this. By default, the limits are ignored.
+    fn from_unprocessed_with_limits<'b>(unprocessed: Cow<'b, str>, limits: &Limits) -> Result<Self::Output<'b>, Self::Error> {
+        let _ = limits;
+
+        Self::from_unprocessed(unprocessed)
+    }
+
     /// Takes some processed thunk value and converts it into RobTop-representation
     fn as_unprocessed<'b>(processed: &'b Self::Output<'_>) -> Result<Cow<'b, str>, Self::Error>;
 
@@ -138,13 +177,30 @@ pub trait ThunkProcessor {
     fn downcast_output_lifetime<'b: 'c, 'c, 's>(output: &'s Self::Output<'b>) -> &'s Self::Output<'c>;
 }
 
+/// Extension of [`ThunkProcessor`] for processors whose output can be detached from the input it
+/// was processed from, required for [`Thunk::into_owned`]
+///
+/// This is a separate trait so that implementing [`ThunkProcessor`] does not require implementing
+/// this conversion.
+pub trait IntoOwnedThunkProcessor: ThunkProcessor {
+    /// Converts some processed thunk value into a version that does not borrow from the input it
+    /// was processed from
+    fn output_into_owned(output: Self::Output<'_>) -> Self::Output<'static>;
+}
+
 impl<'a, C: ThunkProcessor> Thunk<'a, C> {
     /// If this is a [`Thunk::Unprocessed`] variant, calls [`ThunkProcessor::from_unprocessed`] and
     /// returns [`Thunk::Processed`]. Simply returns `self` if this is a [`Thunk::Processed`]
     /// variant
     pub fn process(&mut self) -> Result<&mut C::Output<'a>, C::Error> {
+        self.process_with_limits(&Limits::DEFAULT)
+    }
+
+    /// Like [`Thunk::process`], but enforces the given [`Limits`] via
+    /// [`ThunkProcessor::from_unprocessed_with_limits`]
+    pub fn process_with_limits(&mut self, limits: &Limits) -> Result<&mut C::Output<'a>, C::Error> {
         if let Thunk::Unprocessed(raw_data) = self {
-            *self = Thunk::Processed(C::from_unprocessed(mem::take(raw_data))?)
+            *self = Thunk::Processed(C::from_unprocessed_with_limits(mem::take(raw_data), limits)?)
         }
 
         match self {
@@ -162,8 +218,14 @@ impl<'a, C: ThunkProcessor> Thunk<'a, C> {
 
     /// Returns the result of processing this [`Thunk`]
     pub fn into_processed(self) -> Result<C::Output<'a>, C::Error> {
+        self.into_processed_with_limits(&Limits::DEFAULT)
+    }
+
+    /// Like [`Thunk::into_processed`], but enforces the given [`Limits`] via
+    /// [`ThunkProcessor::from_unprocessed_with_limits`]
+    pub fn into_processed_with_limits(self, limits: &Limits) -> Result<C::Output<'a>, C::Error> {
         match self {
-            Thunk::Unprocessed(unprocessed) => C::from_unprocessed(unprocessed),
+            Thunk::Unprocessed(unprocessed) => C::from_unprocessed_with_limits(unprocessed, limits),
             Thunk::Processed(p) => Ok(p),
         }
     }
@@ -179,6 +241,17 @@ impl<'a, C: ThunkProcessor> Thunk<'a, C> {
     }
 }
 
+impl<C: IntoOwnedThunkProcessor> Thunk<'_, C> {
+    /// Converts this [`Thunk`] into one that does not borrow from the input it was deserialized
+    /// from, without processing it
+    pub fn into_owned(self) -> Thunk<'static, C> {
+        match self {
+            Thunk::Unprocessed(unprocessed) => Thunk::Unprocessed(Cow::Owned(unprocessed.into_owned())),
+            Thunk::Processed(processed) => Thunk::Processed(C::output_into_owned(processed)),
+        }
+    }
+}
+
 /// Set of characters RobTop encodes when doing percent encoding
 ///
 /// This is a subset of [`percent_encoding::NON_ALPHANUMERIC`], since that encodes too many
@@ -216,6 +289,12 @@ impl ThunkProcessor for PercentDecoder {
     }
 }
 
+impl IntoOwnedThunkProcessor for PercentDecoder {
+    fn output_into_owned(output: Self::Output<'_>) -> Self::Output<'static> {
+        Cow::Owned(output.into_owned())
+    }
+}
+
 /// Base64 decoder configuration that most closely matches what Geometry Dash is doing.
 ///
 /// That generally means "be as lenient as possible", aka
@@ -229,6 +308,19 @@ pub const ROBTOP_BASE64_CONFIG: GeneralPurpose = GeneralPurpose::new(
         .with_decode_allow_trailing_bits(true),
 );
 
+/// Variant of [`ROBTOP_BASE64_CONFIG`] using the standard base64 alphabet (`+` and `/` instead of `-`
+/// and `_`).
+///
+/// The official servers never use this alphabet, however some GDPS implementations encode level
+/// data with it.
+pub const ROBTOP_STANDARD_BASE64_CONFIG: GeneralPurpose = GeneralPurpose::new(
+    &STANDARD,
+    GeneralPurposeConfig::new()
+        .with_encode_padding(true)
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent)
+        .with_decode_allow_trailing_bits(true),
+);
+
 #[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone, Copy)]
 pub struct Base64Decoder;
 
@@ -237,6 +329,12 @@ impl ThunkProcessor for Base64Decoder {
     type Output<'a> = Cow<'a, str>;
 
     fn from_unprocessed(unprocessed: Cow<str>) -> Result<Self::Output<'_>, Self::Error> {
+        Self::from_unprocessed_with_limits(unprocessed, &Limits::DEFAULT)
+    }
+
+    fn from_unprocessed_with_limits<'b>(unprocessed: Cow<'b, str>, limits: &Limits) -> Result<Self::Output<'b>, Self::Error> {
+        limits.check(Limit::Base64Length, unprocessed.len())?;
+
         let vec = ROBTOP_BASE64_CONFIG.decode(&*unprocessed)?;
         let string = String::from_utf8(vec).map_err(ProcessError::FromUtf8)?;
 
@@ -252,6 +350,12 @@ impl ThunkProcessor for Base64Decoder {
     }
 }
 
+impl IntoOwnedThunkProcessor for Base64Decoder {
+    fn output_into_owned(output: Self::Output<'_>) -> Self::Output<'static> {
+        Cow::Owned(output.into_owned())
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use std::borrow::Cow;
@@ -0,0 +1,160 @@
+//! Module containing the [`UnknownIndices`] type used to preserve indices dash-rs does not know
+//! about
+
+use alloc::{borrow::Cow, vec::Vec};
+
+use serde::{Deserialize, Serialize};
+
+/// The key-value pairs of an object in RobTop's indexed format whose keys dash-rs does not know
+/// about
+///
+/// When RobTop adds a new index, dash-rs ignores it during deserialization. If the object is parsed
+/// via [`GJFormat::from_gj_str_preserving`](crate::GJFormat::from_gj_str_preserving), these are
+/// captured here instead, together with the position at which they appeared. When the object is
+/// serialized again, they are written back out at that same position, allowing proxies built on
+/// dash-rs to pass through data they do not understand byte-for-byte.
+///
+/// Behaves like a map, but preserves the order in which the indices appeared in the input.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct UnknownIndices<'a>(Vec<Entry<'a>>);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+struct Entry<'a> {
+    position: Position<'a>,
+    index: Cow<'a, str>,
+    value: Cow<'a, str>,
+}
+
+/// The position of an unknown index relative to the known indices of an object
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+enum Position<'a> {
+    /// The index preceded all known indices
+    Start,
+
+    /// The index (directly, or preceded only by other unknown indices) followed the given known
+    /// index
+    After(Cow<'a, str>),
+
+    /// The index was not parsed from RobTop's format, but inserted afterwards. These are written
+    /// after all known indices.
+    End,
+}
+
+impl<'a> UnknownIndices<'a> {
+    pub const fn new() -> Self {
+        UnknownIndices(Vec::new())
+    }
+
+    /// The value of the given index, if it is present
+    pub fn get(&self, index: &str) -> Option<&str> {
+        self.0.iter().find(|entry| entry.index == index).map(|entry| entry.value.as_ref())
+    }
+
+    /// Sets the value of the given index, returning the previous value if it was already present.
+    /// New indices are written after all known indices during serialization.
+    pub fn insert(&mut self, index: impl Into<Cow<'a, str>>, value: impl Into<Cow<'a, str>>) -> Option<Cow<'a, str>> {
+        let index = index.into();
+        let value = value.into();
+
+        match self.0.iter_mut().find(|entry| entry.index == index) {
+            Some(entry) => Some(core::mem::replace(&mut entry.value, value)),
+            None => {
+                self.0.push(Entry {
+                    position: Position::End,
+                    index,
+                    value,
+                });
+                None
+            },
+        }
+    }
+
+    /// Removes the given index, returning its value if it was present
+    pub fn remove(&mut self, index: &str) -> Option<Cow<'a, str>> {
+        let position = self.0.iter().position(|entry| entry.index == index)?;
+
+        Some(self.0.remove(position).value)
+    }
+
+    /// Iterates over all index-value pairs, in the order they appeared in the input
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|entry| (entry.index.as_ref(), entry.value.as_ref()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn into_owned(self) -> UnknownIndices<'static> {
+        UnknownIndices(
+            self.0
+                .into_iter()
+                .map(|entry| Entry {
+                    position: match entry.position {
+                        Position::Start => Position::Start,
+                        Position::After(known) => Position::After(Cow::Owned(known.into_owned())),
+                        Position::End => Position::End,
+                    },
+                    index: Cow::Owned(entry.index.into_owned()),
+                    value: Cow::Owned(entry.value.into_owned()),
+                })
+                .collect(),
+        )
+    }
+
+    /// Constructs an `UnknownIndices` from the indices captured by an
+    /// [`IndexedDeserializer`](crate::IndexedDeserializer). Each index is accompanied by the known
+    /// index it followed, or [`None`] if it preceded all known indices.
+    pub(crate) fn from_captured(captured: Vec<(Option<&'a str>, &'a str, &'a str)>) -> Self {
+        UnknownIndices(
+            captured
+                .into_iter()
+                .map(|(after, index, value)| Entry {
+                    position: after.map_or(Position::Start, |known| Position::After(Cow::Borrowed(known))),
+                    index: Cow::Borrowed(index),
+                    value: Cow::Borrowed(value),
+                })
+                .collect(),
+        )
+    }
+
+    /// Removes all indices that originally followed the given known index (or preceded all known
+    /// indices, if [`None`]), passing them to `write` in order
+    pub(crate) fn drain_after<E>(&mut self, known: Option<&str>, write: impl FnMut(&str, &str) -> Result<(), E>) -> Result<(), E> {
+        self.drain_where(
+            |position| match (position, known) {
+                (Position::Start, None) => true,
+                (Position::After(after), Some(known)) => after == known,
+                _ => false,
+            },
+            write,
+        )
+    }
+
+    /// Removes all remaining indices, passing them to `write` in order
+    pub(crate) fn drain_all<E>(&mut self, write: impl FnMut(&str, &str) -> Result<(), E>) -> Result<(), E> {
+        self.drain_where(|_| true, write)
+    }
+
+    fn drain_where<E>(
+        &mut self, predicate: impl Fn(&Position) -> bool, mut write: impl FnMut(&str, &str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let mut result = Ok(());
+
+        self.0.retain(|entry| {
+            if result.is_err() || !predicate(&entry.position) {
+                return true;
+            }
+
+            result = write(&*entry.index, &*entry.value);
+            false
+        });
+
+        result
+    }
+}
@@ -0,0 +1,206 @@
+//! Snapshot testing support for projects embedding dash-rs
+//!
+//! A fixture consists of a raw RobTop-format response (the `raw` file) and a JSON snapshot of how
+//! dash-rs parsed it (the `processed` file). Committing both allows downstream projects to detect
+//! if an upgrade of dash-rs changes how their responses are parsed.
+//!
+//! Snapshots that do not exist yet are written when first asserted, and the assertion fails so that
+//! the new snapshot can be reviewed before committing it. Setting the `DASH_RS_UPDATE_SNAPSHOTS`
+//! environment variable overwrites all mismatching snapshots instead of failing.
+//!
+//! Only available with the `snapshot` feature.
+
+use crate::{GJFormat, IndexedDeserializer};
+use pretty_assertions::assert_eq;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt::Debug,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+/// Name of the environment variable that causes mismatching snapshots to be overwritten
+pub const UPDATE_SNAPSHOTS_ENV: &str = "DASH_RS_UPDATE_SNAPSHOTS";
+
+/// Trait describing how the raw data of a [`Fixture`] should be parsed
+pub trait Snapshot {
+    type Target<'a>: GJFormat<'a> + Deserialize<'a> + Serialize + Debug + for<'b> PartialEq<Self::Target<'b>>;
+
+    /// Canonicalizes a parsed object before it is compared to (or written as) a snapshot
+    ///
+    /// For example, this is where all Thunks should be evaluated
+    fn canonicalize(_target: &mut Self::Target<'_>) {}
+}
+
+/// Loads all fixtures in the given directory. Each fixture is expected to be a subdirectory
+/// containing a `raw` file, and optionally a `processed` snapshot.
+pub fn load_fixtures<S>(fixture_dir: impl AsRef<Path>) -> BTreeMap<PathBuf, Fixture<S>> {
+    let mut map = BTreeMap::new();
+
+    for dir_entry in std::fs::read_dir(fixture_dir).unwrap() {
+        let dir_entry = dir_entry.unwrap();
+
+        assert!(dir_entry.metadata().unwrap().is_dir());
+
+        map.insert(dir_entry.path(), Fixture::from_dir(dir_entry.path()));
+    }
+
+    map
+}
+
+/// A raw response fixture, together with the location of its snapshot
+pub struct Fixture<S> {
+    raw: PathBuf,
+    processed: PathBuf,
+    ghost: PhantomData<S>,
+}
+
+impl<S> Fixture<S> {
+    /// Registers a fixture whose raw data is stored at `raw` and whose snapshot is stored at
+    /// `processed`
+    pub fn new(raw: impl Into<PathBuf>, processed: impl Into<PathBuf>) -> Self {
+        let raw = raw.into();
+
+        assert!(raw.exists(), "fixture {:?} does not exist", raw);
+
+        Fixture {
+            raw,
+            processed: processed.into(),
+            ghost: PhantomData,
+        }
+    }
+
+    /// Registers the fixture stored in `raw` and `processed` files inside the given directory
+    pub fn from_dir(fixture_dir: impl AsRef<Path>) -> Self {
+        Fixture::new(fixture_dir.as_ref().join("raw"), fixture_dir.as_ref().join("processed"))
+    }
+
+    fn load_raw_data(&self) -> String {
+        let raw_data = std::fs::read(&self.raw).unwrap();
+        String::from_utf8(raw_data).unwrap()
+    }
+
+    fn load_snapshot(&self) -> Option<String> {
+        if !self.processed.exists() {
+            return None;
+        }
+
+        let processed_json = std::fs::read(&self.processed).unwrap();
+        Some(String::from_utf8(processed_json).unwrap())
+    }
+}
+
+impl<S: Snapshot> Fixture<S> {
+    /// Asserts that parsing the raw data yields the committed snapshot
+    pub fn assert_snapshot(&self) {
+        let raw = self.load_raw_data();
+        let mut processed = S::Target::from_gj_str(&raw).unwrap();
+        S::canonicalize(&mut processed);
+
+        let update = std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some();
+
+        match self.load_snapshot() {
+            Some(snapshot) => {
+                let snapshot: S::Target<'_> = serde_json::from_str(&snapshot).unwrap();
+
+                if update && snapshot != processed {
+                    self.write_snapshot(&processed)
+                } else {
+                    assert_eq!(snapshot, processed)
+                }
+            },
+            None => {
+                self.write_snapshot(&processed);
+
+                assert!(update, "new snapshot written to {:?}, review and commit it", self.processed);
+            },
+        }
+    }
+
+    /// Asserts that parsing the raw data and serializing the result again yields the raw data (up
+    /// to reordering of indices)
+    pub fn assert_load_save_roundtrip(&self) {
+        let raw = self.load_raw_data();
+        let mut loaded = S::Target::from_gj_str(&raw).unwrap();
+        S::canonicalize(&mut loaded);
+
+        let mut buffer = Vec::new();
+        loaded.write_gj(&mut buffer).unwrap();
+        let saved = std::str::from_utf8(&buffer).unwrap();
+
+        assert_indexed_strings_equal::<S::Target<'static>>(&raw, saved)
+    }
+
+    /// Asserts that serializing the snapshot into RobTop's format and parsing it again yields the
+    /// snapshot
+    pub fn assert_save_load_roundtrip(&self) {
+        let processed_json = self
+            .load_snapshot()
+            .unwrap_or_else(|| panic!("snapshot {:?} does not exist", self.processed));
+        let processed: S::Target<'_> = serde_json::from_str(&processed_json).unwrap();
+
+        let mut buffer = Vec::new();
+        processed.write_gj(&mut buffer).unwrap();
+        let saved = std::str::from_utf8(&buffer).unwrap();
+
+        let mut restored = S::Target::from_gj_str(saved).unwrap();
+        S::canonicalize(&mut restored);
+
+        assert_eq!(processed, restored);
+    }
+
+    fn write_snapshot(&self, processed: &S::Target<'_>) {
+        let mut json = serde_json::to_string_pretty(processed).unwrap();
+        json.push('\n');
+
+        std::fs::write(&self.processed, json).unwrap();
+    }
+}
+
+fn assert_indexed_strings_equal<'a, D: GJFormat<'a>>(a: &str, b: &str) {
+    let mut deserializer_a = IndexedDeserializer::new(a, D::DELIMITER, D::MAP_LIKE);
+    let mut deserializer_b = IndexedDeserializer::new(b, D::DELIMITER, D::MAP_LIKE);
+
+    if D::MAP_LIKE {
+        let map_a = BTreeMap::<&str, &str>::deserialize(&mut deserializer_a).unwrap();
+        let map_b = BTreeMap::<&str, &str>::deserialize(&mut deserializer_b).unwrap();
+
+        // BTreeMap + pretty_assertions will make sure that this is easily interpretable
+        assert_eq!(map_a, map_b);
+    } else {
+        let vec_a = Vec::<&str>::deserialize(&mut deserializer_a).unwrap();
+        let vec_b = Vec::<&str>::deserialize(&mut deserializer_b).unwrap();
+
+        assert_eq!(vec_a, vec_b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load_fixtures, Snapshot};
+    use crate::model::creator::Creator;
+    use std::path::Path;
+
+    enum CreatorSnapshot {}
+
+    impl Snapshot for CreatorSnapshot {
+        type Target<'a> = Creator<'a>;
+    }
+
+    #[test]
+    fn creator_snapshots() {
+        let fixtures = load_fixtures::<CreatorSnapshot>(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests")
+                .join("artifacts")
+                .join("creator"),
+        );
+
+        for fixture in fixtures.values() {
+            fixture.assert_snapshot();
+            fixture.assert_load_save_roundtrip();
+            fixture.assert_save_load_roundtrip();
+        }
+    }
+}
@@ -0,0 +1,314 @@
+//! Module containing a mock Geometry Dash server for integration testing applications built on
+//! dash-rs. Only available with the `testing` feature.
+//!
+//! The official servers ban aggressively, so tests should not talk to them. [`MockServer`] instead
+//! listens on a local port and answers requests with canned responses registered per endpoint,
+//! which can be generated from dash-rs models via the builders in
+//! [`response::build`](crate::response::build). Point your client at it via
+//! [`MockServer::server_config`].
+//!
+//! ```no_run
+//! use dash_rs::{request::user::GET_USER_ENDPOINT, testing::MockServer};
+//!
+//! let server = MockServer::start()?;
+//!
+//! server.respond(GET_USER_ENDPOINT, 200, "1:stadust:2:1710032");
+//!
+//! // ... make requests to `server.server_config()` ...
+//!
+//! assert_eq!(server.requests()[0].endpoint, GET_USER_ENDPOINT);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! ## GD Internals:
+//! Requests to endpoints without a registered response are answered with `-1`, which is how
+//! RobTop's servers signal that nothing was found.
+
+use crate::{
+    http::HttpResponse,
+    model::{creator::Creator, level::Level, song::NewgroundsSong, user::profile::Profile},
+    request::{
+        level::{DOWNLOAD_LEVEL_ENDPOINT, SEARCH_LEVEL_ENDPOINT},
+        user::GET_USER_ENDPOINT,
+        ServerConfig,
+    },
+    response::{build, Paged},
+    SerError,
+};
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// How long the server waits for a client to send its request or accept the response before giving
+/// up on the connection. Connections are handled one at a time, so this bounds how long an idle
+/// client can block the server (and dropping it).
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A request received by a [`MockServer`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecordedRequest {
+    /// The endpoint the request was made to, e.g. `getGJUserInfo20.php`
+    pub endpoint: String,
+
+    /// The (form-encoded) body of the request
+    pub body: String,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    responses: Mutex<HashMap<String, HttpResponse>>,
+    requests: Mutex<Vec<RecordedRequest>>,
+    shutdown: AtomicBool,
+}
+
+/// A local HTTP server imitating the Geometry Dash servers, see the [module level
+/// documentation](self)
+///
+/// The server runs on a background thread, which is stopped when the `MockServer` is dropped.
+#[derive(Debug)]
+pub struct MockServer {
+    address: SocketAddr,
+    base_url: String,
+    state: Arc<State>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Starts a new `MockServer` on some free port on `127.0.0.1`
+    pub fn start() -> io::Result<MockServer> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let address = listener.local_addr()?;
+        let state = Arc::new(State::default());
+
+        let thread = {
+            let state = Arc::clone(&state);
+
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if state.shutdown.load(Ordering::Relaxed) {
+                        break;
+                    }
+
+                    if let Ok(stream) = stream {
+                        if let Err(err) = handle_connection(stream, &state) {
+                            log::warn!("Mock server failed to handle connection: {}", err);
+                        }
+                    }
+                }
+            })
+        };
+
+        Ok(MockServer {
+            address,
+            base_url: format!("http://{}/", address),
+            state,
+            thread: Some(thread),
+        })
+    }
+
+    /// The address this server is listening on
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// The base URL all endpoints of this server are relative to, e.g. `http://127.0.0.1:1234/`
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// A [`ServerConfig`] describing this server, using the same secrets as the official servers
+    pub fn server_config(&self) -> ServerConfig<'_> {
+        ServerConfig::new(&self.base_url)
+    }
+
+    /// Makes this server answer all subsequent requests to the given endpoint (one of the
+    /// `*_ENDPOINT` constants in the submodules of [`request`](crate::request)) with the given
+    /// status code and body
+    pub fn respond(&self, endpoint: &str, status: u16, body: impl Into<String>) {
+        self.state.responses.lock().unwrap().insert(
+            endpoint.to_owned(),
+            HttpResponse {
+                status,
+                body: body.into(),
+                retry_after: None,
+            },
+        );
+    }
+
+    /// Answers level searches with the given page of levels, see [`build::get_gj_levels_response`]
+    pub fn respond_with_levels(
+        &self, levels: &Paged<Level<'_, ()>>, creators: &[Creator<'_>], songs: &[NewgroundsSong<'_>],
+    ) -> Result<(), SerError> {
+        self.respond(SEARCH_LEVEL_ENDPOINT, 200, build::get_gj_levels_response(levels, creators, songs)?);
+
+        Ok(())
+    }
+
+    /// Answers level downloads with the given level, see [`build::download_gj_level_response`]
+    pub fn respond_with_level(&self, level: &Level<'_>) -> Result<(), SerError> {
+        self.respond(DOWNLOAD_LEVEL_ENDPOINT, 200, build::download_gj_level_response(level)?);
+
+        Ok(())
+    }
+
+    /// Answers profile requests with the given profile, see [`build::get_gj_user_info_response`]
+    pub fn respond_with_profile(&self, profile: &Profile<'_>) -> Result<(), SerError> {
+        self.respond(GET_USER_ENDPOINT, 200, build::get_gj_user_info_response(profile)?);
+
+        Ok(())
+    }
+
+    /// All requests this server has received so far, in the order they were received
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.state.shutdown.store(true, Ordering::Relaxed);
+
+        // The server thread is blocked in `accept`, so wake it up by connecting
+        let _ = TcpStream::connect(self.address);
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Handles a single HTTP/1.1 request, closing the connection afterwards
+fn handle_connection(stream: TcpStream, state: &State) -> io::Result<()> {
+    stream.set_read_timeout(Some(CONNECTION_TIMEOUT))?;
+    stream.set_write_timeout(Some(CONNECTION_TIMEOUT))?;
+
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    let endpoint = request_line
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or_default()
+        .trim_start_matches('/')
+        .to_owned();
+
+    let mut content_length = 0;
+
+    loop {
+        let mut header = String::new();
+
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or_default();
+            }
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    let response = state
+        .responses
+        .lock()
+        .unwrap()
+        .get(&endpoint)
+        .cloned()
+        .unwrap_or_else(|| HttpResponse {
+            status: 200,
+            body: "-1".to_owned(),
+            retry_after: None,
+        });
+
+    state.requests.lock().unwrap().push(RecordedRequest {
+        endpoint,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    });
+
+    let mut stream = reader.into_inner();
+
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        if response.status == 200 { "OK" } else { "Mock" },
+        response.body.len(),
+        response.body
+    )?;
+
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MockServer, CONNECTION_TIMEOUT};
+    use crate::request::user::{UserRequest, GET_USER_ENDPOINT};
+    use std::{
+        io::{Read, Write},
+        net::TcpStream,
+        time::Instant,
+    };
+
+    fn post(server: &MockServer, endpoint: &str, form: &str) -> String {
+        let mut stream = TcpStream::connect(server.address()).unwrap();
+
+        write!(
+            stream,
+            "POST /{} HTTP/1.1\r\nContent-Type: application/x-www-form-urlencoded\r\nContent-Length: {}\r\n\r\n{}",
+            endpoint,
+            form.len(),
+            form
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        response.split_once("\r\n\r\n").unwrap().1.to_owned()
+    }
+
+    #[test]
+    fn serves_registered_responses() {
+        let server = MockServer::start().unwrap();
+        let form = server.server_config().build_request(&UserRequest::new(1710032)).unwrap();
+
+        server.respond(GET_USER_ENDPOINT, 200, "1:stadust:2:1710032");
+
+        assert_eq!(post(&server, GET_USER_ENDPOINT, &form), "1:stadust:2:1710032");
+        assert_eq!(post(&server, "getGJLevels21.php", ""), "-1");
+
+        let requests = server.requests();
+
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].endpoint, GET_USER_ENDPOINT);
+        assert_eq!(requests[0].body, form);
+    }
+
+    #[test]
+    fn idle_clients_do_not_block_the_server() {
+        let server = MockServer::start().unwrap();
+        let _idle = TcpStream::connect(server.address()).unwrap();
+        let start = Instant::now();
+
+        server.respond(GET_USER_ENDPOINT, 200, "1:stadust:2:1710032");
+
+        assert_eq!(post(&server, GET_USER_ENDPOINT, ""), "1:stadust:2:1710032");
+
+        drop(server);
+
+        assert!(start.elapsed() < CONNECTION_TIMEOUT * 3);
+    }
+}
@@ -58,6 +58,33 @@ pub(crate) mod default_to_none {
     }
 }
 
+/// Like [`default_to_none`], but additionally maps negative values to [`None`]
+pub(crate) mod non_positive_to_none {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S, T>(to_serialize: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Default + Serialize,
+    {
+        super::default_to_none::serialize(to_serialize, serializer)
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Default + Deserialize<'de> + PartialOrd,
+    {
+        let deserialized = T::deserialize(deserializer)?;
+
+        if deserialized <= T::default() {
+            Ok(None)
+        } else {
+            Ok(Some(deserialized))
+        }
+    }
+}
+
 pub(crate) fn false_to_empty_string<S: Serializer>(b: &bool, serializer: S) -> Result<S::Ok, S::Error> {
     match *b {
         true => serializer.serialize_str("1"),
@@ -72,13 +99,6 @@ pub(crate) fn true_to_two<S: Serializer>(b: &bool, serializer: S) -> Result<S::O
     }
 }
 
-pub(crate) fn true_to_ten<S: Serializer>(b: &bool, serializer: S) -> Result<S::Ok, S::Error> {
-    match *b {
-        true => serializer.serialize_str("10"),
-        false => serializer.serialize_str("0"),
-    }
-}
-
 #[macro_export]
 macro_rules! into_conversion {
     ($for:ty, $proxy_type:ty) => {
@@ -115,7 +135,7 @@ macro_rules! dash_rs_newtype {
                 Self: 'b;
 
             fn to_serialize_proxy(&self) -> &str {
-                use std::borrow::Borrow;
+                use core::borrow::Borrow;
 
                 self.0.borrow()
             }
@@ -124,5 +144,11 @@ macro_rules! dash_rs_newtype {
                 $name(Cow::Borrowed(from))
             }
         }
+
+        impl<'a> $name<'a> {
+            pub fn into_owned(self) -> $name<'static> {
+                $name(Cow::Owned(self.0.into_owned()))
+            }
+        }
     };
 }
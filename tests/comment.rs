@@ -1,15 +1,18 @@
-use dash_rs::model::comment::{
-    level::{CommentUser, LevelComment},
-    profile::ProfileComment,
-};
-use framework::load_test_units;
+use dash_rs::model::comment::level::LevelComment;
+#[cfg(feature = "snapshot")]
+use dash_rs::model::comment::{level::CommentUser, profile::ProfileComment};
+#[cfg(feature = "snapshot")]
+use framework::{load_fixtures, Snapshot};
+#[cfg(feature = "snapshot")]
 use std::path::Path;
 
 mod framework;
 
+#[cfg(feature = "snapshot")]
 enum LevelCommentTester {}
 
-impl framework::Testable for LevelCommentTester {
+#[cfg(feature = "snapshot")]
+impl Snapshot for LevelCommentTester {
     type Target<'a> = LevelComment<'a>;
 
     fn canonicalize(target: &mut Self::Target<'_>) {
@@ -23,8 +26,9 @@ impl framework::Testable for LevelCommentTester {
 }
 
 #[test]
+#[cfg(feature = "snapshot")]
 fn test_level_comment() {
-    let units = load_test_units::<LevelCommentTester>(
+    let units = load_fixtures::<LevelCommentTester>(
         Path::new(env!("CARGO_MANIFEST_DIR"))
             .join("tests")
             .join("artifacts")
@@ -34,21 +38,24 @@ fn test_level_comment() {
     for (path, unit) in units {
         println!("Testing case {:?}", path);
 
-        unit.test_consistency();
-        unit.test_load_save_roundtrip();
-        unit.test_save_load_roundtrip();
+        unit.assert_snapshot();
+        unit.assert_load_save_roundtrip();
+        unit.assert_save_load_roundtrip();
     }
 }
 
+#[cfg(feature = "snapshot")]
 enum CommentUserTester {}
 
-impl framework::Testable for CommentUserTester {
+#[cfg(feature = "snapshot")]
+impl Snapshot for CommentUserTester {
     type Target<'a> = CommentUser<'a>;
 }
 
 #[test]
+#[cfg(feature = "snapshot")]
 fn test_level_comment_user() {
-    let units = load_test_units::<CommentUserTester>(
+    let units = load_fixtures::<CommentUserTester>(
         Path::new(env!("CARGO_MANIFEST_DIR"))
             .join("tests")
             .join("artifacts")
@@ -58,15 +65,17 @@ fn test_level_comment_user() {
     for (path, unit) in units {
         println!("Testing case {:?}", path);
 
-        unit.test_consistency();
-        unit.test_load_save_roundtrip();
-        unit.test_save_load_roundtrip();
+        unit.assert_snapshot();
+        unit.assert_load_save_roundtrip();
+        unit.assert_save_load_roundtrip();
     }
 }
 
+#[cfg(feature = "snapshot")]
 enum ProfileCommentTester {}
 
-impl framework::Testable for ProfileCommentTester {
+#[cfg(feature = "snapshot")]
+impl Snapshot for ProfileCommentTester {
     type Target<'a> = ProfileComment<'a>;
 
     fn canonicalize(target: &mut Self::Target<'_>) {
@@ -77,8 +86,9 @@ impl framework::Testable for ProfileCommentTester {
 }
 
 #[test]
+#[cfg(feature = "snapshot")]
 fn test_profile_comment() {
-    let units = load_test_units::<ProfileCommentTester>(
+    let units = load_fixtures::<ProfileCommentTester>(
         Path::new(env!("CARGO_MANIFEST_DIR"))
             .join("tests")
             .join("artifacts")
@@ -88,8 +98,26 @@ fn test_profile_comment() {
     for (path, unit) in units {
         println!("Testing case {:?}", path);
 
-        unit.test_consistency();
-        unit.test_load_save_roundtrip();
-        unit.test_save_load_roundtrip();
+        unit.assert_snapshot();
+        unit.assert_load_save_roundtrip();
+        unit.assert_save_load_roundtrip();
     }
 }
+
+#[test]
+fn comment_markup_round_trip() {
+    use dash_rs::{crypto::base64_encode, model::comment::markup::Markup, GJFormat};
+
+    let raw = format!(
+        "2~{}~3~7226087~4~104~6~258976~7~0~9~5 days~10~0~11~2~12~75,255,75",
+        base64_encode(b"GG <cg>nice</c> level <cr>!!")
+    );
+    let mut comment = LevelComment::from_gj_str(&raw).unwrap();
+
+    assert_eq!(comment.plain_content().unwrap().unwrap(), "GG nice level !!");
+
+    let markup = comment.markup().unwrap().unwrap().to_string();
+    comment.set_markup(&Markup::parse(&markup));
+
+    assert_eq!(comment.to_robtop_string().unwrap(), raw);
+}
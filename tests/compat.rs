@@ -0,0 +1,27 @@
+//! Tests for parsing responses of servers running older versions of the game. These set the global
+//! compatibility profile, and thus live in their own test binary.
+
+use dash_rs::{
+    model::level::Level,
+    request::{CompatProfile, GD_SERVER_COMPAT_PROFILE},
+    GJFormat,
+};
+
+#[test]
+fn test_level_without_2x_indices() {
+    GD_SERVER_COMPAT_PROFILE.get_or_init(|| CompatProfile::Gd19);
+
+    // A listed level lacking all indices introduced in 2.x, as returned by a 1.9 server
+    //
+    // FIXME: This is hand-written rather than captured from an actual 1.9 server, so it only covers
+    // the indices we know to be missing. Replace it with a real capture.
+    let raw = "1:128:2:1st level:5:1:6:4:8:10:9:30:10:1234:12:3:13:19:14:12:17::25::18:0:19:0:3:SGVsbG8=:15:1:30:0:31:0";
+
+    let level = Level::<()>::from_gj_str(raw).unwrap();
+
+    assert_eq!(level.level_id, 128);
+    assert_eq!(level.rating.coin_amount, 0);
+    assert_eq!(level.rating.epic, 0);
+    assert_eq!(level.stars_requested, None);
+    assert_eq!(level.object_amount, None);
+}
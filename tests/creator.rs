@@ -1,18 +1,20 @@
+#![cfg(feature = "snapshot")]
+
 use dash_rs::model::creator::Creator;
-use framework::load_test_units;
+use framework::{load_fixtures, Snapshot};
 use std::path::Path;
 
 mod framework;
 
 enum CreatorTester {}
 
-impl framework::Testable for CreatorTester {
+impl Snapshot for CreatorTester {
     type Target<'a> = Creator<'a>;
 }
 
 #[test]
 fn test_creator() {
-    let units = load_test_units::<CreatorTester>(
+    let units = load_fixtures::<CreatorTester>(
         Path::new(env!("CARGO_MANIFEST_DIR"))
             .join("tests")
             .join("artifacts")
@@ -22,8 +24,8 @@ fn test_creator() {
     for (path, unit) in units {
         println!("Testing case {:?}", path);
 
-        unit.test_consistency();
-        unit.test_load_save_roundtrip();
-        unit.test_save_load_roundtrip();
+        unit.assert_snapshot();
+        unit.assert_load_save_roundtrip();
+        unit.assert_save_load_roundtrip();
     }
 }
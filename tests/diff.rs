@@ -0,0 +1,49 @@
+use dash_rs::{
+    diff::{diff_comments, diff_levels, Change},
+    model::{comment::level::LevelComment, level::Level},
+    GJFormat,
+};
+
+const LEVEL: &str = "1:63355989:2:Fantasy:5:3:6:15557115:8:10:9:40:10:23324:12:0:13:21:14:2141:17::43:5:25::18:7:19:24978:42:0:45:37865:3:Q29sbGFiIHdpdGggQnJpbmRpa3osIHRoYW5rIHlvdSBmb3IgdGhpcyBsZXZlbCB1d3UsIEVOSk9ZISEg:15:3:30:63309629:31:0:37:2:38:1:39:7:46:1:47:2:35:771517";
+const LEVEL_LATER: &str = "1:63355989:2:Fantasy:5:3:6:15557115:8:10:9:40:10:23400:12:0:13:21:14:2150:17::43:5:25::18:7:19:24978:42:0:45:37865:3:Q29sbGFiIHdpdGggQnJpbmRpa3osIHRoYW5rIHlvdSBmb3IgdGhpcyBsZXZlbCB1d3UsIEVOSk9ZISEg:15:3:30:63309629:31:0:37:2:38:1:39:7:46:1:47:2:35:771517";
+
+const COMMENT_1: &str = "2~U3BlY2lhbCB0aGFua3MgdG8gSGFkbywgQ2luY2ksIFN5bmFjdGl2ZSwgQ29vbCwgUHJpc20sIFN1Yndvb2ZlciwgYW5kIEhhZG8gZm9yIHBsYXl0ZXN0aW5nLg==~3~7226087~4~104~7~0~10~0~9~5 days~6~258976~11~2~12~75,255,75";
+const COMMENT_1_LATER: &str = "2~U3BlY2lhbCB0aGFua3MgdG8gSGFkbywgQ2luY2ksIFN5bmFjdGl2ZSwgQ29vbCwgUHJpc20sIFN1Yndvb2ZlciwgYW5kIEhhZG8gZm9yIHBsYXl0ZXN0aW5nLg==~3~7226087~4~101~7~0~10~0~9~6 days~6~258976~11~2~12~75,255,75";
+const COMMENT_2: &str =
+    "2~TGV0cyBtYWtlIGF1Z3VzdCAxMHRoIFBhdXplJ3MgaW50ZXJuYXRpb25hbCBkYXk=~3~7178197~4~58~7~0~10~0~9~5 days~6~259333~11~1~12~255,255,255";
+
+#[test]
+fn diff_level_stats() {
+    let old: Level<()> = Level::from_gj_str(LEVEL).unwrap();
+    let new: Level<()> = Level::from_gj_str(LEVEL_LATER).unwrap();
+
+    assert!(diff_levels(&old, &old).is_empty());
+
+    let diff = diff_levels(&old, &new);
+
+    assert_eq!(diff.downloads.map(|change| change.delta()), Some(76));
+    assert_eq!(diff.likes, Some(Change { old: 2141, new: 2150 }));
+    assert_eq!(diff.rating, None);
+}
+
+#[test]
+fn diff_comment_votes() {
+    let old = [LevelComment::from_gj_str(COMMENT_1).unwrap()];
+    let new = [
+        LevelComment::from_gj_str(COMMENT_2).unwrap(),
+        LevelComment::from_gj_str(COMMENT_1_LATER).unwrap(),
+    ];
+
+    let diff = diff_comments(&old, &new);
+
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.added[0].comment_id, 259333);
+    assert!(diff.removed.is_empty());
+    assert_eq!(diff.likes.len(), 1);
+    assert_eq!(diff.likes[0].1.delta(), -3);
+
+    let diff = diff_comments(&new, &old);
+
+    assert_eq!(diff.removed.len(), 1);
+    assert!(diff.added.is_empty());
+}
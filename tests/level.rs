@@ -1,13 +1,19 @@
-use std::path::Path;
+use std::{borrow::Cow, path::Path};
 
-use dash_rs::model::level::Level;
-use framework::load_test_units;
+use dash_rs::{
+    model::level::{Level, LevelObjectBuffer, OwnedLevel},
+    GJFormat,
+};
+#[cfg(feature = "snapshot")]
+use framework::{load_fixtures, Snapshot};
 
 mod framework;
 
+#[cfg(feature = "snapshot")]
 enum LevelTester {}
 
-impl framework::Testable for LevelTester {
+#[cfg(feature = "snapshot")]
+impl Snapshot for LevelTester {
     type Target<'a> = Level<'a, ()>;
 
     fn canonicalize(level: &mut Self::Target<'_>) {
@@ -18,8 +24,9 @@ impl framework::Testable for LevelTester {
 }
 
 #[test]
+#[cfg(feature = "snapshot")]
 fn test_listed_level() {
-    let units = load_test_units::<LevelTester>(
+    let units = load_fixtures::<LevelTester>(
         Path::new(env!("CARGO_MANIFEST_DIR"))
             .join("tests")
             .join("artifacts")
@@ -29,15 +36,17 @@ fn test_listed_level() {
     for (path, unit) in units {
         println!("Testing case {:?}", path);
 
-        unit.test_consistency();
-        unit.test_load_save_roundtrip();
-        unit.test_save_load_roundtrip();
+        unit.assert_snapshot();
+        unit.assert_load_save_roundtrip();
+        unit.assert_save_load_roundtrip();
     }
 }
 
+#[cfg(feature = "snapshot")]
 enum FullLevelTester {}
 
-impl framework::Testable for FullLevelTester {
+#[cfg(feature = "snapshot")]
+impl Snapshot for FullLevelTester {
     type Target<'a> = Level<'a>;
 
     fn canonicalize(level: &mut Self::Target<'_>) {
@@ -50,14 +59,68 @@ impl framework::Testable for FullLevelTester {
 }
 
 #[test]
+#[cfg(feature = "snapshot")]
 fn test_full_level() {
-    let units = load_test_units::<FullLevelTester>(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("artifacts").join("level"));
+    let units = load_fixtures::<FullLevelTester>(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("artifacts").join("level"));
 
     for (path, unit) in units {
         println!("Testing case {:?}", path);
 
-        unit.test_consistency();
+        unit.assert_snapshot();
         // Cannot do round trip testing for onw, as the level data handling in dash-rs is incomplete
         // (to put it nicely)
     }
 }
+
+#[test]
+fn test_level_into_owned() {
+    let raw = std::fs::read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("artifacts")
+            .join("level")
+            .join("897837")
+            .join("raw"),
+    )
+    .unwrap();
+
+    let borrowed = Level::from_gj_str(&raw).unwrap();
+    let owned: OwnedLevel = borrowed.into_owned();
+
+    drop(raw);
+
+    assert_eq!(owned.level_id, 897837);
+    assert!(matches!(owned.name, Cow::Owned(_)));
+}
+
+#[test]
+fn test_level_without_2x_indices() {
+    // Hand-written, see `tests/compat.rs`. Since the default profile is 2.2, the indices introduced
+    // in 2.x are required
+    let raw = "1:128:2:1st level:5:1:6:4:8:10:9:30:10:1234:12:3:13:19:14:12:17::25::18:0:19:0:3:SGVsbG8=:15:1:30:0:31:0";
+
+    assert!(Level::<()>::from_gj_str(raw).is_err());
+}
+
+#[test]
+fn test_parse_objects_into_buffer() {
+    // The decompressed level string of this level spans many of the chunks `parse_objects_into`
+    // processes at a time
+    let raw = include_str!("artifacts/level/11774780/raw").trim();
+    let mut level: Level = Level::from_gj_str(raw).unwrap();
+    let mut buffer = LevelObjectBuffer::new();
+
+    level.level_data.parse_objects_into(&mut buffer).unwrap();
+
+    let objects = level.level_data.level_data.process().unwrap();
+
+    assert_eq!(buffer.meta(), &objects.meta);
+    assert_eq!(buffer.objects(), &objects.objects[..]);
+
+    // Already processed level data is copied into the buffer instead of being re-encoded
+    let mut copied = LevelObjectBuffer::new();
+
+    level.level_data.parse_objects_into(&mut copied).unwrap();
+
+    assert_eq!(copied.objects(), buffer.objects());
+}
@@ -0,0 +1,112 @@
+use std::borrow::Cow;
+
+use dash_rs::{
+    limits::{Limit, LimitExceeded, Limits},
+    model::{
+        level::{LevelObjectBuffer, LevelProcessError, Objects},
+        song::NewgroundsSong,
+    },
+    DeError, GJFormat, Thunk, ThunkProcessor,
+};
+
+const LEVEL_DATA: &str = "H4sIAAAAAAACA8t2NNExtDbUMdQx0jE01TEGEkCeBZBnAuUBAA3b16giAAAA";
+const SONG: &str = "1~|~771277~|~2~|~Random Song~|~3~|~50531~|~4~|~Artist~|~5~|~8.31~|~6~|~~|~10~|~https%3A%2F%2Faudio.ngfiles.com%2F771000%2F771277_Random-Song.mp3~|~7~|~~|~8~|~1";
+
+#[test]
+fn decompressed_level_size_is_limited() {
+    // The level data decompresses to 34 bytes
+    let limits = Limits {
+        max_decompressed_level_size: 33,
+        ..Limits::DEFAULT
+    };
+
+    assert!(matches!(
+        Objects::from_unprocessed_with_limits(Cow::Borrowed(LEVEL_DATA), &limits),
+        Err(LevelProcessError::LimitExceeded(LimitExceeded {
+            limit: Limit::DecompressedLevelSize,
+            max: 33
+        }))
+    ));
+
+    let mut buffer = LevelObjectBuffer::with_limits(limits);
+
+    assert!(matches!(
+        dash_rs::model::level::parse_objects_into(LEVEL_DATA, &mut buffer),
+        Err(LevelProcessError::LimitExceeded(_))
+    ));
+
+    let limits = Limits {
+        max_decompressed_level_size: 34,
+        ..Limits::DEFAULT
+    };
+
+    assert_eq!(
+        Objects::from_unprocessed_with_limits(Cow::Borrowed(LEVEL_DATA), &limits)
+            .unwrap()
+            .objects
+            .len(),
+        2
+    );
+
+    // Other calls are unaffected
+    assert_eq!(Objects::from_unprocessed(Cow::Borrowed(LEVEL_DATA)).unwrap().objects.len(), 2);
+}
+
+#[test]
+fn unbounded_decompressed_level_size() {
+    let limits = Limits {
+        max_decompressed_level_size: usize::MAX,
+        ..Limits::DEFAULT
+    };
+
+    assert_eq!(
+        Objects::from_unprocessed_with_limits(Cow::Borrowed(LEVEL_DATA), &limits)
+            .unwrap()
+            .objects
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn base64_length_is_limited() {
+    let limits = Limits {
+        max_base64_length: LEVEL_DATA.len() - 1,
+        ..Limits::DEFAULT
+    };
+
+    let mut thunk = Thunk::<Objects>::Unprocessed(Cow::Borrowed(LEVEL_DATA));
+
+    assert!(matches!(
+        thunk.process_with_limits(&limits),
+        Err(LevelProcessError::LimitExceeded(LimitExceeded {
+            limit: Limit::Base64Length,
+            ..
+        }))
+    ));
+    assert!(thunk.process().is_ok());
+}
+
+#[test]
+fn input_length_is_limited() {
+    let limits = Limits {
+        max_input_length: SONG.len() - 1,
+        ..Limits::DEFAULT
+    };
+
+    assert!(matches!(
+        NewgroundsSong::from_gj_str_with_limits(SONG, &limits),
+        Err(DeError::LimitExceeded(LimitExceeded {
+            limit: Limit::InputLength,
+            ..
+        }))
+    ));
+
+    let limits = Limits {
+        max_input_length: SONG.len(),
+        ..Limits::DEFAULT
+    };
+
+    assert!(NewgroundsSong::from_gj_str_with_limits(SONG, &limits).is_ok());
+    assert!(NewgroundsSong::from_gj_str(SONG).is_ok());
+}
@@ -3,8 +3,6 @@ use dash_rs::{
     GJFormat,
 };
 
-mod framework;
-
 const CREO_DUNE_DATA_TOO_MANY_FIELDS: &str = "1~|~771277~|~54~|~should be ignored~|~2~|~Creo - \
                                               Dune~|~3~|~50531~|~4~|~CreoMusic~|~5~|~8.\
                                               03~|~6~|~~|~7~|~UCsCWA3Y3JppL6feQiMRgm6Q~|~8~|~1~|~10~|~https%3A%2F%2Faudio.ngfiles.com%\
@@ -18,3 +16,13 @@ fn deserialize_too_many_fields() {
     NewgroundsSong::from_gj_str(CREO_DUNE_DATA_TOO_MANY_FIELDS).unwrap();
     Creator::from_gj_str(CREATOR_REGISTERED_DATA_TOO_MANY_FIELDS).unwrap();
 }
+
+#[test]
+fn robtop_string_round_trip() {
+    let creator = Creator::from_robtop_str("4170784:Serponge:119741").unwrap();
+    let serialized = creator.to_robtop_string().unwrap();
+
+    assert_eq!(serialized, "4170784:Serponge:119741");
+    assert_eq!(creator.display_robtop().to_string(), serialized);
+    assert_eq!(Creator::from_robtop_str(&serialized).unwrap(), creator);
+}
@@ -0,0 +1,63 @@
+//! Tests that all models can be persisted to (and restored from) formats other than RobTop's, both
+//! self-describing (JSON) and non-self-describing (bincode) ones
+
+use std::path::{Path, PathBuf};
+
+use dash_rs::model::{
+    comment::{
+        level::{CommentUser, LevelComment},
+        profile::ProfileComment,
+    },
+    creator::Creator,
+    level::Level,
+    song::NewgroundsSong,
+    user::{profile::Profile, searched::SearchedUser},
+};
+use pretty_assertions::assert_eq;
+
+fn load_processed_artifacts(kind: &str) -> Vec<(PathBuf, String)> {
+    let artifact_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("artifacts").join(kind);
+
+    std::fs::read_dir(artifact_dir)
+        .unwrap()
+        .map(|dir_entry| {
+            let processed = dir_entry.unwrap().path().join("processed");
+            let json = std::fs::read_to_string(&processed).unwrap();
+
+            (processed, json)
+        })
+        .collect()
+}
+
+macro_rules! persistence_test {
+    ($name:ident, $kind:literal, $target:ty) => {
+        #[test]
+        fn $name() {
+            for (path, json) in load_processed_artifacts($kind) {
+                println!("Testing case {:?}", path);
+
+                let value: $target = serde_json::from_str(&json).unwrap();
+
+                let json = serde_json::to_string(&value).unwrap();
+                let restored: $target = serde_json::from_str(&json).unwrap();
+
+                assert_eq!(value, restored);
+
+                let bytes = bincode::serialize(&value).unwrap();
+                let restored: $target = bincode::deserialize(&bytes).unwrap();
+
+                assert_eq!(value, restored);
+            }
+        }
+    };
+}
+
+persistence_test!(persist_level, "level", Level<'_>);
+persistence_test!(persist_listed_level, "listed_level", Level<'_, ()>);
+persistence_test!(persist_creator, "creator", Creator<'_>);
+persistence_test!(persist_song, "song", NewgroundsSong<'_>);
+persistence_test!(persist_profile, "profile", Profile<'_>);
+persistence_test!(persist_searched_user, "searched_user", SearchedUser<'_>);
+persistence_test!(persist_level_comment, "level_comment", LevelComment<'_>);
+persistence_test!(persist_comment_user, "comment_user", CommentUser<'_>);
+persistence_test!(persist_profile_comment, "profile_comment", ProfileComment<'_>);
@@ -1,18 +1,28 @@
-use dash_rs::model::user::profile::Profile;
-use framework::load_test_units;
+use dash_rs::{
+    model::user::{
+        profile::{CompletionStats, DemonStats, Profile},
+        Rank,
+    },
+    GJFormat,
+};
+#[cfg(feature = "snapshot")]
+use framework::{load_fixtures, Snapshot};
 use std::path::Path;
 
 mod framework;
 
+#[cfg(feature = "snapshot")]
 enum ProfileTester {}
 
-impl framework::Testable for ProfileTester {
+#[cfg(feature = "snapshot")]
+impl Snapshot for ProfileTester {
     type Target<'a> = Profile<'a>;
 }
 
 #[test]
+#[cfg(feature = "snapshot")]
 fn test_profile() {
-    let units = load_test_units::<ProfileTester>(
+    let units = load_fixtures::<ProfileTester>(
         Path::new(env!("CARGO_MANIFEST_DIR"))
             .join("tests")
             .join("artifacts")
@@ -22,8 +32,98 @@ fn test_profile() {
     for (path, unit) in units {
         println!("Testing case {:?}", path);
 
-        unit.test_consistency();
-        unit.test_load_save_roundtrip();
-        unit.test_save_load_roundtrip();
+        unit.assert_snapshot();
+        unit.assert_load_save_roundtrip();
+        unit.assert_save_load_roundtrip();
+    }
+}
+
+#[test]
+fn test_profile_builder() {
+    let profile = Profile::builder("stardust1971", 2073761, 8451)
+        .stars(13791)
+        .cube_index(95)
+        .global_rank(13731)
+        .twitter("stadust1971")
+        .build();
+
+    let mut bytes = Vec::new();
+    profile.write_gj(&mut bytes).unwrap();
+
+    let serialized = String::from_utf8(bytes).unwrap();
+
+    assert_eq!(Profile::from_gj_str(&serialized).unwrap(), profile);
+}
+
+#[test]
+fn test_profile_22_stats() {
+    let raw = std::fs::read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("artifacts")
+            .join("profile")
+            .join("8451")
+            .join("raw"),
+    )
+    .unwrap()
+        + ":55:1,2,3,4,5,6,7,8,9,10,11,12:56:1,2,3,4,5,6,7,8:57:1,2,3,4,5,6,7";
+    let profile = Profile::from_gj_str(&raw).unwrap();
+
+    let demons = profile.demon_stats.unwrap().into_processed().unwrap();
+
+    assert_eq!(demons.extreme, 5);
+    assert_eq!(demons.gauntlet, 12);
+    assert_eq!(demons.classic(), 15);
+    assert_eq!(demons.platformer(), 40);
+
+    let classic = profile.classic_completions.unwrap().into_processed().unwrap();
+    let platformer = profile.platformer_completions.unwrap().into_processed().unwrap();
+
+    assert_eq!(classic.daily, Some(7));
+    assert_eq!(
+        platformer,
+        CompletionStats {
+            auto: 1,
+            easy: 2,
+            normal: 3,
+            hard: 4,
+            harder: 5,
+            insane: 6,
+            daily: None,
+            gauntlet: 7
+        }
+    );
+    assert_eq!(DemonStats::default().classic(), 0);
+}
+
+#[test]
+fn test_profile_global_rank() {
+    let raw = std::fs::read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("artifacts")
+            .join("profile")
+            .join("8451")
+            .join("raw"),
+    )
+    .unwrap();
+    let profile = Profile::from_gj_str(&raw).unwrap();
+
+    assert_eq!(profile.global_rank, Rank::new(13731));
+    assert_eq!(profile.global_rank.unwrap().to_string(), "#13731");
+    assert!(profile.is_creator());
+    assert!(!profile.is_leaderboard_banned());
+
+    for unranked in ["30:0:", "30:-1:"].iter() {
+        let raw = raw.replace("30:13731:", unranked);
+        let profile = Profile::from_gj_str(&raw).unwrap();
+
+        assert_eq!(profile.global_rank, None);
+        assert!(profile.is_leaderboard_banned());
+
+        let mut bytes = Vec::new();
+        profile.write_gj(&mut bytes).unwrap();
+
+        assert!(String::from_utf8(bytes).unwrap().contains(":30:0:"));
     }
 }
@@ -23,3 +23,338 @@ fn process_get_gj_levels_response() {
         }
     }
 }
+
+#[test]
+fn malformed_fragment_is_captured() {
+    let err = dash_rs::response::parse_get_gj_users_response("1:stardust1971:2:not a number").unwrap_err();
+
+    match err {
+        dash_rs::response::ResponseError::MalformedFragment {
+            endpoint, key, fragment, ..
+        } => {
+            assert_eq!(endpoint, dash_rs::request::user::SEARCH_USER_ENDPOINT);
+            assert_eq!(key, Some("not a number"));
+            assert_eq!(fragment, "1:stardust1971:2:not a number");
+        },
+        _ => panic!("expected a malformed fragment error, got {:?}", err),
+    }
+}
+
+#[test]
+fn malformed_fragment_capture_is_bounded() {
+    use dash_rs::response::{parse_get_gj_levels_response_with_quirks, Quirks, ResponseError};
+
+    let (levels, rest) = GET_GJ_LEVELS_RESPONSE.split_once('#').unwrap();
+    let response = format!("1:63355989:2:Broken:5:nope|{}#{}", levels, rest);
+
+    let quirks = Quirks {
+        fragment_capture_limit: 10,
+        ..Quirks::default()
+    };
+
+    match parse_get_gj_levels_response_with_quirks(&response, quirks).unwrap_err() {
+        ResponseError::MalformedFragment { key, fragment, .. } => {
+            assert_eq!(key, Some("63355989"));
+            assert_eq!(fragment, "1:63355989");
+        },
+        err => panic!("expected a malformed fragment error, got {:?}", err),
+    }
+
+    let quirks = Quirks {
+        fragment_capture_limit: 0,
+        ..Quirks::default()
+    };
+
+    assert!(matches!(
+        parse_get_gj_levels_response_with_quirks(&response, quirks),
+        Err(ResponseError::De(_))
+    ));
+}
+
+#[test]
+fn lossy_mode_skips_malformed_levels() {
+    let (levels, rest) = GET_GJ_LEVELS_RESPONSE.split_once('#').unwrap();
+    let response = format!("{}|1:63355989:2:Broken:5:nope#{}", levels, rest);
+
+    assert!(dash_rs::response::parse_get_gj_levels_response(&response).is_err());
+
+    let lossy = dash_rs::response::parse_get_gj_levels_response_lossy(&response).unwrap();
+
+    assert_eq!(lossy.items.len(), 10);
+    assert_eq!(lossy.errors.len(), 1);
+
+    match &lossy.errors[0] {
+        dash_rs::response::ResponseError::MalformedFragment {
+            error: dash_rs::DeError::Custom { index, value, offset, .. },
+            ..
+        } => {
+            assert_eq!(*index, Some("5"));
+            assert_eq!(*value, Some("nope"));
+            assert_eq!(*offset, Some(22));
+        },
+        err => panic!("expected a malformed fragment error, got {:?}", err),
+    }
+}
+
+#[test]
+fn parse_deletion_responses() {
+    use dash_rs::response::{parse_delete_gj_acc_comment_response, DeletionOutcome, ResponseError};
+
+    assert_eq!(parse_delete_gj_acc_comment_response("1").unwrap(), DeletionOutcome::Done);
+    assert_eq!(parse_delete_gj_acc_comment_response("-1").unwrap(), DeletionOutcome::Rejected(-1));
+    assert_eq!(parse_delete_gj_acc_comment_response("-2").unwrap(), DeletionOutcome::Rejected(-2));
+    assert!(matches!(
+        parse_delete_gj_acc_comment_response("0"),
+        Err(ResponseError::UnexpectedFormat)
+    ));
+    assert!(matches!(
+        parse_delete_gj_acc_comment_response("2"),
+        Err(ResponseError::UnexpectedFormat)
+    ));
+    assert!(parse_delete_gj_acc_comment_response("<html>").is_err());
+}
+
+#[test]
+fn parse_mod_access_responses() {
+    use dash_rs::{model::user::ModLevel, response::parse_request_user_access_response};
+
+    assert_eq!(parse_request_user_access_response("-1").unwrap(), ModLevel::None);
+    assert_eq!(parse_request_user_access_response("1").unwrap(), ModLevel::Normal);
+    assert_eq!(parse_request_user_access_response("2").unwrap(), ModLevel::Elder);
+    assert!(parse_request_user_access_response("").is_err());
+}
+
+#[test]
+fn process_get_gj_comment_history_response() {
+    let response =
+        "1~897837~2~R0c=~3~7226087~4~12~7~0~10~100~9~5 days~6~258976~11~0:1~Pauze~9~58~10~18~11~16~14~0~15~2~16~1705254#25:10:10";
+    let page = dash_rs::response::parse_get_gj_comment_history_response(response).unwrap();
+
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].level_id, Some(897837));
+    assert_eq!(page.page(), 1);
+    assert_eq!(page.page_count(), 3);
+    assert!(page.has_next_page());
+}
+
+#[test]
+fn pagination_does_not_overflow() {
+    let response = "1~897837~2~R0c=~3~7226087~4~12~7~0~10~100~9~5 days~6~258976~11~0:1~Pauze~9~58~10~18~11~16~14~0~15~2~16~1705254#4294967295:4294967295:10";
+    let page = dash_rs::response::parse_get_gj_comment_history_response(response).unwrap();
+
+    assert_eq!(page.page_count(), 429496730);
+    assert_eq!(page.page(), 429496729);
+    assert!(!page.has_next_page());
+}
+
+#[test]
+fn process_get_gj_levels_response_paged() {
+    let page = dash_rs::response::parse_get_gj_levels_response_paged(GET_GJ_LEVELS_RESPONSE).unwrap();
+
+    assert_eq!(page.items.len(), 10);
+    assert_eq!(page.total, 11389);
+    assert_eq!(page.offset, 0);
+    assert_eq!(page.page_size, 10);
+    assert_eq!(page.page_count(), 1139);
+}
+
+#[test]
+fn process_inline_level_data_quirk() {
+    use dash_rs::response::{parse_get_gj_levels_response_with_quirks, Quirks};
+
+    let raw_level = std::fs::read_to_string("tests/artifacts/level/897837/raw").unwrap();
+    let response = format!("{}##", raw_level);
+
+    let levels = parse_get_gj_levels_response_with_quirks(&response, Quirks::default()).unwrap();
+    assert!(levels[0].level_data.is_none());
+
+    let levels = parse_get_gj_levels_response_with_quirks(
+        &response,
+        Quirks {
+            inline_level_data: true,
+            ..Quirks::default()
+        },
+    )
+    .unwrap();
+    assert!(levels[0].level_data.is_some());
+
+    let levels = parse_get_gj_levels_response_with_quirks(
+        GET_GJ_LEVELS_RESPONSE,
+        Quirks {
+            inline_level_data: true,
+            ..Quirks::default()
+        },
+    )
+    .unwrap();
+    assert!(levels.iter().all(|level| level.level_data.is_none()));
+}
+
+#[test]
+fn process_levels_with_server_compat_profile() {
+    use dash_rs::{
+        request::{CompatProfile, ServerConfig},
+        response::{parse_get_gj_levels_response_with_quirks, Quirks},
+    };
+
+    // Hand-written, see `tests/compat.rs`
+    let raw = "1:128:2:1st level:5:1:6:4:8:10:9:30:10:1234:12:3:13:19:14:12:17::25::18:0:19:0:3:SGVsbG8=:15:1:30:0:31:0";
+    let response = format!("{}##", raw);
+    let quirks = ServerConfig::new("https://example.com/").compat(CompatProfile::Gd19).quirks();
+
+    // This test binary does not set the global profile, so it is 2.2
+    assert!(parse_get_gj_levels_response_with_quirks(&response, Quirks::default()).is_err());
+
+    let levels = parse_get_gj_levels_response_with_quirks(&response, quirks).unwrap();
+    assert_eq!(levels[0].level_id, 128);
+    assert_eq!(levels[0].rating.coin_amount, 0);
+    assert_eq!(levels[0].object_amount, None);
+
+    // 2.0 servers already include the coin indices, but not the ones introduced in 2.1
+    let quirks = Quirks {
+        compat: CompatProfile::Gd20,
+        ..Quirks::default()
+    };
+    assert!(parse_get_gj_levels_response_with_quirks(&response, quirks).is_err());
+}
+
+#[test]
+fn verify_level_download_checksums() {
+    use dash_rs::response::{parse_download_gj_level_response_checked, ResponseError};
+
+    let level = include_str!("artifacts/level/897837/raw").trim();
+    // FIXME: These were computed via `dash_rs::crypto` rather than taken from an actual server
+    // response, so they only guard against regressions, not against the hashing being wrong in the
+    // first place. Replace them with the checksums of a captured downloadGJLevel22 response.
+    let data_hash = "bf337d690d496f0d4744f4987f33879545168285";
+    let info_hash = "f153089c6726a8faef998600b2645dfbbb76eb74";
+
+    let response = format!("{}#{}#{}", level, data_hash, info_hash);
+
+    assert_eq!(parse_download_gj_level_response_checked(&response).unwrap().level_id, 897837);
+
+    let tampered = response.replace(":18:10:", ":18:1:");
+
+    match parse_download_gj_level_response_checked(&tampered).unwrap_err() {
+        ResponseError::ChecksumMismatch { expected, .. } => assert_eq!(expected, info_hash),
+        err => panic!("expected a checksum mismatch, got {:?}", err),
+    }
+
+    assert!(matches!(
+        parse_download_gj_level_response_checked(level),
+        Err(ResponseError::UnexpectedFormat)
+    ));
+}
+
+#[test]
+fn parse_rewards_response() {
+    use dash_rs::{
+        crypto::{xor_encode, REWARDS_XOR_KEY},
+        response::parse_get_gj_rewards_response,
+    };
+    use std::time::{Duration, Instant};
+
+    let payload = "Abcde:4170784:1234:S1234:1710032:3600:25,2,1,0:12:0:100,20,0,0:3:1";
+    let response = format!("qwert{}|abcdef", xor_encode(payload.as_bytes(), REWARDS_XOR_KEY));
+
+    let rewards = parse_get_gj_rewards_response(&response).unwrap();
+
+    assert_eq!(rewards.user_id, 4170784);
+    assert_eq!(rewards.account_id, 1710032);
+    assert_eq!(rewards.small_chest.content.orbs, 25);
+    assert_eq!(rewards.small_chest.content.items, [1, 0]);
+    assert_eq!(rewards.small_chest.times_opened, 12);
+    assert!(rewards.large_chest.cooldown.is_ready());
+    assert_eq!(rewards.next_chest(), rewards.large_chest.cooldown);
+
+    let now = Instant::now();
+
+    assert_eq!(rewards.small_chest.cooldown.deadline(now), now + Duration::from_secs(3600));
+}
+
+#[test]
+fn parse_challenges_response() {
+    use dash_rs::{
+        crypto::{xor_encode, CHALLENGES_XOR_KEY},
+        model::reward::QuestKind,
+        response::parse_get_gj_challenges_response,
+    };
+
+    let payload = "Abcde:4170784:1234:S1234:1710032:7200:1,1,200,10,Orb Finder:2,2,2,15,Coin Finder:3,3,5,20,Star Finder";
+    let response = format!("qwert{}|abcdef", xor_encode(payload.as_bytes(), CHALLENGES_XOR_KEY));
+
+    let quests = parse_get_gj_challenges_response(&response).unwrap();
+
+    assert_eq!(quests.next_quest.seconds, 7200);
+    assert_eq!(quests.quests.len(), 3);
+    assert_eq!(quests.quests[1].kind, QuestKind::Coins);
+    assert_eq!(quests.quests[2].name, "Star Finder");
+}
+
+#[test]
+fn build_responses_round_trip() {
+    use dash_rs::{
+        model::{creator::Creator, level::Level, song::NewgroundsSong, user::profile::Profile},
+        response::{
+            build::{download_gj_level_response, get_gj_levels_response, get_gj_user_info_response},
+            parse_download_gj_level_response_checked, parse_get_gj_levels_response_paged, parse_get_gj_user_info_response, Paged,
+        },
+        GJFormat,
+    };
+
+    let mut sections = GET_GJ_LEVELS_RESPONSE.split('#');
+    let levels: Vec<Level<()>> = sections
+        .next()
+        .unwrap()
+        .split('|')
+        .map(|level| Level::from_gj_str(level).unwrap())
+        .collect();
+    let creators: Vec<Creator> = sections
+        .next()
+        .unwrap()
+        .split('|')
+        .map(|creator| Creator::from_gj_str(creator).unwrap())
+        .collect();
+    let songs: Vec<NewgroundsSong> = sections
+        .next()
+        .unwrap()
+        .split("~:~")
+        .map(|song| NewgroundsSong::from_gj_str(song).unwrap())
+        .collect();
+    let page = Paged {
+        items: levels,
+        total: 11389,
+        offset: 0,
+        page_size: 10,
+    };
+
+    let response = get_gj_levels_response(&page, &creators, &songs).unwrap();
+
+    assert!(response.ends_with("#11389:0:10#f687963dcfd37f857633563ee28b0cfadc727c97"));
+
+    let parsed = parse_get_gj_levels_response_paged(&response).unwrap();
+    let expected = parse_get_gj_levels_response_paged(GET_GJ_LEVELS_RESPONSE).unwrap();
+
+    assert_eq!(parsed.total, expected.total);
+    assert_eq!(
+        parsed
+            .items
+            .iter()
+            .map(|level| (level.level_id, level.creator.is_some(), level.custom_song.is_some()))
+            .collect::<Vec<_>>(),
+        expected
+            .items
+            .iter()
+            .map(|level| (level.level_id, level.creator.is_some(), level.custom_song.is_some()))
+            .collect::<Vec<_>>()
+    );
+
+    let level: Level = Level::from_gj_str(include_str!("artifacts/level/897837/raw").trim()).unwrap();
+    let response = download_gj_level_response(&level).unwrap();
+
+    assert_eq!(parse_download_gj_level_response_checked(&response).unwrap().level_id, 897837);
+
+    let profile = Profile::from_gj_str(include_str!("artifacts/profile/8451/raw").trim()).unwrap();
+    let response = get_gj_user_info_response(&profile).unwrap();
+
+    assert_eq!(parse_get_gj_user_info_response(&response).unwrap(), profile);
+}
@@ -1,18 +1,20 @@
+#![cfg(feature = "snapshot")]
+
 use dash_rs::model::user::searched::SearchedUser;
-use framework::load_test_units;
+use framework::{load_fixtures, Snapshot};
 use std::path::Path;
 
 mod framework;
 
 enum SearchedUserTester {}
 
-impl framework::Testable for SearchedUserTester {
+impl Snapshot for SearchedUserTester {
     type Target<'a> = SearchedUser<'a>;
 }
 
 #[test]
 fn test_searched_user() {
-    let units = load_test_units::<SearchedUserTester>(
+    let units = load_fixtures::<SearchedUserTester>(
         Path::new(env!("CARGO_MANIFEST_DIR"))
             .join("tests")
             .join("artifacts")
@@ -22,8 +24,8 @@ fn test_searched_user() {
     for (path, unit) in units {
         println!("Testing case {:?}", path);
 
-        unit.test_consistency();
-        unit.test_load_save_roundtrip();
-        unit.test_save_load_roundtrip();
+        unit.assert_snapshot();
+        unit.assert_load_save_roundtrip();
+        unit.assert_save_load_roundtrip();
     }
 }
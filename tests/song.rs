@@ -1,12 +1,19 @@
-use dash_rs::model::song::NewgroundsSong;
-use framework::load_test_units;
+use dash_rs::{
+    model::song::{NewgroundsSong, SongLink},
+    GJFormat, ProcessError, Thunk,
+};
+#[cfg(feature = "snapshot")]
+use framework::{load_fixtures, Snapshot};
+#[cfg(feature = "snapshot")]
 use std::path::Path;
 
 mod framework;
 
+#[cfg(feature = "snapshot")]
 enum NewgroundsSongTester {}
 
-impl framework::Testable for NewgroundsSongTester {
+#[cfg(feature = "snapshot")]
+impl Snapshot for NewgroundsSongTester {
     type Target<'a> = NewgroundsSong<'a>;
 
     fn canonicalize(target: &mut Self::Target<'_>) {
@@ -15,14 +22,58 @@ impl framework::Testable for NewgroundsSongTester {
 }
 
 #[test]
+#[cfg(feature = "snapshot")]
 fn test_newgrounds_song() {
-    let units = load_test_units::<NewgroundsSongTester>(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("artifacts").join("song"));
+    let units = load_fixtures::<NewgroundsSongTester>(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("artifacts").join("song"));
 
     for (path, unit) in units {
         println!("Testing case {:?}", path);
 
-        unit.test_consistency();
-        unit.test_load_save_roundtrip();
-        unit.test_save_load_roundtrip();
+        unit.assert_snapshot();
+        unit.assert_load_save_roundtrip();
+        unit.assert_save_load_roundtrip();
+    }
+}
+
+#[test]
+fn test_song_link() {
+    let raw = "1~|~444085~|~2~|~Dark Angel - F-777~|~3~|~286~|~4~|~F-777~|~5~|~8.96~|~6~|~~|~7~|~~|~8~|~1~|~10~|~http%3a%2f%2Faudio.ngfiles.com%2F444000%2F444085_Dark%20Angel.mp3";
+    let mut song = NewgroundsSong::from_gj_str(raw).unwrap();
+    let link = song.link.process().unwrap();
+
+    assert_eq!(link.as_str(), "http://audio.ngfiles.com/444000/444085_Dark Angel.mp3");
+    assert_eq!(link.scheme(), "http");
+    assert_eq!(link.host(), "audio.ngfiles.com");
+    assert_eq!(link.path(), "/444000/444085_Dark Angel.mp3");
+    assert!(link.is_newgrounds());
+
+    // Processed links are re-encoded exactly as they were received
+    let mut bytes = Vec::new();
+    song.write_gj(&mut bytes).unwrap();
+
+    assert_eq!(String::from_utf8(bytes).unwrap(), raw);
+
+    // Newly constructed links are encoded like the official servers do
+    let link = SongLink::new("https://example.com:8080/song.mp3?v=2").unwrap();
+
+    assert_eq!(
+        Thunk::<SongLink>::Processed(link.clone()).as_unprocessed().unwrap(),
+        "https%3A%2F%2Fexample.com%3A8080%2Fsong.mp3%3Fv=2"
+    );
+    assert_eq!(link.host(), "example.com:8080");
+    assert!(!link.is_newgrounds());
+
+    for invalid in [
+        "",
+        "audio.ngfiles.com/song.mp3",
+        "ftp://audio.ngfiles.com/",
+        "https:///song.mp3",
+        "https://a..b/",
+        "https://a b/",
+        "https://host:port/",
+    ]
+    .iter()
+    {
+        assert!(matches!(SongLink::new(*invalid), Err(ProcessError::InvalidUrl(_))), "{:?}", invalid);
     }
 }
@@ -0,0 +1,66 @@
+use dash_rs::{
+    model::{level::Level, song::NewgroundsSong, user::profile::Profile},
+    GJFormat,
+};
+
+const SONG: &str = "1~|~771277~|~2~|~Random Song~|~3~|~50531~|~4~|~Artist~|~5~|~8.31~|~9~|~new value~|~6~|~~|~7~|~~|~8~|~1~|~10~|~https%3A%2F%2Faudio.ngfiles.com%2F771000%2F771277_Random-Song.mp3~|~99~|~";
+
+#[test]
+fn unknown_indices_are_preserved() {
+    let song = NewgroundsSong::from_gj_str_preserving(SONG).unwrap();
+
+    assert_eq!(song.unknown_indices.len(), 2);
+    assert_eq!(song.unknown_indices.get("9"), Some("new value"));
+    assert_eq!(song.unknown_indices.get("99"), Some(""));
+
+    // Unknown indices are written back out at their original positions
+    assert_eq!(song.to_robtop_string().unwrap(), SONG);
+    assert_eq!(
+        NewgroundsSong::from_gj_str_preserving(SONG)
+            .unwrap()
+            .into_owned()
+            .to_robtop_string()
+            .unwrap(),
+        SONG
+    );
+
+    let raw = include_str!("artifacts/profile/8451/raw");
+    let raw = format!("{}:1337:leet", raw.trim_end());
+
+    let profile = Profile::from_gj_str_preserving(&raw).unwrap();
+
+    assert_eq!(profile.unknown_indices.get("1337"), Some("leet"));
+    assert_eq!(profile.into_owned().unknown_indices.get("1337"), Some("leet"));
+}
+
+#[test]
+fn unknown_indices_are_dropped_by_default() {
+    let song = NewgroundsSong::from_gj_str(SONG).unwrap();
+
+    assert!(song.unknown_indices.is_empty());
+    assert!(!song.to_robtop_string().unwrap().contains("new value"));
+}
+
+#[test]
+fn unknown_level_indices_are_preserved() {
+    // Level has a hand-written `Dash` implementation
+    let raw = include_str!("artifacts/level/897837/raw")
+        .trim_end()
+        .replacen(":2:time pressure:", ":2:time pressure:1337:leet:", 1);
+
+    let level: Level = Level::from_gj_str_preserving(&raw).unwrap();
+
+    assert_eq!(level.unknown_indices.get("1337"), Some("leet"));
+
+    let serialized = level.to_robtop_string().unwrap();
+
+    assert!(serialized.starts_with("1:897837:2:time pressure:1337:leet:"));
+
+    let reparsed: Level = Level::from_gj_str_preserving(&serialized).unwrap();
+
+    assert_eq!(reparsed.unknown_indices, level.unknown_indices);
+
+    let listed: Level<'_, ()> = Level::from_gj_str_preserving(&raw).unwrap();
+
+    assert_eq!(listed.unknown_indices.get("1337"), Some("leet"));
+}